@@ -36,7 +36,7 @@ pub fn sys_update_endpoint_positions_on_edge_move(
     }
 }
 
-/// Moves the Position of all EdgeVariant to the bounding box of that edge.
+/// Moves the Position of all EdgeVariant to the min of the bounding box of that edge's curve.
 pub fn sys_cleanup_edge_positions_to_bounding_box(
     r_uid_registry: Res<UidRegistry>,
     q_endpoints: Query<(&Endpoint, &Position), Without<Edge>>,
@@ -56,18 +56,7 @@ pub fn sys_cleanup_edge_positions_to_bounding_box(
             continue;
         };
 
-        let mut min = prev_position.0.min(next_position.0);
-
-        match edge_variant {
-            EdgeVariant::Line => (),
-            EdgeVariant::Quadratic { ctrl1 } => {
-                min = min.min(*ctrl1);
-            }
-            EdgeVariant::Cubic { ctrl1, ctrl2 } => {
-                min = min.min(*ctrl1);
-                min = min.min(*ctrl2);
-            }
-        }
+        let (min, _) = edge_variant.bounds(prev_position.0, next_position.0);
 
         if position.0 != min {
             position.0 = min;
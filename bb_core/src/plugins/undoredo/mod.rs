@@ -60,6 +60,8 @@ impl Plugin for UndoRedoPlugin {
             .allow::<StrokeColor>()
             .allow::<FillOptions>()
             .allow::<FillColor>()
+            .allow::<FillGradient>()
+            .allow::<StrokeGradient>()
             // State tags
             .allow::<Selected>()
             .allow::<Selectable>()
@@ -93,6 +95,8 @@ impl Plugin for UndoRedoPlugin {
         app.register_type::<StrokeColor>();
         app.register_type::<FillOptions>();
         app.register_type::<FillColor>();
+        app.register_type::<FillGradient>();
+        app.register_type::<StrokeGradient>();
         // State tags
         app.register_type::<Selected>();
         app.register_type::<Inspected>();
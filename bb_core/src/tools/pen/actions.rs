@@ -12,19 +12,12 @@ use bevy_spts_vectorgraphic::{
 };
 
 use crate::{
-    ecs::{
-        InternalObject, ObjectBundle, ObjectType, Position, ProxiedPosition,
-        ProxiedPositionStrategy, ProxiedUid,
-    },
+    ecs::{InternalObject, ObjectBundle, ObjectType, Position},
     plugins::{
         model_view::{Model, View},
-        selected::{ProxiedHovered, ProxiedSelected, ProxiedVisibility},
         undoredo::{UndoRedoApi, UndoRedoTag},
     },
-    utils::{
-        curve::{cubic_point_at, quadratic_point_at},
-        safe_world_ext::BBSafeWorldExt,
-    },
+    utils::curve::{cubic_point_at, quadratic_point_at},
     views::vector_edge::VectorEdgeVM,
 };
 
@@ -211,47 +204,13 @@ impl PenToolActions {
         })?
     }
 
-    fn spawn_merged_endpoint_if_slots_full(
-        &mut self,
-        world: &mut World,
-        endpoint_uid: Uid,
-    ) -> anyhow::Result<Option<Uid>> {
-        let endpoint_e = endpoint_uid
-            .entity(world)
-            .ok_or_else(|| anyhow!("No entity for endpoint {endpoint_uid}."))?;
-        let endpoint = *world.bb_get::<Endpoint>(endpoint_e)?;
-        let needs_new_endpoint = matches!(
-            (endpoint.prev_edge_entity(), endpoint.next_edge_entity()),
-            (Some(_), Some(_))
-        );
-        if needs_new_endpoint {
-            let position = world
-                .get::<Position>(endpoint_e)
-                .ok_or_else(|| anyhow!("Endpoint to copy doesn't have position."))?;
-            let new_endpoint_uid = self.spawn_new_endpoint(world, **position)?;
-            self.multi_commands.changeset_scope(world, |_, commands| {
-                commands
-                    .entity(new_endpoint_uid)
-                    .insert(ProxiedPosition::new(
-                        endpoint_uid,
-                        ProxiedPositionStrategy::Local,
-                    ))
-                    .insert(ProxiedSelected::new(endpoint_uid, ()))
-                    .insert(ProxiedHovered::new(endpoint_uid, ()))
-                    .insert(ProxiedUid::new(endpoint_uid, ()))
-                    .insert(ProxiedVisibility::new(endpoint_uid, ()));
-            });
-            Ok(Some(new_endpoint_uid))
-        } else {
-            Ok(None)
-        }
-    }
-
+    /// Spawns a line from the endpoint being built from to `to_endpoint_uid`.  Endpoints can
+    /// link any number of edges so both endpoints are reused as is.
     pub fn spawn_edge_to_endpoint(
         &mut self,
         world: &mut World,
         to_endpoint_uid: Uid,
-    ) -> anyhow::Result<(Uid, Option<Uid>)> {
+    ) -> anyhow::Result<Uid> {
         let parent_vector_graphic = self
             .parent_vector_graphic
             .ok_or_else(|| anyhow!("No parent vector graphic."))?;
@@ -259,31 +218,10 @@ impl PenToolActions {
         let from_endpoint_uid = self
             .from_endpoint
             .ok_or_else(|| anyhow!("Not build from and endpoint."))?;
-        let from_endpoint_uid = self
-            .spawn_merged_endpoint_if_slots_full(world, from_endpoint_uid)?
-            .unwrap_or(from_endpoint_uid);
-
-        let new_to_endpoint_uid =
-            self.spawn_merged_endpoint_if_slots_full(world, to_endpoint_uid)?;
-        let to_endpoint_uid = new_to_endpoint_uid.unwrap_or(to_endpoint_uid);
-
-        let endpoint_e = to_endpoint_uid.entity(world).unwrap();
-        let endpoint = *world.get::<Endpoint>(endpoint_e).unwrap();
 
         self.changeset_scope(world, |_, commands| {
-            let mut new_edge_commands =
-                match (endpoint.prev_edge_entity(), endpoint.next_edge_entity()) {
-                    // SAFETY: Checked above by `needs_new_endpoint`
-                    (Some(_), Some(_)) => unreachable!(),
-                    (None, None) | (None, Some(_)) => {
-                        commands.spawn_edge(EdgeVariant::Line, from_endpoint_uid, to_endpoint_uid)
-                    }
-                    (Some(_), None) => {
-                        commands.spawn_edge(EdgeVariant::Line, to_endpoint_uid, from_endpoint_uid)
-                    }
-                };
-
-            let uid = new_edge_commands
+            let uid = commands
+                .spawn_edge(EdgeVariant::Line, from_endpoint_uid, to_endpoint_uid)
                 .insert((
                     Name::from("Edge"),
                     ObjectBundle::new(ObjectType::VectorEdge),
@@ -294,7 +232,7 @@ impl PenToolActions {
                 .set_parent(parent_vector_graphic)
                 .uid();
 
-            Ok((uid, new_to_endpoint_uid))
+            Ok(uid)
         })?
     }
 
@@ -344,7 +344,7 @@ fn handle_pen_tool_event(
             let hits = SelectableRaycaster::raycast_uncached::<Selectable>(world, *screen_pos);
             match hits.top() {
                 Some(SelectableHit(_, endpoint_uid, ObjectType::VectorEndpoint, _)) => {
-                    let (edge_uid, maybe_new_endpoint_uid) = actions
+                    let edge_uid = actions
                         .spawn_edge_to_endpoint(world, *endpoint_uid)
                         .unwrap();
                     actions
@@ -353,9 +353,6 @@ fn handle_pen_tool_event(
                     actions
                         .changeset_scope(world, |_, commands| {
                             commands.entity(edge_uid).insert(VectorEdgeVM);
-                            if let Some(new_endpoint_uid) = maybe_new_endpoint_uid {
-                                commands.entity(new_endpoint_uid).insert(VectorEndpointVM);
-                            }
                         })
                         .unwrap();
                 }
@@ -367,8 +364,7 @@ fn handle_pen_tool_event(
                             commands.entity(endpoint_uid).insert(VectorEndpointVM);
                         })
                         .unwrap();
-                    let (edge_uid, _) =
-                        actions.spawn_edge_to_endpoint(world, endpoint_uid).unwrap();
+                    let edge_uid = actions.spawn_edge_to_endpoint(world, endpoint_uid).unwrap();
                     actions
                         .set_building_from_endpoint_tag(world, endpoint_uid)
                         .unwrap();
@@ -385,8 +381,7 @@ fn handle_pen_tool_event(
                             commands.entity(endpoint_uid).insert(VectorEndpointVM);
                         })
                         .unwrap();
-                    let (edge_uid, _) =
-                        actions.spawn_edge_to_endpoint(world, endpoint_uid).unwrap();
+                    let edge_uid = actions.spawn_edge_to_endpoint(world, endpoint_uid).unwrap();
                     actions
                         .set_building_from_endpoint_tag(world, endpoint_uid)
                         .unwrap();
@@ -319,23 +319,13 @@ pub fn sys_update_vector_edge_vm_mesh_when_endpoint_move(
     let mut updated_edges: HashSet<Entity> = HashSet::new();
 
     for moved_endpoint in q_moved_endpoints.iter() {
-        let next_edge = moved_endpoint
-            .next_edge_entity()
-            .map(|uid| r_uid_registry.entity(uid))
-            .and_then(|e| q_edge.get(e).ok());
-        let next_stale_edge = next_edge.filter(|(e, _)| !updated_edges.contains(e));
-        if let Some((model_entity, model)) = next_stale_edge {
-            updated_edges.insert(model_entity);
-            update_vector_edge_mesh(world, model_entity, model.view().entity(), &mut commands);
-        }
-        let prev_edge = moved_endpoint
-            .prev_edge_entity()
-            .map(|uid| r_uid_registry.entity(uid))
-            .and_then(|e| q_edge.get(e).ok());
-        let prev_stale_edge = prev_edge.filter(|(e, _)| !updated_edges.contains(e));
-        if let Some((model_entity, model)) = prev_stale_edge {
-            updated_edges.insert(model_entity);
-            update_vector_edge_mesh(world, model_entity, model.view().entity(), &mut commands);
+        for edge_uid in moved_endpoint.edges() {
+            let edge = q_edge.get(r_uid_registry.entity(edge_uid)).ok();
+            let stale_edge = edge.filter(|(e, _)| !updated_edges.contains(e));
+            if let Some((model_entity, model)) = stale_edge {
+                updated_edges.insert(model_entity);
+                update_vector_edge_mesh(world, model_entity, model.view().entity(), &mut commands);
+            }
         }
     }
 }
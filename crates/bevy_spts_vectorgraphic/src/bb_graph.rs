@@ -0,0 +1,260 @@
+//! Conversion between the `VectorGraphic` entity hierarchy and `bb_vector_network::BBGraph`, so
+//! the graph algorithms (regions, boolean ops, offsetting...) can run on editor objects.
+//!
+//! Endpoints map to nodes and edges map to edges, an edge runs from its `prev_endpoint` to its
+//! `next_endpoint`.  The [BBGraphUidMap] returned by each conversion relates the two.
+
+use bb_vector_network::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_spts_uid::Uid;
+
+use crate::components::{Edge, EdgeVariant, Endpoint, VectorGraphic};
+
+#[derive(thiserror::Error, Debug)]
+pub enum BBGraphBridgeError {
+    #[error("Entity {0:?} does not have a VectorGraphic component.")]
+    NotAVectorGraphic(Entity),
+    #[error(
+        "Edge {edge} references endpoint {endpoint} which is not a child of the VectorGraphic."
+    )]
+    MissingEndpoint { edge: Uid, endpoint: Uid },
+    #[error(transparent)]
+    Graph(#[from] BBError),
+}
+
+/// Relates the `Uid`s of endpoint / edge entities to the indices of a [BBGraph].
+#[derive(Debug, Clone, Default)]
+pub struct BBGraphUidMap {
+    node_indices: HashMap<Uid, BBNodeIndex>,
+    endpoint_uids: HashMap<BBNodeIndex, Uid>,
+    edge_indices: HashMap<Uid, BBEdgeIndex>,
+    edge_uids: HashMap<BBEdgeIndex, Uid>,
+}
+
+impl BBGraphUidMap {
+    pub fn insert_endpoint(&mut self, uid: Uid, node_idx: BBNodeIndex) {
+        self.node_indices.insert(uid, node_idx);
+        self.endpoint_uids.insert(node_idx, uid);
+    }
+    pub fn insert_edge(&mut self, uid: Uid, edge_idx: BBEdgeIndex) {
+        self.edge_indices.insert(uid, edge_idx);
+        self.edge_uids.insert(edge_idx, uid);
+    }
+
+    pub fn node_idx(&self, endpoint_uid: Uid) -> Option<BBNodeIndex> {
+        self.node_indices.get(&endpoint_uid).copied()
+    }
+    pub fn endpoint_uid(&self, node_idx: BBNodeIndex) -> Option<Uid> {
+        self.endpoint_uids.get(&node_idx).copied()
+    }
+    pub fn edge_idx(&self, edge_uid: Uid) -> Option<BBEdgeIndex> {
+        self.edge_indices.get(&edge_uid).copied()
+    }
+    pub fn edge_uid(&self, edge_idx: BBEdgeIndex) -> Option<Uid> {
+        self.edge_uids.get(&edge_idx).copied()
+    }
+
+    pub fn endpoints_len(&self) -> usize {
+        self.node_indices.len()
+    }
+    pub fn edges_len(&self) -> usize {
+        self.edge_indices.len()
+    }
+}
+
+/// Builds a [BBGraph] snapshot of the `Endpoint` and `Edge` children of a `VectorGraphic` entity,
+/// with its regions calculated.  Endpoint positions are read from their `Transform`.
+///
+/// * `world`:
+/// * `vector_graphic`: The entity with the `VectorGraphic` component
+pub fn vector_graphic_to_bb_graph(
+    world: &World,
+    vector_graphic: Entity,
+) -> Result<(BBGraph, BBGraphUidMap), BBGraphBridgeError> {
+    let (mut graph, map) = bb_graph_without_regions(world, vector_graphic)?;
+    graph.update_regions()?;
+    Ok((graph, map))
+}
+
+/// Returns the (min, max) of the tight bounding box of a `VectorGraphic`'s endpoints and edge
+/// curves, in the local space of the `VectorGraphic`.  Returns `None` if it has no endpoints.
+///
+/// * `world`:
+/// * `vector_graphic`: The entity with the `VectorGraphic` component
+pub fn vector_graphic_bounds(
+    world: &World,
+    vector_graphic: Entity,
+) -> Result<Option<(Vec2, Vec2)>, BBGraphBridgeError> {
+    let (graph, _) = bb_graph_without_regions(world, vector_graphic)?;
+    Ok(graph.bounds().map(|(min, max)| {
+        (
+            Vec2::from_array(min.to_array()),
+            Vec2::from_array(max.to_array()),
+        )
+    }))
+}
+
+/// Returns the convex hull of a `VectorGraphic`'s endpoints and edge curves, in the local space
+/// of the `VectorGraphic`.  See [BBGraph::convex_hull].
+///
+/// * `world`:
+/// * `vector_graphic`: The entity with the `VectorGraphic` component
+pub fn vector_graphic_convex_hull(
+    world: &World,
+    vector_graphic: Entity,
+) -> Result<Vec<Vec2>, BBGraphBridgeError> {
+    let (graph, _) = bb_graph_without_regions(world, vector_graphic)?;
+    Ok(graph
+        .convex_hull()
+        .into_iter()
+        .map(|p| Vec2::from_array(p.to_array()))
+        .collect())
+}
+
+/// Builds a [BBGraph] snapshot of a `VectorGraphic` entity without calculating its regions.
+fn bb_graph_without_regions(
+    world: &World,
+    vector_graphic: Entity,
+) -> Result<(BBGraph, BBGraphUidMap), BBGraphBridgeError> {
+    if world.get::<VectorGraphic>(vector_graphic).is_none() {
+        return Err(BBGraphBridgeError::NotAVectorGraphic(vector_graphic));
+    }
+
+    let mut endpoints = vec![];
+    let mut edges = vec![];
+    for child in world
+        .get::<Children>(vector_graphic)
+        .map(|children| children.iter())
+        .into_iter()
+        .flatten()
+    {
+        let Some(uid) = world.get::<Uid>(*child) else {
+            continue;
+        };
+        if world.get::<Endpoint>(*child).is_some() {
+            let position = world
+                .get::<Transform>(*child)
+                .map(|transform| transform.translation.xy())
+                .unwrap_or_default();
+            endpoints.push((*uid, position));
+        } else if let (Some(edge), Some(edge_variant)) =
+            (world.get::<Edge>(*child), world.get::<EdgeVariant>(*child))
+        {
+            edges.push((*uid, *edge, *edge_variant));
+        }
+    }
+
+    // Goes through a BBDocument so endpoints without edges and single edge loops are kept.
+    let endpoint_ids: HashMap<_, _> = endpoints
+        .iter()
+        .enumerate()
+        .map(|(id, (uid, _))| (*uid, id as u32))
+        .collect();
+    let endpoint_id = |edge: Uid, endpoint: Uid| {
+        endpoint_ids
+            .get(&endpoint)
+            .copied()
+            .ok_or(BBGraphBridgeError::MissingEndpoint { edge, endpoint })
+    };
+
+    let mut document_edges = Vec::with_capacity(edges.len());
+    for (uid, edge, edge_variant) in &edges {
+        let start = endpoint_id(*uid, edge.prev_endpoint_uid())?;
+        let end = endpoint_id(*uid, edge.next_endpoint_uid())?;
+        document_edges.push(match edge_variant {
+            EdgeVariant::Line => BBDocumentEdge::Line { start, end },
+            EdgeVariant::Quadratic { ctrl1 } => BBDocumentEdge::Quadratic {
+                start,
+                ctrl1: ctrl1.to_array(),
+                end,
+            },
+            EdgeVariant::Cubic { ctrl1, ctrl2 } => BBDocumentEdge::Cubic {
+                start,
+                ctrl1: ctrl1.to_array(),
+                ctrl2: ctrl2.to_array(),
+                end,
+            },
+        });
+    }
+
+    let document = BBDocument {
+        version: BB_DOCUMENT_VERSION,
+        nodes: endpoints
+            .iter()
+            .map(|(_, position)| position.to_array())
+            .collect(),
+        edges: document_edges,
+        regions: vec![],
+    };
+    let (graph, indices) = BBGraph::from_document_with_indices(&document)?;
+
+    let mut map = BBGraphUidMap::default();
+    for ((uid, _), node_idx) in endpoints.iter().zip(indices.nodes) {
+        map.insert_endpoint(*uid, node_idx);
+    }
+    for ((uid, _, _), edge_idx) in edges.iter().zip(indices.edges) {
+        map.insert_edge(*uid, edge_idx);
+    }
+
+    Ok((graph, map))
+}
+
+/// Adds changes to `commands` that recreate `graph` as `Endpoint` and `Edge` entities parented to
+/// the `parent` VectorGraphic.  Edges keep the direction they have in the graph.
+///
+/// Returns the `Uid`s of the entities that will be spawned, use them to insert any other
+/// components.
+///
+/// * `commands`:
+/// * `graph`: The graph to recreate
+/// * `parent`: Uid of the VectorGraphic entity
+#[cfg(feature = "changeset")]
+pub fn spawn_bb_graph(
+    commands: &mut bevy_spts_changeset::builder::ChangesetCommands,
+    graph: &BBGraph,
+    parent: Uid,
+) -> Result<BBGraphUidMap, BBGraphBridgeError> {
+    use crate::changeset::VectorGraphicChangesetExt;
+
+    let mut node_indices: Vec<_> = graph.nodes.keys().copied().collect();
+    node_indices.sort();
+
+    let mut map = BBGraphUidMap::default();
+    for node_idx in &node_indices {
+        let position = graph.node(*node_idx)?.position();
+        let uid = commands
+            .spawn((
+                Transform::from_xyz(position.x, position.y, 0.),
+                Endpoint::default(),
+            ))
+            .set_parent(parent)
+            .uid();
+        map.insert_endpoint(uid, *node_idx);
+    }
+
+    let mut edge_indices: Vec<_> = graph.edges.keys().copied().collect();
+    edge_indices.sort();
+    for edge_idx in edge_indices {
+        let edge = *graph.edge(edge_idx)?;
+        let edge_variant = match edge {
+            BBEdge::Line { .. } => EdgeVariant::Line,
+            BBEdge::Quadratic { ctrl1, .. } => EdgeVariant::Quadratic {
+                ctrl1: Vec2::from_array(ctrl1.to_array()),
+            },
+            BBEdge::Cubic { ctrl1, ctrl2, .. } => EdgeVariant::Cubic {
+                ctrl1: Vec2::from_array(ctrl1.to_array()),
+                ctrl2: Vec2::from_array(ctrl2.to_array()),
+            },
+        };
+        // Every node was given an endpoint above.
+        let prev_endpoint = map.endpoint_uid(edge.start_idx()).unwrap();
+        let next_endpoint = map.endpoint_uid(edge.end_idx()).unwrap();
+        let uid = commands
+            .spawn_edge(edge_variant, prev_endpoint, next_endpoint)
+            .set_parent(parent)
+            .uid();
+        map.insert_edge(uid, edge_idx);
+    }
+
+    Ok(map)
+}
@@ -27,12 +27,12 @@ impl Change for LinkEdgeChange {
         let prev_endpoint_e = reg.get_entity(self.prev_endpoint)?;
 
         if let Some(mut endpoint) = world.get_mut::<Endpoint>(next_endpoint_e) {
-            endpoint.prev_edge = Some(self.edge);
+            endpoint.link_prev_edge(&self.edge);
         } else {
             return Err(anyhow!("LinkEdgeCommand: Attempted to get Endpoint component on entity {:?}, but none found.", self.next_endpoint));
         };
         if let Some(mut endpoint) = world.get_mut::<Endpoint>(prev_endpoint_e) {
-            endpoint.next_edge = Some(self.edge);
+            endpoint.link_next_edge(&self.edge);
         } else {
             return Err(anyhow!("LinkEdgeCommand: Attempted to get Endpoint component on entity {:?}, but none found.", self.prev_endpoint));
         };
@@ -76,13 +76,14 @@ impl Change for UnlinkEdgeChange {
         let next_endpoint_e = reg.get_entity(edge.next_endpoint)?;
         let prev_endpoint_e = reg.get_entity(edge.prev_endpoint)?;
 
+        // Unlinking from either endpoint removes both links of an edge that loops back to it.
         if let Some(mut endpoint) = world.get_mut::<Endpoint>(next_endpoint_e) {
-            endpoint.prev_edge = None;
+            let _ = endpoint.unlink_edge(&self.edge);
         } else {
             return Err(anyhow!("LinkEdgeCommand: Attempted to get Endpoint component on entity {:?}, but none found.", edge.next_endpoint));
         };
         if let Some(mut endpoint) = world.get_mut::<Endpoint>(prev_endpoint_e) {
-            endpoint.next_edge = None;
+            let _ = endpoint.unlink_edge(&self.edge);
         } else {
             return Err(anyhow!("LinkEdgeCommand: Attempted to get Endpoint component on entity {:?}, but none found.", edge.prev_endpoint));
         };
@@ -33,6 +33,17 @@ impl Command for LinkEdgeCommand {
         let next_endpoint_e = reg.entity(next_endpoint_uid);
         let prev_endpoint_e = reg.entity(prev_endpoint_uid);
 
+        // Edges that loop back to the same endpoint can't borrow it twice.
+        if prev_endpoint_e == next_endpoint_e {
+            if let Some(mut endpoint) = world.get_mut::<Endpoint>(prev_endpoint_e) {
+                endpoint.link_next_edge(&edge);
+                endpoint.link_prev_edge(&edge);
+            } else {
+                warn!("LinkEdgeCommand: Tried to link edge to endpoint {prev_endpoint_uid} but couldn't get `Endpoint` component.")
+            }
+            return;
+        }
+
         let endpoints = world
             .get_many_entities_mut([prev_endpoint_e, next_endpoint_e])
             .map(|[prev, next]| (prev, next));
@@ -49,17 +60,8 @@ impl Command for LinkEdgeCommand {
         let endpoints = (prev.get_mut::<Endpoint>(), next.get_mut::<Endpoint>());
 
         if let (Some(mut prev_endpoint), Some(mut next_endpoint)) = endpoints {
-            if !prev_endpoint.can_link_edge() {
-                warn!("LinkEdgeCommand: Tried to link to endpoint {prev_endpoint_uid} but all link slots are full.");
-                return;
-            }
-            if !next_endpoint.can_link_edge() {
-                warn!("LinkEdgeCommand: Tried to link to endpoint {next_endpoint_uid} but all link slots are full.");
-                return;
-            }
-
-            prev_endpoint.link_edge(&edge).unwrap();
-            next_endpoint.link_edge(&edge).unwrap();
+            prev_endpoint.link_next_edge(&edge);
+            next_endpoint.link_prev_edge(&edge);
         } else {
             warn!("LinkEdgeCommand: Tried to link edge between endpoints {prev_endpoint_uid} and {next_endpoint_uid} but couldn't get `Endpoint` components.")
         }
@@ -141,6 +143,9 @@ impl VectorGraphicCommandsExt for Commands<'_, '_> {
                 )
             });
         endpoint.unlink_edge(&edge_uid).unwrap();
+        if edge.prev_endpoint == edge.next_endpoint {
+            return;
+        }
 
         let mut endpoint = q_endpoints
             .get_mut(reg.entity(edge.prev_endpoint))
@@ -208,24 +213,16 @@ impl VectorGraphicWorldExt for World {
             .get::<Edge>(reg.entity(edge_uid))
             .unwrap_or_else(|| panic!("Could not get edge to despawn {edge_uid:?}"));
 
-        let mut endpoint = self
-            .get_mut::<Endpoint>(reg.entity(edge.next_endpoint))
-            .unwrap_or_else(|| {
-                panic!(
-                    "Could not get endpoint of edge ({edge_uid:?}) to despawn {:?}",
-                    edge.next_endpoint
-                )
-            });
-        endpoint.prev_edge = None;
-
-        let mut endpoint = self
-            .get_mut::<Endpoint>(reg.entity(edge.prev_endpoint))
-            .unwrap_or_else(|| {
-                panic!(
-                    "Could not get endpoint of edge ({edge_uid:?}) to despawn {:?}",
-                    edge.prev_endpoint
-                )
-            });
-        endpoint.next_edge = None
+        for endpoint_uid in [edge.next_endpoint, edge.prev_endpoint] {
+            let mut endpoint = self
+                .get_mut::<Endpoint>(reg.entity(endpoint_uid))
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Could not get endpoint of edge ({edge_uid:?}) to despawn {endpoint_uid:?}"
+                    )
+                });
+            // Already unlinked if the edge loops back to the same endpoint.
+            let _ = endpoint.unlink_edge(&edge_uid);
+        }
     }
 }
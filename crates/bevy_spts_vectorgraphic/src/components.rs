@@ -15,76 +15,85 @@ pub struct RemoveEdgeError {
     pub uid: Uid,
 }
 
-#[derive(thiserror::Error, Debug)]
-#[error("Tried to link new edge {tried_to_link} but endpoint already linked to two edges ({prev_edge}, {next_edge})")]
-pub struct LinkEdgeError {
-    pub tried_to_link: Uid,
-    pub prev_edge: Uid,
-    pub next_edge: Uid,
-}
-
-#[derive(Component, Clone, Copy, Default, Debug)]
+/// An endpoint can link any number of edges, making the edges and endpoints of a VectorGraphic a
+/// planar graph.  Fills are calculated from the faces of this graph.
+#[derive(Component, Clone, Default, Debug)]
 #[allow(dead_code)]
 #[derive(Reflect)]
 #[reflect(Component)]
 pub struct Endpoint {
-    /// Previous edge in loop
-    pub(crate) next_edge: Option<Uid>,
-    /// Next edge in loop
-    pub(crate) prev_edge: Option<Uid>,
+    /// Edges that leave this endpoint, this endpoint is their `prev_endpoint`.
+    pub(crate) next_edges: Vec<Uid>,
+    /// Edges that arrive at this endpoint, this endpoint is their `next_endpoint`.
+    pub(crate) prev_edges: Vec<Uid>,
 }
 impl Endpoint {
     pub fn with_next_edge(mut self, next_edge: Uid) -> Self {
-        self.next_edge = Some(next_edge);
+        self.next_edges.push(next_edge);
         self
     }
     pub fn with_prev_edge(mut self, prev_edge: Uid) -> Self {
-        self.prev_edge = Some(prev_edge);
+        self.prev_edges.push(prev_edge);
         self
     }
 
+    /// Returns the first edge that leaves this endpoint.
     pub fn next_edge_entity(&self) -> Option<Uid> {
-        self.next_edge
+        self.next_edges.first().copied()
     }
     pub fn next_edge(
         &self,
         q_edges: &mut QueryLens<&Edge>,
         reg: &mut UidRegistry,
     ) -> Option<Result<Edge, QueryEntityError>> {
-        self.next_edge.map(|uid| {
+        self.next_edge_entity().map(|uid| {
             let q_edges = q_edges.query();
             let entity = reg.entity(uid);
             q_edges.get(entity).copied()
         })
     }
+    /// Returns the first edge that arrives at this endpoint.
     pub fn prev_edge_entity(&self) -> Option<Uid> {
-        self.prev_edge
+        self.prev_edges.first().copied()
     }
     pub fn prev_edge(
         &self,
         q_edges: &mut QueryLens<&Edge>,
         reg: &mut UidRegistry,
     ) -> Option<Result<Edge, QueryEntityError>> {
-        self.prev_edge.map(|uid| {
+        self.prev_edge_entity().map(|uid| {
             let q_edges = q_edges.query();
             let entity = reg.entity(uid);
             q_edges.get(entity).copied()
         })
     }
 
-    /// Returns the other edge when given the Uid of one edge.
+    pub fn next_edges(&self) -> &[Uid] {
+        &self.next_edges
+    }
+    pub fn prev_edges(&self) -> &[Uid] {
+        &self.prev_edges
+    }
+    /// Returns every edge linked to this endpoint, edges that loop back to this endpoint are
+    /// returned twice.
+    pub fn edges(&self) -> impl Iterator<Item = Uid> + '_ {
+        self.prev_edges.iter().chain(self.next_edges.iter()).copied()
+    }
+    /// Number of edges linked to this endpoint, edges that loop back to this endpoint count
+    /// twice.
+    pub fn edge_count(&self) -> usize {
+        self.prev_edges.len() + self.next_edges.len()
+    }
+
+    /// Returns the other edge when given the Uid of one edge.  Only endpoints that link exactly
+    /// two edges have another edge.
     pub fn other_edge_uid(&self, edge_uid: &Uid) -> Option<&Uid> {
-        if let Some(edge) = self.next_edge {
-            if edge == *edge_uid {
-                return self.prev_edge.as_ref();
-            }
+        let mut edges = self.prev_edges.iter().chain(self.next_edges.iter());
+        match (edges.next(), edges.next(), edges.next()) {
+            (Some(a), Some(b), None) if a == edge_uid => Some(b),
+            (Some(a), Some(b), None) if b == edge_uid => Some(a),
+            _ => None,
         }
-        if let Some(edge) = self.prev_edge {
-            if edge == *edge_uid {
-                return self.next_edge.as_ref();
-            }
-        }
-        None
     }
     pub fn other_edge(
         &self,
@@ -98,42 +107,26 @@ impl Endpoint {
         Some(q_edges.get(entity).copied())
     }
 
-    pub fn can_link_edge(&mut self) -> bool {
-        self.prev_edge.is_none() || self.next_edge.is_none()
+    /// Links an edge that leaves this endpoint.
+    pub fn link_next_edge(&mut self, edge_uid: &Uid) {
+        if !self.next_edges.contains(edge_uid) {
+            self.next_edges.push(*edge_uid);
+        }
     }
-
-    pub fn link_edge(&mut self, edge_uid: &Uid) -> Result<(), LinkEdgeError> {
-        if self.next_edge.is_none() {
-            self.next_edge = Some(*edge_uid);
-            Ok(())
-        } else if self.prev_edge.is_none() {
-            self.prev_edge = Some(*edge_uid);
-            Ok(())
-        } else {
-            Err(LinkEdgeError {
-                tried_to_link: *edge_uid,
-                prev_edge: self.prev_edge.unwrap(),
-                next_edge: self.next_edge.unwrap(),
-            })
+    /// Links an edge that arrives at this endpoint.
+    pub fn link_prev_edge(&mut self, edge_uid: &Uid) {
+        if !self.prev_edges.contains(edge_uid) {
+            self.prev_edges.push(*edge_uid);
         }
     }
 
+    /// Unlinks an edge from this endpoint, in either direction.
     pub fn unlink_edge(&mut self, edge_uid: &Uid) -> Result<(), RemoveEdgeError> {
-        let mut did_remove = false;
-        if let Some(edge) = self.next_edge {
-            if edge == *edge_uid {
-                self.next_edge = None;
-                did_remove = true;
-            }
-        }
-        if let Some(edge) = self.prev_edge {
-            if edge == *edge_uid {
-                self.prev_edge = None;
-                did_remove = true;
-            }
-        }
+        let count = self.edge_count();
+        self.next_edges.retain(|uid| uid != edge_uid);
+        self.prev_edges.retain(|uid| uid != edge_uid);
 
-        if did_remove {
+        if self.edge_count() < count {
             Ok(())
         } else {
             Err(RemoveEdgeError {
@@ -186,7 +179,7 @@ impl Edge {
         reg: &UidRegistry,
     ) -> Result<Endpoint, QueryEntityError> {
         let entity = reg.entity(self.prev_endpoint);
-        q_endpoints.query().get(entity).copied()
+        q_endpoints.query().get(entity).cloned()
     }
 
     pub fn prev_endpoint_uid(&self) -> Uid {
@@ -199,7 +192,7 @@ impl Edge {
         reg: &UidRegistry,
     ) -> Result<Endpoint, QueryEntityError> {
         let entity = reg.entity(self.next_endpoint);
-        q_endpoints.query().get(entity).copied()
+        q_endpoints.query().get(entity).cloned()
     }
 
     pub fn other_endpoint_uid(&self, endpoint_uid: &Uid) -> Option<Uid> {
@@ -226,6 +219,39 @@ pub enum EdgeVariant {
         ctrl2: Vec2,
     },
 }
+impl EdgeVariant {
+    /// Returns the (start, ctrl1, ctrl2, end) points of the edge as a cubic bezier, quadratics
+    /// are degree elevated.
+    ///
+    /// * `prev_position`: Position of the edge's prev endpoint
+    /// * `next_position`: Position of the edge's next endpoint
+    pub fn cubic_points(&self, prev_position: Vec2, next_position: Vec2) -> [Vec2; 4] {
+        let (start, end) = (prev_position, next_position);
+        match self {
+            EdgeVariant::Line => [start, start, end, end],
+            EdgeVariant::Quadratic { ctrl1 } => [
+                start,
+                start + (*ctrl1 - start) * 2. / 3.,
+                end + (*ctrl1 - end) * 2. / 3.,
+                end,
+            ],
+            EdgeVariant::Cubic { ctrl1, ctrl2 } => [start, *ctrl1, *ctrl2, end],
+        }
+    }
+
+    /// Returns the (min, max) of the tight bounding box of the edge's curve, rather than of its
+    /// control points.
+    ///
+    /// * `prev_position`: Position of the edge's prev endpoint
+    /// * `next_position`: Position of the edge's next endpoint
+    pub fn bounds(&self, prev_position: Vec2, next_position: Vec2) -> (Vec2, Vec2) {
+        let points = self
+            .cubic_points(prev_position, next_position)
+            .map(|p| bb_vector_network::glam::Vec2::from_array(p.to_array()));
+        let (min, max) = bb_vector_network::bb_edge::cubic_bounds(points);
+        (Vec2::from_array(min.to_array()), Vec2::from_array(max.to_array()))
+    }
+}
 
 #[derive(Bundle)]
 pub struct EdgeBundle {
@@ -240,16 +266,15 @@ pub struct VectorGraphic {
     pub edges: HashSet<Entity>,
 }
 
-#[derive(Component, Reflect)]
+/// Stores the path built from the edges of a VectorGraphic.  `path` is stroked and, unless a
+/// separate fill path is set, filled.
+#[derive(Component, Clone, Default, Reflect)]
 #[reflect(Component)]
 pub struct VectorGraphicPathStorage {
     #[reflect(ignore)]
     path: Option<Path>,
-}
-impl Default for VectorGraphicPathStorage {
-    fn default() -> Self {
-        Self { path: None }
-    }
+    #[reflect(ignore)]
+    fill_path: Option<Path>,
 }
 impl VectorGraphicPathStorage {
     pub fn needs_recalculate(&self) -> bool {
@@ -261,15 +286,37 @@ impl VectorGraphicPathStorage {
     pub fn path(&self) -> Option<&Path> {
         self.path.as_ref()
     }
+    pub fn set_fill_path(&mut self, fill_path: Path) {
+        self.fill_path = Some(fill_path);
+    }
+    /// Returns the path to fill, falls back to `path` if no fill path is set.
+    pub fn fill_path(&self) -> Option<&Path> {
+        self.fill_path.as_ref().or(self.path.as_ref())
+    }
     pub fn set_dirty(&mut self) {
         self.path = None;
+        self.fill_path = None;
     }
 }
 
+/// The tessellated fill and stroke of a VectorGraphic, rebuilt on the CPU whenever it's
+/// remeshed.  The render plugin uploads it to the VectorGraphic's `Mesh2dHandle`.
+#[derive(Component, Clone, Default, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VectorGraphicMesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// 0. for vertices of the fill and 1. for vertices of the stroke.
+    pub shape_mix: Vec<f32>,
+    /// Triangle list indices into the vertices.
+    pub indices: Vec<u32>,
+}
+
 #[derive(Bundle, Default)]
 pub struct VectorGraphicBundle {
     pub vector_graphic: VectorGraphic,
     pub path_storage: VectorGraphicPathStorage,
+    pub mesh: VectorGraphicMesh,
     pub stroke_options: StrokeOptions,
     pub fill_options: FillOptions,
 }
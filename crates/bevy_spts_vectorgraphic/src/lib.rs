@@ -11,26 +11,45 @@
 //!    VectorGraphic needs remesh
 //! 3. If `Transform` on entity with `Endpoint` component changes, parent VectorGraphic needs remesh
 //! 4. If `Edge` component changes, parent VectorGraphic needs remesh
-//! 5. Remesh the parent VectorGraphic if necessary.
+//! 5. Remesh the parent VectorGraphic if necessary.  See `RemeshMode` for remeshing on background
+//!    tasks instead of within the frame.
+//! 6. Upload the `VectorGraphicMesh` to the VectorGraphic's `Mesh2dHandle` (render plugin only).
+//!
+//! ## Plugins
+//!
+//! - `VectorGraphicCorePlugin` handles steps 1-5 and doesn't require the render pipeline so it
+//!   can run headless, i.e. with `MinimalPlugins`.
+//! - `VectorGraphicRenderPlugin` (`render` feature) draws VectorGraphics with the
+//!   `VectorGraphicMaterial`.
+//! - `VectorGraphicPlugin` adds both.
 //!
 pub mod commands_ext;
 pub mod components;
 pub mod lyon_components;
+#[cfg(feature = "render")]
 pub mod material;
+mod planar;
+#[cfg(feature = "render")]
+pub mod render;
 pub mod systems;
 mod utils;
 
 #[cfg(feature = "changeset")]
 mod changeset;
+mod bb_graph;
 
 pub mod prelude {
-    pub use super::{VectorGraphicPlugin, VectorGraphicSet};
+    pub use super::{RemeshMode, VectorGraphicCorePlugin, VectorGraphicPlugin, VectorGraphicSet};
     #[cfg(feature = "changeset")]
     pub use crate::changeset::*;
+    pub use crate::bb_graph::*;
     pub use crate::commands_ext;
     pub use crate::components::*;
     pub use crate::lyon_components::*;
+    #[cfg(feature = "render")]
     pub use crate::material::*;
+    #[cfg(feature = "render")]
+    pub use crate::render::*;
     pub use crate::systems::*;
 }
 
@@ -44,23 +63,21 @@ pub mod lyon_path {
 
 use bevy::{
     app::{App, Plugin, PostUpdate},
-    asset::{load_internal_asset, Handle},
     ecs::{schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet}, system::Resource},
     prelude::{Deref, DerefMut},
-    render::render_resource::Shader,
-    sprite::Material2dPlugin,
     transform::TransformSystem,
+    utils::Parallel,
 };
 use systems::{
     sys_add_spawned_edges_to_vector_graphic, sys_add_spawned_endpoints_to_vector_graphic,
-    sys_check_vector_graphic_children_changed, sys_collect_vector_graph_path_endpoints,
-    sys_remesh_vector_graphic, sys_remove_despawned_edges_from_vector_graphic,
-    sys_remove_despawned_endpoints_from_vector_graphic,
+    sys_apply_remesh_tasks, sys_check_vector_graphic_children_changed,
+    sys_collect_vector_graph_path_endpoints, sys_remesh_vector_graphic,
+    sys_remove_despawned_edges_from_vector_graphic,
+    sys_remove_despawned_endpoints_from_vector_graphic, RemeshScratch,
 };
 
-use crate::material::{sys_sync_vector_graphic_material, VectorGraphicMaterial};
-
-pub const SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(3296418235224473622063937256920);
+#[cfg(feature = "render")]
+pub use render::{VectorGraphicRenderPlugin, SHADER_HANDLE};
 
 #[derive(SystemSet, Clone, Debug, Copy, PartialEq, Eq, Hash)]
 pub enum VectorGraphicSet {
@@ -70,27 +87,59 @@ pub enum VectorGraphicSet {
     Remesh,
 }
 
-#[derive(Resource, Deref, DerefMut)]
-pub struct SptsFillTessellator(lyon_tessellation::FillTessellator);
+/// The fill and stroke tessellators used by a single thread.
+pub struct SptsTessellator {
+    pub fill: lyon_tessellation::FillTessellator,
+    pub stroke: lyon_tessellation::StrokeTessellator,
+}
+impl Default for SptsTessellator {
+    fn default() -> Self {
+        Self {
+            fill: lyon_tessellation::FillTessellator::new(),
+            stroke: lyon_tessellation::StrokeTessellator::new(),
+        }
+    }
+}
 
-#[derive(Resource, Deref, DerefMut)]
-pub struct SptsStrokeTessellator(lyon_tessellation::StrokeTessellator);
+/// Thread local tessellators so that VectorGraphics can be remeshed in parallel.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct SptsTessellators(Parallel<SptsTessellator>);
+
+/// Tessellators and scratch buffers that are handed to each `RemeshTask` in
+/// [RemeshMode::Deferred] and taken back once it finishes, so they're reused between tasks.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct RemeshTaskBuffers(Vec<(SptsTessellator, RemeshScratch)>);
+
+/// How changed VectorGraphics are remeshed.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemeshMode {
+    /// Tessellates in parallel on the `ComputeTaskPool`, meshes are updated in the same frame.
+    #[default]
+    Synchronous,
+    /// Tessellates on the `AsyncComputeTaskPool`, meshes are updated in a later frame once the
+    /// task finishes.  Keeps frames short for large documents at the cost of latency.
+    Deferred,
+}
 
+/// Adds the [VectorGraphicCorePlugin] and, with the `render` feature, the
+/// `VectorGraphicRenderPlugin`.
 pub struct VectorGraphicPlugin;
 impl Plugin for VectorGraphicPlugin {
     fn build(&self, app: &mut App) {
-        let fill_tess = lyon_tessellation::FillTessellator::new();
-        let stroke_tess = lyon_tessellation::StrokeTessellator::new();
-        load_internal_asset!(
-            app,
-            SHADER_HANDLE,
-            "./shaders/vector_graphic_material.wgsl",
-            Shader::from_wgsl
-        );
-        app.add_plugins(Material2dPlugin::<VectorGraphicMaterial>::default());
+        app.add_plugins(VectorGraphicCorePlugin);
+        #[cfg(feature = "render")]
+        app.add_plugins(VectorGraphicRenderPlugin);
+    }
+}
 
-        app.insert_resource(SptsFillTessellator(fill_tess))
-            .insert_resource(SptsStrokeTessellator(stroke_tess));
+/// Tracks the topology of VectorGraphics, builds their paths and tessellates them into a
+/// [components::VectorGraphicMesh].  Doesn't depend on the render pipeline.
+pub struct VectorGraphicCorePlugin;
+impl Plugin for VectorGraphicCorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SptsTessellators>()
+            .init_resource::<RemeshTaskBuffers>()
+            .init_resource::<RemeshMode>();
 
         app.configure_sets(
             PostUpdate,
@@ -122,9 +171,9 @@ impl Plugin for VectorGraphicPlugin {
 
         app.add_systems(
             PostUpdate,
-            sys_remesh_vector_graphic.in_set(VectorGraphicSet::Remesh),
+            (sys_remesh_vector_graphic, sys_apply_remesh_tasks)
+                .chain()
+                .in_set(VectorGraphicSet::Remesh),
         );
-
-        app.add_systems(PostUpdate, sys_sync_vector_graphic_material);
     }
 }
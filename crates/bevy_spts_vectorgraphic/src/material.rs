@@ -1,13 +1,16 @@
 use bevy::{
-    asset::{Asset, Assets, Handle}, color::{Color, LinearRgba}, ecs::{
+    asset::{Asset, Assets, Handle}, color::{Color, ColorToComponents, LinearRgba}, ecs::{
         component::Component,
-        query::{Changed, Or},
+        entity::{Entity, EntityHashSet},
+        query::{Changed, Or, With},
         reflect::{ReflectComponent},
+        removal_detection::RemovedComponents,
         system::{Query, ResMut},
-    }, reflect::{std_traits::ReflectDefault, Reflect}, render::{
+    }, math::{Affine2, Mat3, Vec2, Vec4}, reflect::{std_traits::ReflectDefault, Reflect}, render::{
         mesh::{Mesh, MeshVertexAttribute, MeshVertexBufferLayoutRef},
         render_resource::{
-            AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError, VertexFormat,
+            AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+            VertexFormat,
         },
     }, sprite::{Material2d, Material2dKey}
 };
@@ -22,9 +25,18 @@ pub const ATTRIBUTE_SHAPE_MIX: MeshVertexAttribute =
 #[reflect(Default, Debug)]
 pub struct VectorGraphicMaterial {
     #[uniform(0)]
-    fill_color: LinearRgba,
+    fill: VectorGraphicPaint,
     #[uniform(1)]
-    stroke_color: LinearRgba,
+    stroke: VectorGraphicPaint,
+}
+
+impl VectorGraphicMaterial {
+    pub fn fill(&self) -> &VectorGraphicPaint {
+        &self.fill
+    }
+    pub fn stroke(&self) -> &VectorGraphicPaint {
+        &self.stroke
+    }
 }
 
 impl Material2d for VectorGraphicMaterial {
@@ -59,28 +71,324 @@ pub struct FillColor(pub Color);
 #[reflect(Component)]
 pub struct StrokeColor(pub Color);
 
+/// Maximum number of stops the shader supports, must match `MAX_GRADIENT_STOPS` in
+/// `vector_graphic_material.wgsl`.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// A color at an `offset` along a gradient, 0. being the start and 1. the end.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: Color,
+}
+impl GradientStop {
+    pub fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// How a gradient is painted outside of its 0. - 1. range.
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpreadMode {
+    /// Extends the colors of the first and last stops.
+    #[default]
+    Pad,
+    /// Mirrors the gradient back and forth.
+    Reflect,
+    /// Repeats the gradient.
+    Repeat,
+}
+
+/// A gradient along the line from `start` to `end`.  `transform` maps from the gradient's space
+/// to the local space of the VectorGraphic.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub stops: Vec<GradientStop>,
+    pub spread: SpreadMode,
+    pub transform: Affine2,
+}
+impl LinearGradient {
+    pub fn new(start: Vec2, end: Vec2, stops: Vec<GradientStop>) -> Self {
+        Self {
+            start,
+            end,
+            stops,
+            spread: SpreadMode::default(),
+            transform: Affine2::IDENTITY,
+        }
+    }
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+    pub fn with_transform(mut self, transform: Affine2) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+/// A gradient from `center` out to `radius`.  `transform` maps from the gradient's space to the
+/// local space of the VectorGraphic, i.e. a non uniform scale makes the gradient elliptical.
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    pub center: Vec2,
+    pub radius: f32,
+    pub stops: Vec<GradientStop>,
+    pub spread: SpreadMode,
+    pub transform: Affine2,
+}
+impl RadialGradient {
+    pub fn new(center: Vec2, radius: f32, stops: Vec<GradientStop>) -> Self {
+        Self {
+            center,
+            radius,
+            stops,
+            spread: SpreadMode::default(),
+            transform: Affine2::IDENTITY,
+        }
+    }
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+    pub fn with_transform(mut self, transform: Affine2) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub enum Gradient {
+    Linear(LinearGradient),
+    Radial(RadialGradient),
+}
+impl From<LinearGradient> for Gradient {
+    fn from(value: LinearGradient) -> Self {
+        Self::Linear(value)
+    }
+}
+impl From<RadialGradient> for Gradient {
+    fn from(value: RadialGradient) -> Self {
+        Self::Radial(value)
+    }
+}
+
+/// Paints the fill with a gradient, takes priority over the [FillColor].
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct FillGradient(pub Gradient);
+
+/// Paints the stroke with a gradient, takes priority over the [StrokeColor].
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct StrokeGradient(pub Gradient);
+
+const PAINT_SOLID: u32 = 0;
+const PAINT_LINEAR: u32 = 1;
+const PAINT_RADIAL: u32 = 2;
+
+/// The fill or stroke paint of a [VectorGraphicMaterial], laid out as the `Paint` struct in
+/// `vector_graphic_material.wgsl`.
+#[derive(ShaderType, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct VectorGraphicPaint {
+    kind: u32,
+    spread: u32,
+    stop_count: u32,
+    color: Vec4,
+    local_to_gradient: Mat3,
+    /// Start of a linear gradient or center of a radial gradient.
+    start: Vec2,
+    end: Vec2,
+    radius: f32,
+    /// Offsets of the stops packed 4 per element.
+    stop_offsets: [Vec4; MAX_GRADIENT_STOPS / 4],
+    stop_colors: [Vec4; MAX_GRADIENT_STOPS],
+}
+
+impl Default for VectorGraphicPaint {
+    fn default() -> Self {
+        Self::solid(LinearRgba::default())
+    }
+}
+
+impl VectorGraphicPaint {
+    pub fn solid(color: impl Into<LinearRgba>) -> Self {
+        Self {
+            kind: PAINT_SOLID,
+            spread: 0,
+            stop_count: 0,
+            color: color.into().to_vec4(),
+            local_to_gradient: Mat3::IDENTITY,
+            start: Vec2::ZERO,
+            end: Vec2::ZERO,
+            radius: 0.,
+            stop_offsets: [Vec4::ZERO; MAX_GRADIENT_STOPS / 4],
+            stop_colors: [Vec4::ZERO; MAX_GRADIENT_STOPS],
+        }
+    }
+
+    /// Stops are sorted by offset, only the first [MAX_GRADIENT_STOPS] are used.  A gradient
+    /// with a singular transform is painted with the color of its first stop.
+    pub fn gradient(gradient: &Gradient) -> Self {
+        let (kind, start, end, radius, stops, spread, transform) = match gradient {
+            Gradient::Linear(linear) => (
+                PAINT_LINEAR,
+                linear.start,
+                linear.end,
+                0.,
+                &linear.stops,
+                linear.spread,
+                linear.transform,
+            ),
+            Gradient::Radial(radial) => (
+                PAINT_RADIAL,
+                radial.center,
+                radial.center,
+                radial.radius,
+                &radial.stops,
+                radial.spread,
+                radial.transform,
+            ),
+        };
+
+        let mut stops = stops.clone();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        stops.truncate(MAX_GRADIENT_STOPS);
+
+        let local_to_gradient = transform.inverse();
+        // The transform squashes the gradient flat, so there's no way back to gradient space.
+        if !local_to_gradient.is_finite() {
+            return Self::solid(
+                stops
+                    .first()
+                    .map_or(LinearRgba::NONE, |stop| stop.color.into()),
+            );
+        }
+
+        let mut stop_offsets = [0.; MAX_GRADIENT_STOPS];
+        let mut stop_colors = [Vec4::ZERO; MAX_GRADIENT_STOPS];
+        for (i, stop) in stops.iter().enumerate() {
+            stop_offsets[i] = stop.offset;
+            stop_colors[i] = LinearRgba::from(stop.color).to_vec4();
+        }
+
+        Self {
+            kind,
+            spread: match spread {
+                SpreadMode::Pad => 0,
+                SpreadMode::Reflect => 1,
+                SpreadMode::Repeat => 2,
+            },
+            stop_count: stops.len() as u32,
+            color: Vec4::ZERO,
+            local_to_gradient: Mat3::from(local_to_gradient),
+            start,
+            end,
+            radius,
+            stop_offsets: std::array::from_fn(|i| Vec4::from_slice(&stop_offsets[i * 4..])),
+            stop_colors,
+        }
+    }
+
+    fn stop_offset(&self, i: usize) -> f32 {
+        self.stop_offsets[i / 4][i % 4]
+    }
+
+    /// Returns the color at a position in the local space of the VectorGraphic, calculated the
+    /// same way as in the shader.
+    pub fn color_at(&self, local_position: Vec2) -> LinearRgba {
+        if self.kind == PAINT_SOLID {
+            return LinearRgba::from_vec4(self.color);
+        }
+        if self.stop_count == 0 {
+            return LinearRgba::NONE;
+        }
+
+        let position = self.local_to_gradient.transform_point2(local_position);
+        let t = match self.kind {
+            PAINT_LINEAR => {
+                let direction = self.end - self.start;
+                (position - self.start).dot(direction)
+                    / direction.length_squared().max(f32::EPSILON)
+            }
+            _ => position.distance(self.start) / self.radius.max(f32::EPSILON),
+        };
+        let t = match self.spread {
+            0 => t.clamp(0., 1.),
+            1 => 1. - ((t.rem_euclid(2.)) - 1.).abs(),
+            _ => t.rem_euclid(1.),
+        };
+
+        let mut color = self.stop_colors[0];
+        for i in 1..self.stop_count as usize {
+            let (prev, next) = (self.stop_offset(i - 1), self.stop_offset(i));
+            if t >= prev {
+                let mix = ((t - prev) / (next - prev).max(f32::EPSILON)).clamp(0., 1.);
+                color = self.stop_colors[i - 1].lerp(self.stop_colors[i], mix);
+            }
+        }
+        LinearRgba::from_vec4(color)
+    }
+}
+
+/// Syncs the [FillColor], [StrokeColor], [FillGradient] and [StrokeGradient] components to the
+/// VectorGraphic's material.  Gradients take priority over colors, removing a gradient falls back
+/// to the color and removing both resets the paint.
 #[allow(clippy::type_complexity)]
 pub fn sys_sync_vector_graphic_material(
     mut res_vector_graphic_materials: ResMut<Assets<VectorGraphicMaterial>>,
-    q_vector_graphic: Query<
+    q_changed: Query<
+        Entity,
         (
-            &Handle<VectorGraphicMaterial>,
-            Option<&FillColor>,
-            Option<&StrokeColor>,
+            With<Handle<VectorGraphicMaterial>>,
+            Or<(
+                Changed<FillColor>,
+                Changed<StrokeColor>,
+                Changed<FillGradient>,
+                Changed<StrokeGradient>,
+            )>,
         ),
-        Or<(Changed<FillColor>, Changed<StrokeColor>)>
     >,
+    mut r_removed_fill_color: RemovedComponents<FillColor>,
+    mut r_removed_stroke_color: RemovedComponents<StrokeColor>,
+    mut r_removed_fill_gradient: RemovedComponents<FillGradient>,
+    mut r_removed_stroke_gradient: RemovedComponents<StrokeGradient>,
+    q_vector_graphic: Query<(
+        &Handle<VectorGraphicMaterial>,
+        Option<&FillColor>,
+        Option<&StrokeColor>,
+        Option<&FillGradient>,
+        Option<&StrokeGradient>,
+    )>,
 ) {
+    let changed: EntityHashSet = q_changed
+        .iter()
+        .chain(r_removed_fill_color.read())
+        .chain(r_removed_stroke_color.read())
+        .chain(r_removed_fill_gradient.read())
+        .chain(r_removed_stroke_gradient.read())
+        .collect();
 
-    for (handle, fill, stroke) in q_vector_graphic.iter() {
+    for entity in changed {
+        let Ok((handle, fill_color, stroke_color, fill_gradient, stroke_gradient)) =
+            q_vector_graphic.get(entity)
+        else {
+            continue;
+        };
         let Some(mat) = res_vector_graphic_materials.get_mut(handle) else {
             continue;
         };
-        if let Some(fill) = fill {
-            mat.fill_color = fill.0.into();
+        match (fill_gradient, fill_color) {
+            (Some(gradient), _) => mat.fill = VectorGraphicPaint::gradient(&gradient.0),
+            (None, Some(color)) => mat.fill = VectorGraphicPaint::solid(color.0),
+            (None, None) => mat.fill = VectorGraphicPaint::default(),
         }
-        if let Some(stroke) = stroke {
-            mat.stroke_color = stroke.0.into();
+        match (stroke_gradient, stroke_color) {
+            (Some(gradient), _) => mat.stroke = VectorGraphicPaint::gradient(&gradient.0),
+            (None, Some(color)) => mat.stroke = VectorGraphicPaint::solid(color.0),
+            (None, None) => mat.stroke = VectorGraphicPaint::default(),
         }
     }
 }
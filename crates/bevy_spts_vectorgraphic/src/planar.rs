@@ -0,0 +1,280 @@
+//! Builds the paths of a VectorGraphic from its endpoints and edges.  Endpoints can link any
+//! number of edges so the edges form a planar graph, the fill is made from the faces of this
+//! graph and the stroke from the chains of edges between branching endpoints.
+
+use bevy::{
+    math::Vec2,
+    utils::{HashMap, HashSet},
+};
+use bevy_spts_uid::Uid;
+use lyon_tessellation::path::{path::Builder, Path};
+
+use crate::{components::EdgeVariant, utils::ToPoint};
+
+/// Number of segments each edge is split into when calculating the area of a face.
+const AREA_SEGMENTS: usize = 8;
+
+/// An edge from its `prev_endpoint` to its `next_endpoint`, stored as endpoint indices.
+struct PlanarEdge {
+    prev: usize,
+    next: usize,
+    variant: EdgeVariant,
+}
+
+/// An edge walked in one direction, forwards walks from `prev` to `next`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct HalfEdge {
+    edge: usize,
+    forward: bool,
+}
+impl HalfEdge {
+    fn twin(self) -> Self {
+        Self {
+            edge: self.edge,
+            forward: !self.forward,
+        }
+    }
+}
+
+/// A walk of half edges, closed if it ends where it starts.
+struct Walk {
+    half_edges: Vec<HalfEdge>,
+    closed: bool,
+}
+
+pub(crate) struct PlanarGraph {
+    positions: Vec<Vec2>,
+    edges: Vec<PlanarEdge>,
+    /// Half edges leaving each endpoint, sorted counterclockwise by the direction they leave in.
+    outgoing: Vec<Vec<HalfEdge>>,
+}
+
+impl PlanarGraph {
+    /// * `endpoints`: The uid and position of each endpoint
+    /// * `edges`: The prev endpoint, next endpoint and variant of each edge.  Edges that
+    ///   reference a missing endpoint are skipped.
+    pub fn new(
+        endpoints: &[(Uid, Vec2)],
+        edges: impl IntoIterator<Item = (Uid, Uid, EdgeVariant)>,
+    ) -> Self {
+        let indices: HashMap<Uid, usize> = endpoints
+            .iter()
+            .enumerate()
+            .map(|(i, (uid, _))| (*uid, i))
+            .collect();
+
+        let edges: Vec<_> = edges
+            .into_iter()
+            .filter_map(|(prev, next, variant)| {
+                Some(PlanarEdge {
+                    prev: *indices.get(&prev)?,
+                    next: *indices.get(&next)?,
+                    variant,
+                })
+            })
+            .collect();
+
+        let mut graph = Self {
+            positions: endpoints.iter().map(|(_, position)| *position).collect(),
+            edges,
+            outgoing: vec![vec![]; endpoints.len()],
+        };
+        for edge in 0..graph.edges.len() {
+            for forward in [true, false] {
+                let half_edge = HalfEdge { edge, forward };
+                let origin = graph.origin(half_edge);
+                graph.outgoing[origin].push(half_edge);
+            }
+        }
+        for i in 0..graph.outgoing.len() {
+            let mut outgoing = std::mem::take(&mut graph.outgoing[i]);
+            outgoing.sort_by(|a, b| graph.leaving_angle(*a).total_cmp(&graph.leaving_angle(*b)));
+            graph.outgoing[i] = outgoing;
+        }
+        graph
+    }
+
+    fn origin(&self, half_edge: HalfEdge) -> usize {
+        let edge = &self.edges[half_edge.edge];
+        match half_edge.forward {
+            true => edge.prev,
+            false => edge.next,
+        }
+    }
+    fn dest(&self, half_edge: HalfEdge) -> usize {
+        self.origin(half_edge.twin())
+    }
+
+    /// Returns the (start, ctrl1, ctrl2, end) points of the half edge as a cubic bezier.
+    fn cubic_points(&self, half_edge: HalfEdge) -> [Vec2; 4] {
+        let edge = &self.edges[half_edge.edge];
+        let mut points = edge
+            .variant
+            .cubic_points(self.positions[edge.prev], self.positions[edge.next]);
+        if !half_edge.forward {
+            points.reverse();
+        }
+        points
+    }
+
+    /// Angle of the direction the half edge leaves its origin in.
+    fn leaving_angle(&self, half_edge: HalfEdge) -> f32 {
+        let [start, rest @ ..] = self.cubic_points(half_edge);
+        let direction = rest
+            .iter()
+            .map(|p| *p - start)
+            .find(|d| d.length_squared() > f32::EPSILON)
+            .unwrap_or(Vec2::X);
+        direction.y.atan2(direction.x)
+    }
+
+    /// The half edge that follows `half_edge` around the face on its left.
+    fn next_in_face(&self, half_edge: HalfEdge) -> HalfEdge {
+        let outgoing = &self.outgoing[self.dest(half_edge)];
+        let twin_idx = outgoing
+            .iter()
+            .position(|h| *h == half_edge.twin())
+            .expect("Twin leaves the destination.");
+        outgoing[(twin_idx + outgoing.len() - 1) % outgoing.len()]
+    }
+
+    /// Area of the face, positive when counterclockwise.
+    fn signed_area(&self, half_edges: &[HalfEdge]) -> f32 {
+        let points: Vec<_> = half_edges
+            .iter()
+            .flat_map(|half_edge| {
+                let [p0, p1, p2, p3] = self.cubic_points(*half_edge);
+                (0..AREA_SEGMENTS).map(move |i| {
+                    let t = i as f32 / AREA_SEGMENTS as f32;
+                    let mt = 1. - t;
+                    p0 * (mt * mt * mt)
+                        + p1 * (3. * mt * mt * t)
+                        + p2 * (3. * mt * t * t)
+                        + p3 * (t * t * t)
+                })
+            })
+            .collect();
+        let doubled: f32 = points
+            .iter()
+            .zip(points.iter().cycle().skip(1))
+            .map(|(a, b)| a.perp_dot(*b))
+            .sum();
+        doubled / 2.
+    }
+
+    /// Returns the bounded faces of the graph, counterclockwise.  Faces of separate parts of the
+    /// graph aren't nested, a part drawn inside of another part's face overlaps it.
+    fn faces(&self) -> Vec<Walk> {
+        let mut visited = HashSet::new();
+        let mut faces = vec![];
+        for start in self.outgoing.iter().flatten() {
+            if visited.contains(start) {
+                continue;
+            }
+            let mut half_edges = vec![];
+            let mut half_edge = *start;
+            while visited.insert(half_edge) {
+                half_edges.push(half_edge);
+                half_edge = self.next_in_face(half_edge);
+            }
+            if self.signed_area(&half_edges) > f32::EPSILON {
+                faces.push(Walk {
+                    half_edges,
+                    closed: true,
+                });
+            }
+        }
+        faces
+    }
+
+    /// Splits the edges into chains that only pass through endpoints with two edges.
+    fn chains(&self) -> Vec<Walk> {
+        let mut visited = vec![false; self.edges.len()];
+        let mut chains = vec![];
+
+        // Chains between branching / dead end endpoints, then the loops that remain.
+        let starts = (0..self.positions.len())
+            .filter(|i| self.outgoing[*i].len() != 2)
+            .chain((0..self.positions.len()).filter(|i| self.outgoing[*i].len() == 2));
+        for start in starts {
+            for first in &self.outgoing[start] {
+                if visited[first.edge] {
+                    continue;
+                }
+                visited[first.edge] = true;
+                let mut half_edges = vec![*first];
+                let mut half_edge = *first;
+                loop {
+                    let outgoing = &self.outgoing[self.dest(half_edge)];
+                    if outgoing.len() != 2 {
+                        break;
+                    }
+                    let Some(next) = outgoing
+                        .iter()
+                        .find(|h| **h != half_edge.twin() && !visited[h.edge])
+                    else {
+                        break;
+                    };
+                    visited[next.edge] = true;
+                    half_edges.push(*next);
+                    half_edge = *next;
+                }
+                chains.push(Walk {
+                    closed: self.dest(half_edge) == start,
+                    half_edges,
+                });
+            }
+        }
+        chains
+    }
+
+    fn add_walk(&self, pb: &mut Builder, walk: &Walk) {
+        let Some(first) = walk.half_edges.first() else {
+            return;
+        };
+        pb.begin(self.positions[self.origin(*first)].to_point());
+        for half_edge in &walk.half_edges {
+            let [_, ctrl1, ctrl2, end] = self.cubic_points(*half_edge);
+            match self.edges[half_edge.edge].variant {
+                EdgeVariant::Line => {
+                    pb.line_to(end.to_point());
+                }
+                EdgeVariant::Quadratic { ctrl1 } => {
+                    pb.quadratic_bezier_to(ctrl1.to_point(), end.to_point());
+                }
+                EdgeVariant::Cubic { .. } => {
+                    pb.cubic_bezier_to(ctrl1.to_point(), ctrl2.to_point(), end.to_point());
+                }
+            }
+        }
+        pb.end(walk.closed);
+    }
+
+    /// Builds the (fill, stroke) paths.  The fill contains every face of the graph, and parts of
+    /// the graph that are a single open chain so they are filled as if closed.  The stroke
+    /// contains every edge once.
+    pub fn build_paths(&self) -> (Path, Path) {
+        let chains = self.chains();
+
+        let mut fill = Path::builder();
+        for face in self.faces() {
+            self.add_walk(&mut fill, &face);
+        }
+        for chain in chains.iter().filter(|chain| !chain.closed) {
+            let first = chain.half_edges.first().unwrap();
+            let last = chain.half_edges.last().unwrap();
+            if self.outgoing[self.origin(*first)].len() == 1
+                && self.outgoing[self.dest(*last)].len() == 1
+            {
+                self.add_walk(&mut fill, chain);
+            }
+        }
+
+        let mut stroke = Path::builder();
+        for chain in &chains {
+            self.add_walk(&mut stroke, chain);
+        }
+
+        (fill.build(), stroke.build())
+    }
+}
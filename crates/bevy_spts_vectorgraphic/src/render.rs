@@ -0,0 +1,115 @@
+//! Draws VectorGraphics by uploading their [VectorGraphicMesh] to a `Mesh2dHandle` that is
+//! rendered with the [VectorGraphicMaterial].
+
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    asset::{load_internal_asset, Assets, Handle},
+    ecs::{
+        entity::Entity,
+        query::Changed,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query, ResMut},
+    },
+    render::{
+        mesh::{Indices, Mesh, MeshVertexAttribute, PrimitiveTopology, VertexAttributeValues},
+        render_asset::RenderAssetUsages,
+        render_resource::Shader,
+    },
+    sprite::{Material2dPlugin, Mesh2dHandle},
+};
+
+use crate::{
+    components::VectorGraphicMesh,
+    material::{sys_sync_vector_graphic_material, VectorGraphicMaterial, ATTRIBUTE_SHAPE_MIX},
+    VectorGraphicCorePlugin, VectorGraphicSet,
+};
+
+pub const SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(3296418235224473622063937256920);
+
+/// Renders VectorGraphics, adds the [VectorGraphicCorePlugin] if it hasn't been added yet.
+pub struct VectorGraphicRenderPlugin;
+impl Plugin for VectorGraphicRenderPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<VectorGraphicCorePlugin>() {
+            app.add_plugins(VectorGraphicCorePlugin);
+        }
+
+        load_internal_asset!(
+            app,
+            SHADER_HANDLE,
+            "./shaders/vector_graphic_material.wgsl",
+            Shader::from_wgsl
+        );
+        app.add_plugins(Material2dPlugin::<VectorGraphicMaterial>::default());
+
+        app.add_systems(
+            PostUpdate,
+            sys_upload_vector_graphic_mesh.after(VectorGraphicSet::Remesh),
+        );
+        app.add_systems(PostUpdate, sys_sync_vector_graphic_material);
+    }
+}
+
+/// Overwrites the values of a `Float32x3` attribute, reusing its allocation if it already exists.
+fn write_float32x3_attribute(mesh: &mut Mesh, attribute: MeshVertexAttribute, values: &[[f32; 3]]) {
+    match mesh.attribute_mut(attribute.id) {
+        Some(VertexAttributeValues::Float32x3(existing)) => {
+            existing.clear();
+            existing.extend_from_slice(values);
+        }
+        _ => mesh.insert_attribute(attribute, values.to_vec()),
+    }
+}
+
+/// Writes the [VectorGraphicMesh] into the mesh, reusing its allocations.
+fn write_vector_graphic_mesh(mesh: &mut Mesh, vector_graphic_mesh: &VectorGraphicMesh) {
+    match mesh.attribute_mut(ATTRIBUTE_SHAPE_MIX.id) {
+        Some(VertexAttributeValues::Float32(existing)) => {
+            existing.clear();
+            existing.extend_from_slice(&vector_graphic_mesh.shape_mix);
+        }
+        _ => mesh.insert_attribute(ATTRIBUTE_SHAPE_MIX, vector_graphic_mesh.shape_mix.clone()),
+    }
+
+    match mesh.indices_mut() {
+        Some(Indices::U32(existing)) => {
+            existing.clear();
+            existing.extend_from_slice(&vector_graphic_mesh.indices);
+        }
+        _ => mesh.insert_indices(Indices::U32(vector_graphic_mesh.indices.clone())),
+    }
+
+    write_float32x3_attribute(
+        mesh,
+        Mesh::ATTRIBUTE_POSITION,
+        &vector_graphic_mesh.positions,
+    );
+    write_float32x3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL, &vector_graphic_mesh.normals);
+}
+
+/// Uploads changed [VectorGraphicMesh]es.  If the VectorGraphic already has a mesh asset it's
+/// updated in place, otherwise a new mesh is added.
+pub fn sys_upload_vector_graphic_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    q_vector_graphic: Query<
+        (Entity, &VectorGraphicMesh, Option<&Mesh2dHandle>),
+        Changed<VectorGraphicMesh>,
+    >,
+) {
+    for (entity, vector_graphic_mesh, maybe_mesh_handle) in q_vector_graphic.iter() {
+        if let Some(mesh) = maybe_mesh_handle.and_then(|handle| meshes.get_mut(&handle.0)) {
+            write_vector_graphic_mesh(mesh, vector_graphic_mesh);
+            continue;
+        }
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
+        );
+        write_vector_graphic_mesh(&mut mesh, vector_graphic_mesh);
+
+        let handle = Mesh2dHandle::from(meshes.add(mesh));
+        commands.entity(entity).insert(handle);
+    }
+}
@@ -38,10 +38,74 @@ struct VertexOutput {
     // @location(4) color: vec4<f32>,
     #endif
     @location(5) shape_mix: f32,
+    @location(6) local_position: vec2<f32>,
 }
 
-@group(2) @binding(0) var<uniform> fill_color: vec4<f32>;
-@group(2) @binding(1) var<uniform> stroke_color: vec4<f32>;
+// Must match `MAX_GRADIENT_STOPS` in material.rs
+const MAX_GRADIENT_STOPS: u32 = 8u;
+
+const PAINT_SOLID: u32 = 0u;
+const PAINT_LINEAR: u32 = 1u;
+
+const SPREAD_PAD: u32 = 0u;
+const SPREAD_REFLECT: u32 = 1u;
+
+// Layout of `VectorGraphicPaint` in material.rs
+struct Paint {
+    kind: u32,
+    spread: u32,
+    stop_count: u32,
+    color: vec4<f32>,
+    local_to_gradient: mat3x3<f32>,
+    start: vec2<f32>,
+    end: vec2<f32>,
+    radius: f32,
+    stop_offsets: array<vec4<f32>, 2>,
+    stop_colors: array<vec4<f32>, MAX_GRADIENT_STOPS>,
+}
+
+@group(2) @binding(0) var<uniform> fill: Paint;
+@group(2) @binding(1) var<uniform> stroke: Paint;
+
+fn paint_color(paint: Paint, local_position: vec2<f32>) -> vec4<f32> {
+    if paint.kind == PAINT_SOLID {
+        return paint.color;
+    }
+    if paint.stop_count == 0u {
+        return vec4<f32>(0.);
+    }
+
+    let position = (paint.local_to_gradient * vec3<f32>(local_position, 1.)).xy;
+    var t: f32;
+    if paint.kind == PAINT_LINEAR {
+        let direction = paint.end - paint.start;
+        t = dot(position - paint.start, direction) / max(dot(direction, direction), 1e-6);
+    } else {
+        t = distance(position, paint.start) / max(paint.radius, 1e-6);
+    }
+
+    if paint.spread == SPREAD_PAD {
+        t = clamp(t, 0., 1.);
+    } else if paint.spread == SPREAD_REFLECT {
+        t = 1. - abs(t - 2. * floor(t / 2.) - 1.);
+    } else {
+        t = t - floor(t);
+    }
+
+    // Copied into function variables so they can be indexed dynamically.
+    var offsets = paint.stop_offsets;
+    var colors = paint.stop_colors;
+    var color = colors[0];
+    for (var i = 1u; i < paint.stop_count; i++) {
+        let prev = offsets[(i - 1u) / 4u][(i - 1u) % 4u];
+        let next = offsets[i / 4u][i % 4u];
+        if t >= prev {
+            let mix_amount = clamp((t - prev) / max(next - prev, 1e-6), 0., 1.);
+            color = mix(colors[i - 1u], colors[i], mix_amount);
+        }
+    }
+    return color;
+}
 
 @vertex
 fn vertex(vertex: Vertex) -> VertexOutput {
@@ -58,6 +122,7 @@ fn vertex(vertex: Vertex) -> VertexOutput {
         vec4<f32>(pos, 1.0)
     );
     out.position = mesh_functions::mesh2d_position_world_to_clip(world_position);
+    out.local_position = vertex.position.xy;
 #endif
 
 #ifdef VERTEX_NORMALS
@@ -88,6 +153,8 @@ fn fragment(
 #endif
     return color;
 #else
+    let fill_color = paint_color(fill, in.local_position);
+    let stroke_color = paint_color(stroke, in.local_position);
     return mix(fill_color, stroke_color, in.shape_mix);
 #endif
 }
@@ -1,7 +1,6 @@
 //! Lifecycle methods for handling when edges/endpoints are spawned/despawned.
 
-use core::panic;
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Range};
 
 // use bevy::{
 //     ecs::{
@@ -12,21 +11,21 @@ use std::fmt::Debug;
 // };
 
 use bevy::{
-    asset::Assets, ecs::{
-        entity::{Entity, EntityHashMap, EntityHashSet}, query::{Added, Changed, Or, QueryEntityError, With, Without}, removal_detection::RemovedComponents, system::{Commands, Query, QueryLens, ResMut}
-    }, hierarchy::Parent, log::warn, math::Vec3Swizzles, render::{mesh::{Indices, Mesh, PrimitiveTopology}, render_asset::RenderAssetUsages}, sprite::Mesh2dHandle, transform::components::Transform, utils::HashMap
+    ecs::{
+        component::Component, entity::{Entity, EntityHashMap, EntityHashSet}, query::{Added, Changed, Or, QueryEntityError, With, Without}, removal_detection::RemovedComponents, system::{Commands, Local, Query, Res, ResMut}
+    }, hierarchy::Parent, log::warn, math::Vec3Swizzles, tasks::{block_on, poll_once, AsyncComputeTaskPool, Task}, transform::components::Transform, utils::Parallel,
 };
-use bevy_spts_uid::{Uid, UidRegistry, UidRegistryError};
+use bevy_spts_uid::{Uid, UidRegistryError};
 use lyon_tessellation::{
-    path::Path, BuffersBuilder, FillVertexConstructor, StrokeVertexConstructor, VertexBuffers,
+    BuffersBuilder, FillVertexConstructor, StrokeVertexConstructor, VertexBuffers,
 };
 
 use crate::{
-    components::{Edge, Endpoint, VectorGraphic, VectorGraphicPathStorage},
+    components::{Edge, Endpoint, VectorGraphic, VectorGraphicMesh, VectorGraphicPathStorage},
     lyon_components::{FillOptions, StrokeOptions},
-    prelude::{EdgeVariant, ATTRIBUTE_SHAPE_MIX},
-    utils::ToPoint,
-    SptsFillTessellator, SptsStrokeTessellator,
+    planar::PlanarGraph,
+    prelude::EdgeVariant,
+    RemeshMode, RemeshTaskBuffers, SptsTessellator, SptsTessellators,
 };
 
 #[derive(Debug)]
@@ -162,247 +161,62 @@ pub fn sys_check_vector_graphic_children_changed(
     }
 }
 
-pub enum TraverseEndpointsDirectedResult {
-    Closed,
-    DeadEnd,
-}
-
-/// Returns Vec<Entity> containing the endpoint/edge walk.
-/// [Entity (Edge), Entity(Endpoint), ...]
-/// WARN: Different from traverse_endpoints as it doesn't contain the start_endpoint in the output.
-fn traverse_endpoints_directed(
-    start_endpoint_uid: &Uid,
-    first_edge_uid: &Uid,
-    q_endpoints: &mut QueryLens<&Endpoint>,
-    q_edges: &mut QueryLens<&Edge>,
-    reg: &mut UidRegistry,
-    out: &mut Vec<Entity>,
-) -> Result<TraverseEndpointsDirectedResult, VectorGraphicError> {
-    let q_endpoints = q_endpoints.query();
-    let q_edges = q_edges.query();
-
-    let start_endpoint_e = reg.get_entity(*start_endpoint_uid)?;
-    let start_endpoint = *q_endpoints.get(start_endpoint_e)?;
-
-    let mut behind_edge_uid = None;
-    let mut curr_endpoint_uid = *start_endpoint_uid;
-    let mut curr_endpoint = start_endpoint;
-    loop {
-        let maybe_next_edge_uid = match behind_edge_uid {
-            Some(behind_edge_uid) => curr_endpoint.other_edge_uid(&behind_edge_uid),
-            None => Some(first_edge_uid),
-        };
-
-        let Some(next_edge_uid) = maybe_next_edge_uid else {
-            return Ok(TraverseEndpointsDirectedResult::DeadEnd);
-        };
-        let next_edge_e = reg.get_entity(*next_edge_uid)?;
-        let next_edge = q_edges.get(next_edge_e)?;
-
-        let Some(next_endpoint_uid) = next_edge.other_endpoint_uid(&curr_endpoint_uid) else {
-            return Ok(TraverseEndpointsDirectedResult::DeadEnd);
-        };
-        let next_endpoint_e = reg.get_entity(next_endpoint_uid)?;
-        let next_endpoint = *q_endpoints.get(next_endpoint_e)?;
-
-        out.push(next_edge_e);
-        out.push(next_endpoint_e);
-        if next_endpoint_uid == *start_endpoint_uid {
-            return Ok(TraverseEndpointsDirectedResult::Closed);
-        }
-        curr_endpoint_uid = next_endpoint_uid;
-        behind_edge_uid = Some(*next_edge_uid);
-        curr_endpoint = next_endpoint;
-    }
-}
-
-/// Returns Vec<Entity> containing the endpoint/edge walk.
-/// [ Entity (Endpoint),
-///     (Entity (Edge), Entity (Endpoint))... Repeating
-/// ]
-/// WARN: Length is always >= 1 as start_endpoint is included in Vec.
-fn traverse_endpoints(
-    start_endpoint_uid: &Uid,
-    q_endpoints: &mut QueryLens<&Endpoint>,
-    q_edges: &mut QueryLens<&Edge>,
-    reg: &mut UidRegistry,
-) -> Result<Vec<Entity>, VectorGraphicError> {
-    let start_endpoint_e = reg.get_entity(*start_endpoint_uid)?;
-    let start_endpoint = *q_endpoints.query().get(start_endpoint_e)?;
-    // warn!("traverse_endpoints: Starting at {first_e:?} ({start_endpoint})");
-
-    let Some(forward_first_edge_uid) = start_endpoint
-        .prev_edge_entity()
-        .or(start_endpoint.next_edge_entity())
-    else {
-        return Ok(vec![]);
-    };
-    let maybe_backward_first_edge_uid = start_endpoint.other_edge_uid(&forward_first_edge_uid);
-
-    let mut forward_walk = vec![start_endpoint_e];
-    let result = traverse_endpoints_directed(
-        start_endpoint_uid,
-        &forward_first_edge_uid,
-        q_endpoints,
-        q_edges,
-        reg,
-        &mut forward_walk,
-    )?;
-
-    match (maybe_backward_first_edge_uid, result) {
-        (Some(backward_back_edge), TraverseEndpointsDirectedResult::DeadEnd) => {
-            let mut back_walk = vec![];
-            traverse_endpoints_directed(
-                start_endpoint_uid,
-                backward_back_edge,
-                q_endpoints,
-                q_edges,
-                reg,
-                &mut back_walk,
-            )?;
-
-            back_walk.reverse();
-            back_walk.extend(forward_walk);
-            Ok(back_walk)
-        }
-        (Some(_), TraverseEndpointsDirectedResult::Closed) => Ok(forward_walk),
-        (None, TraverseEndpointsDirectedResult::Closed) => {
-            panic!("Impossible.  Can't have a closed loop without a back_edge on first endpoint.")
-        }
-        (None, TraverseEndpointsDirectedResult::DeadEnd) => Ok(forward_walk),
-    }
-}
-
-/// Builds Vec<Vec<Entity>> of all endpoint paths that need to be regenerated.
+/// Rebuilds the stroke and fill paths of every VectorGraphic that needs recalculating.
+/// Endpoints can link any number of edges, the fill is built from the faces of the graph.
 ///
-/// * `changed_vector_graphics`: The VectorGraphic entities that have changes.
+/// * `q_vector_graphic`:
 /// * `q_endpoints`:
 /// * `q_edges`:
 pub fn sys_collect_vector_graph_path_endpoints(
-    mut q_vector_graphic: Query<(Entity, &VectorGraphic, &mut VectorGraphicPathStorage)>,
-    mut q_endpoints: Query<(Entity, &Uid, &Endpoint, &Parent, &Transform)>,
-    mut q_edges: Query<(Entity, &Edge, &EdgeVariant, &Parent)>,
-    mut reg: ResMut<UidRegistry>,
+    mut q_vector_graphic: Query<(Entity, &mut VectorGraphicPathStorage)>,
+    q_endpoints: Query<(&Uid, &Parent, &Transform), With<Endpoint>>,
+    q_edges: Query<(&Edge, &EdgeVariant, &Parent)>,
 ) {
-    let changed_vector_graphics: Vec<_> = q_vector_graphic
-        .iter()
-        .filter_map(|(e, _, path_storage)| {
-            if path_storage.needs_recalculate() {
-                Some(e)
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    // Hashset storing endpoint entity(0) and their parent(1)
-    let mut unvisited: EntityHashMap<_> = q_endpoints
+    let changed_vector_graphics: EntityHashSet = q_vector_graphic
         .iter()
-        .filter_map(|(entity, uid, _, parent, _)| {
-            if changed_vector_graphics.contains(&parent.get()) {
-                Some((entity, (*uid, parent.get())))
-            } else {
-                None
-            }
-        })
+        .filter(|(_, path_storage)| path_storage.needs_recalculate())
+        .map(|(e, _)| e)
         .collect();
+    if changed_vector_graphics.is_empty() {
+        return;
+    }
 
-    let mut vector_graphic_path_endpoints = HashMap::new();
-
-    while let Some(entity) = unvisited.keys().copied().next() {
-        let Some((uid, parent)) = unvisited.get(&entity).copied() else {
-            continue;
-        };
-        // Collect endpoints in path
-        let endpoints = traverse_endpoints(
-            &uid,
-            &mut q_endpoints.transmute_lens::<&Endpoint>(),
-            &mut q_edges.transmute_lens::<&Edge>(),
-            &mut reg,
-        );
-
-        let Ok(endpoints) = endpoints else {
-            warn!("sys_collect_vector_graph_path_endpoints: Could not get endpoints of group.  Reason {endpoints:?}");
-            break;
-        };
-
-        // let result: Vec<_> = endpoints
-        //     .iter()
-        //     .map(|e| (e, q_endpoints.get(*e).unwrap().1))
-        //     .collect();
-
-        unvisited.remove(&entity);
-        for chunk in endpoints.as_slice().chunks(2) {
-            let endpoint_e = unsafe { chunk.get_unchecked(0) };
-            unvisited.remove(endpoint_e);
+    let mut endpoints: EntityHashMap<Vec<_>> = EntityHashMap::default();
+    for (uid, parent, transform) in &q_endpoints {
+        if changed_vector_graphics.contains(&parent.get()) {
+            endpoints
+                .entry(parent.get())
+                .or_default()
+                .push((*uid, transform.translation.xy()));
         }
-
-        if endpoints.len() <= 1 {
-            continue;
+    }
+    let mut edges: EntityHashMap<Vec<_>> = EntityHashMap::default();
+    for (edge, edge_variant, parent) in &q_edges {
+        if changed_vector_graphics.contains(&parent.get()) {
+            edges.entry(parent.get()).or_default().push((
+                edge.prev_endpoint_uid(),
+                edge.next_endpoint_uid(),
+                *edge_variant,
+            ));
         }
-
-        let entry = vector_graphic_path_endpoints.entry(parent);
-        let paths = entry.or_insert(vec![]);
-        paths.push(endpoints);
     }
 
-    // Build the paths
-    for vector_grapic_entity in changed_vector_graphics {
-        let Some(paths_walks_2d) = vector_graphic_path_endpoints.get(&vector_grapic_entity) else {
-            // warn!("sys_mark_vector_graph_path_starts: Tried to get endpoints of vector_grapic_entity({vector_grapic_entity:?}) but not in hashmap.");
-            continue;
-        };
+    for vector_graphic_entity in changed_vector_graphics {
+        let graph = PlanarGraph::new(
+            endpoints
+                .get(&vector_graphic_entity)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+            edges.remove(&vector_graphic_entity).unwrap_or_default(),
+        );
+        let (fill_path, path) = graph.build_paths();
 
-        let Ok((_, _, mut path_storage)) = q_vector_graphic.get_mut(vector_grapic_entity) else {
-            warn!("sys_collect_vector_graph_path_endpoints: Tried to get VectorGraphicPathStorage for changed path but entity or component on entity does not exist.  Entity: {vector_grapic_entity:?}");
+        let Ok((_, mut path_storage)) = q_vector_graphic.get_mut(vector_graphic_entity) else {
+            warn!("sys_collect_vector_graph_path_endpoints: Tried to get VectorGraphicPathStorage for changed path but entity or component on entity does not exist.  Entity: {vector_graphic_entity:?}");
             continue;
         };
-
-        let mut pb = Path::builder();
-
-        for path_walk in paths_walks_2d {
-            if path_walk.len() <= 1 {
-                warn!("sys_collect_vector_graph_path_endpoints: Endpoints path too short({path_walk:?}).");
-                continue;
-            }
-            let first_endpoint_e = path_walk.first().unwrap();
-
-            let (_, _, _, _, transform) = q_endpoints
-                .get(*first_endpoint_e)
-                .expect("Could not get endpoint.");
-            pb.begin(transform.translation.xy().to_point());
-
-            let remaining_walk_slice = &path_walk[1..];
-            for chunk in remaining_walk_slice.chunks(2) {
-                let Some(edge_e) = chunk.first() else {
-                    panic!("sys_collect_vector_graph_path_endpoints: Couldn't get first endpoint, maybe the walk is malformed.");
-                };
-                let Some(endpoint_e) = chunk.get(1) else {
-                    panic!("sys_collect_vector_graph_path_endpoints: Couldn't get first edge, maybe the walk is malformed.");
-                };
-                let (_, _, edge_variant, _) = q_edges.get(*edge_e).unwrap();
-                let (_, _, _, _, transform) = q_endpoints.get(*endpoint_e).unwrap();
-
-                let to_point = transform.translation.xy().to_point();
-                match edge_variant {
-                    EdgeVariant::Line => {
-                        pb.line_to(to_point);
-                    }
-                    EdgeVariant::Quadratic { ctrl1 } => {
-                        pb.quadratic_bezier_to(ctrl1.to_point(), to_point);
-                    }
-                    EdgeVariant::Cubic { ctrl1, ctrl2 } => {
-                        pb.cubic_bezier_to(ctrl1.to_point(), ctrl2.to_point(), to_point);
-                    }
-                }
-            }
-
-            let is_closed = path_walk.last().unwrap() == first_endpoint_e;
-            pb.end(is_closed);
-        }
-
-        let path = pb.build();
         path_storage.set_path(path);
+        path_storage.set_fill_path(fill_path);
     }
 }
 
@@ -428,88 +242,292 @@ impl StrokeVertexConstructor<RemeshVertex> for RemeshVertexConstructor {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Default)]
 struct RemeshVertex {
     position: [f32; 2],
     normal: [f32; 2],
 }
 
-#[allow(clippy::type_complexity)]
-pub fn sys_remesh_vector_graphic(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    q_vector_graphic: Query<
-        (
-            Entity,
-            &VectorGraphicPathStorage,
-            Option<&StrokeOptions>,
-            Option<&FillOptions>,
-        ),
-        Or<(
-            Changed<VectorGraphicPathStorage>,
-            Changed<StrokeOptions>,
-            Changed<FillOptions>,
-        )>,
-    >,
-    mut fill_tessellator: ResMut<SptsFillTessellator>,
-    mut stroke_tesellator: ResMut<SptsStrokeTessellator>,
-) {
-    for (entity, path_storage, maybe_stroke_options, maybe_fill_options) in q_vector_graphic.iter()
-    {
-        let Some(path) = path_storage.path() else {
-            continue;
+/// The geometry of a single VectorGraphic within a [RemeshScratch].
+struct RemeshRange {
+    entity: Entity,
+    vertices: Range<usize>,
+    indices: Range<usize>,
+    /// Number of vertices from the fill, the remaining vertices are from the stroke.
+    fill_vertex_count: usize,
+}
+
+/// Vertex buffers that are reused between remeshes instead of allocating new ones each time.
+/// The geometry of every VectorGraphic tessellated on a thread is appended to the same buffers.
+#[derive(Default)]
+pub struct RemeshScratch {
+    geometry: VertexBuffers<RemeshVertex, u32>,
+    ranges: Vec<RemeshRange>,
+}
+
+impl RemeshScratch {
+    /// Tessellates the fill and stroke of a VectorGraphic, appending the geometry to the buffers.
+    /// Nothing is appended if tessellation fails.
+    fn tessellate(
+        &mut self,
+        tessellator: &mut SptsTessellator,
+        entity: Entity,
+        path_storage: &VectorGraphicPathStorage,
+        maybe_stroke_options: Option<&StrokeOptions>,
+        maybe_fill_options: Option<&FillOptions>,
+    ) {
+        let (Some(path), Some(fill_path)) = (path_storage.path(), path_storage.fill_path()) else {
+            return;
         };
-        let mut geometry = VertexBuffers::new();
+        let vertex_start = self.geometry.vertices.len();
+        let index_start = self.geometry.indices.len();
 
         if let Some(fill_options) = maybe_fill_options {
-            if let Err(reason) = fill_tessellator.tessellate_path(
-                path,
+            if let Err(reason) = tessellator.fill.tessellate_path(
+                fill_path,
                 &(*fill_options).into(),
-                &mut BuffersBuilder::new(&mut geometry, RemeshVertexConstructor),
+                &mut BuffersBuilder::new(&mut self.geometry, RemeshVertexConstructor),
             ) {
                 warn!("sys_remesh_vector_graphic: Failed to tessellate fill {reason:?}.");
-                continue;
+                self.truncate(vertex_start, index_start);
+                return;
             }
         }
-        // Stores vertex attribtue of ATTRIBUTE_SHAPE_MIX
-        let mut shape_mix_attr = vec![0.; geometry.vertices.len()];
+        let fill_vertex_count = self.geometry.vertices.len() - vertex_start;
 
         if let Some(stroke_options) = maybe_stroke_options {
-            if let Err(reason) = stroke_tesellator.tessellate_path(
+            if let Err(reason) = tessellator.stroke.tessellate_path(
                 path,
                 &(*stroke_options).into(),
-                &mut BuffersBuilder::new(&mut geometry, RemeshVertexConstructor),
+                &mut BuffersBuilder::new(&mut self.geometry, RemeshVertexConstructor),
             ) {
                 warn!("sys_remesh_vector_graphic: Failed to tessellate stroke {reason:?}.");
-                continue;
+                self.truncate(vertex_start, index_start);
+                return;
             }
         }
 
-        shape_mix_attr.extend(vec![1.; geometry.vertices.len() - shape_mix_attr.len()]);
+        self.ranges.push(RemeshRange {
+            entity,
+            vertices: vertex_start..self.geometry.vertices.len(),
+            indices: index_start..self.geometry.indices.len(),
+            fill_vertex_count,
+        });
+    }
 
-        let mut mesh = Mesh::new(
-            PrimitiveTopology::TriangleList,
-            RenderAssetUsages::RENDER_WORLD | RenderAssetUsages::MAIN_WORLD,
-        );
+    fn truncate(&mut self, vertex_len: usize, index_len: usize) {
+        self.geometry.vertices.truncate(vertex_len);
+        self.geometry.indices.truncate(index_len);
+    }
 
-        let VertexBuffers { vertices, indices } = geometry;
+    /// Writes the tessellated geometry into the [VectorGraphicMesh] of each VectorGraphic,
+    /// inserting one if it doesn't have one yet.  Clears the buffers so they can be reused.
+    fn apply(&mut self, commands: &mut Commands, q_meshes: &mut Query<&mut VectorGraphicMesh>) {
+        for range in self.ranges.drain(..) {
+            let vertices = &self.geometry.vertices[range.vertices.clone()];
+            let indices = &self.geometry.indices[range.indices];
+            let vertex_start = range.vertices.start as u32;
+
+            if let Ok(mut mesh) = q_meshes.get_mut(range.entity) {
+                write_remesh_buffers(
+                    &mut mesh,
+                    vertices,
+                    indices,
+                    vertex_start,
+                    range.fill_vertex_count,
+                );
+                continue;
+            }
 
-        mesh.insert_attribute(ATTRIBUTE_SHAPE_MIX, shape_mix_attr);
+            let mut mesh = VectorGraphicMesh::default();
+            write_remesh_buffers(
+                &mut mesh,
+                vertices,
+                indices,
+                vertex_start,
+                range.fill_vertex_count,
+            );
+            commands.entity(range.entity).insert(mesh);
+        }
+        self.truncate(0, 0);
+    }
+}
 
-        mesh.insert_indices(Indices::U32(indices));
-        let (positions, normals): (Vec<[f32; 3]>, Vec<[f32; 3]>) = vertices
-            .into_iter()
-            .map(|vert| {
-                let position: [f32; 3] = [vert.position[0], vert.position[1], 0.0];
-                let normal: [f32; 3] = [vert.normal[0], vert.normal[1], 0.0];
-                (position, normal)
-            })
-            .unzip();
+/// Writes the tessellated geometry into the mesh, reusing its allocations.  The first
+/// `fill_vertex_count` vertices are from the fill and the rest from the stroke.  `indices` index
+/// into the buffer that `vertices` starts at `vertex_start` of.
+fn write_remesh_buffers(
+    mesh: &mut VectorGraphicMesh,
+    vertices: &[RemeshVertex],
+    indices: &[u32],
+    vertex_start: u32,
+    fill_vertex_count: usize,
+) {
+    mesh.shape_mix.clear();
+    mesh.shape_mix
+        .extend((0..vertices.len()).map(|i| match i < fill_vertex_count {
+            true => 0.,
+            false => 1.,
+        }));
+
+    mesh.indices.clear();
+    mesh.indices
+        .extend(indices.iter().map(|i| i - vertex_start));
+
+    mesh.positions.clear();
+    mesh.positions.extend(
+        vertices
+            .iter()
+            .map(|vert| [vert.position[0], vert.position[1], 0.0]),
+    );
+    mesh.normals.clear();
+    mesh.normals.extend(
+        vertices
+            .iter()
+            .map(|vert| [vert.normal[0], vert.normal[1], 0.0]),
+    );
+}
 
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+/// A tessellation running on the `AsyncComputeTaskPool`, see [RemeshMode::Deferred].  Removing
+/// the component cancels the task.
+#[derive(Component)]
+pub struct RemeshTask {
+    task: Task<(SptsTessellator, RemeshScratch)>,
+    /// The VectorGraphic changed while the task was running, it's remeshed again once the task
+    /// finishes.
+    dirty: bool,
+}
+
+impl RemeshTask {
+    /// Tessellates a VectorGraphic on the `AsyncComputeTaskPool`, using buffers from the pool if
+    /// there are any.
+    fn spawn(
+        buffers: &mut RemeshTaskBuffers,
+        entity: Entity,
+        path_storage: &VectorGraphicPathStorage,
+        maybe_stroke_options: Option<&StrokeOptions>,
+        maybe_fill_options: Option<&FillOptions>,
+    ) -> Self {
+        let (mut tessellator, mut scratch) = buffers.pop().unwrap_or_default();
+        let path_storage = path_storage.clone();
+        let stroke_options = maybe_stroke_options.copied();
+        let fill_options = maybe_fill_options.copied();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            scratch.tessellate(
+                &mut tessellator,
+                entity,
+                &path_storage,
+                stroke_options.as_ref(),
+                fill_options.as_ref(),
+            );
+            (tessellator, scratch)
+        });
+        Self { task, dirty: false }
+    }
+}
 
-        let handle = Mesh2dHandle::from(meshes.add(mesh));
-        commands.entity(entity).insert(handle);
+/// Tessellates the paths of changed VectorGraphics into their [VectorGraphicMesh].
+///
+/// In [RemeshMode::Synchronous] the VectorGraphics are tessellated in parallel using thread local
+/// tessellators and scratch buffers.  In [RemeshMode::Deferred] a [RemeshTask] is spawned for
+/// each VectorGraphic, which is applied by [sys_apply_remesh_tasks] once it finishes.  If a
+/// VectorGraphic changes while its task is running it's remeshed again after the task finishes,
+/// rather than cancelling the task, so it keeps updating while it's being edited.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn sys_remesh_vector_graphic(
+    mut commands: Commands,
+    q_vector_graphic: Query<
+        (
+            Entity,
+            &VectorGraphicPathStorage,
+            Option<&StrokeOptions>,
+            Option<&FillOptions>,
+        ),
+        Or<(
+            Changed<VectorGraphicPathStorage>,
+            Changed<StrokeOptions>,
+            Changed<FillOptions>,
+        )>,
+    >,
+    mut q_meshes: Query<&mut VectorGraphicMesh>,
+    mut q_tasks: Query<&mut RemeshTask>,
+    remesh_mode: Res<RemeshMode>,
+    tessellators: Res<SptsTessellators>,
+    mut task_buffers: ResMut<RemeshTaskBuffers>,
+    mut scratch: Local<Parallel<RemeshScratch>>,
+) {
+    match *remesh_mode {
+        RemeshMode::Synchronous => {
+            q_vector_graphic.par_iter().for_each(
+                |(entity, path_storage, maybe_stroke_options, maybe_fill_options)| {
+                    scratch.borrow_local_mut().tessellate(
+                        &mut tessellators.borrow_local_mut(),
+                        entity,
+                        path_storage,
+                        maybe_stroke_options,
+                        maybe_fill_options,
+                    );
+                },
+            );
+            for scratch in scratch.iter_mut() {
+                scratch.apply(&mut commands, &mut q_meshes);
+            }
+        }
+        RemeshMode::Deferred => {
+            for (entity, path_storage, maybe_stroke_options, maybe_fill_options) in
+                q_vector_graphic.iter()
+            {
+                if let Ok(mut task) = q_tasks.get_mut(entity) {
+                    task.dirty = true;
+                    continue;
+                }
+                let task = RemeshTask::spawn(
+                    &mut task_buffers,
+                    entity,
+                    path_storage,
+                    maybe_stroke_options,
+                    maybe_fill_options,
+                );
+                commands.entity(entity).insert(task);
+            }
+        }
+    }
+}
+
+/// Applies the [RemeshTask]s that have finished, respawning the ones whose VectorGraphic changed
+/// while they were running.
+#[allow(clippy::type_complexity)]
+pub fn sys_apply_remesh_tasks(
+    mut commands: Commands,
+    mut q_tasks: Query<(
+        Entity,
+        &mut RemeshTask,
+        &VectorGraphicPathStorage,
+        Option<&StrokeOptions>,
+        Option<&FillOptions>,
+    )>,
+    mut q_meshes: Query<&mut VectorGraphicMesh>,
+    mut task_buffers: ResMut<RemeshTaskBuffers>,
+) {
+    for (entity, mut task, path_storage, maybe_stroke_options, maybe_fill_options) in
+        q_tasks.iter_mut()
+    {
+        let Some((tessellator, mut scratch)) = block_on(poll_once(&mut task.task)) else {
+            continue;
+        };
+        scratch.apply(&mut commands, &mut q_meshes);
+        task_buffers.push((tessellator, scratch));
+
+        if task.dirty {
+            *task = RemeshTask::spawn(
+                &mut task_buffers,
+                entity,
+                path_storage,
+                maybe_stroke_options,
+                maybe_fill_options,
+            );
+        } else {
+            commands.entity(entity).remove::<RemeshTask>();
+        }
     }
 }
@@ -0,0 +1,249 @@
+use bb_vector_network::prelude::{BBEdge, BBGraph};
+use bevy::{math::vec2, prelude::*};
+
+use bevy_spts_uid::Uid;
+use bevy_spts_vectorgraphic::prelude::*;
+
+mod common;
+use common::{build_app, spawn_edge, spawn_endpoint};
+
+/// A box with a line, quadratic, cubic and line edge.
+fn build_box(world: &mut World) -> (Entity, [Uid; 4], [Uid; 4]) {
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p0 = spawn_endpoint(world, vg, vec2(0., 0.));
+    let p1 = spawn_endpoint(world, vg, vec2(100., 0.));
+    let p2 = spawn_endpoint(world, vg, vec2(100., 100.));
+    let p3 = spawn_endpoint(world, vg, vec2(0., 100.));
+
+    let e0 = spawn_edge(world, vg, EdgeVariant::Line, p0, p1);
+    let e1 = spawn_edge(
+        world,
+        vg,
+        EdgeVariant::Quadratic {
+            ctrl1: vec2(150., 50.),
+        },
+        p1,
+        p2,
+    );
+    let e2 = spawn_edge(
+        world,
+        vg,
+        EdgeVariant::Cubic {
+            ctrl1: vec2(75., 150.),
+            ctrl2: vec2(25., 150.),
+        },
+        p2,
+        p3,
+    );
+    let e3 = spawn_edge(world, vg, EdgeVariant::Line, p3, p0);
+
+    (vg, [p0, p1, p2, p3], [e0, e1, e2, e3])
+}
+
+#[test]
+fn it_builds_a_bb_graph_from_a_vector_graphic() {
+    let mut app = build_app();
+    let world = app.world_mut();
+    let (vg, endpoints, edges) = build_box(world);
+
+    let (graph, map) = vector_graphic_to_bb_graph(world, vg).unwrap();
+    assert_eq!(graph.nodes_count(), 4);
+    assert_eq!(graph.edges_count(), 4);
+    assert_eq!(graph.regions.len(), 1);
+    assert_eq!(map.endpoints_len(), 4);
+    assert_eq!(map.edges_len(), 4);
+
+    let p2_idx = map.node_idx(endpoints[2]).unwrap();
+    assert_eq!(map.endpoint_uid(p2_idx), Some(endpoints[2]));
+    assert_eq!(
+        graph.node(p2_idx).unwrap().position().to_array(),
+        [100., 100.]
+    );
+
+    let e1_idx = map.edge_idx(edges[1]).unwrap();
+    assert_eq!(map.edge_uid(e1_idx), Some(edges[1]));
+    let BBEdge::Quadratic { start, ctrl1, end } = *graph.edge(e1_idx).unwrap() else {
+        panic!("Expected a quadratic edge.");
+    };
+    assert_eq!(start, map.node_idx(endpoints[1]).unwrap());
+    assert_eq!(ctrl1.to_array(), [150., 50.]);
+    assert_eq!(end, p2_idx);
+}
+
+#[test]
+fn it_keeps_endpoints_without_edges() {
+    let mut app = build_app();
+    let world = app.world_mut();
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p0 = spawn_endpoint(world, vg, vec2(10., 20.));
+
+    let (graph, map) = vector_graphic_to_bb_graph(world, vg).unwrap();
+    assert_eq!(graph.nodes_count(), 1);
+    assert_eq!(graph.edges_count(), 0);
+    let node_idx = map.node_idx(p0).unwrap();
+    assert_eq!(
+        graph.node(node_idx).unwrap().position().to_array(),
+        [10., 20.]
+    );
+}
+
+#[test]
+fn it_calculates_tight_bounds_and_convex_hull() {
+    let mut app = build_app();
+    let world = app.world_mut();
+    let (vg, _, _) = build_box(world);
+
+    // The quadratic and cubic bulge past the box, but not as far as their control points.
+    let (min, max) = vector_graphic_bounds(world, vg).unwrap().unwrap();
+    assert_eq!(min, vec2(0., 0.));
+    assert!(max.distance(vec2(125., 137.5)) < 0.001);
+
+    let hull = vector_graphic_convex_hull(world, vg).unwrap();
+    assert!(hull.len() > 4);
+    for p in &hull {
+        assert!(p.cmpge(min - 0.001).all() && p.cmple(max + 0.001).all());
+    }
+
+    let empty = world.spawn(VectorGraphicBundle::default()).id();
+    assert_eq!(vector_graphic_bounds(world, empty).unwrap(), None);
+}
+
+#[test]
+fn it_errors_on_invalid_hierarchies() {
+    let mut app = build_app();
+    let world = app.world_mut();
+
+    let not_vg = world.spawn_empty().id();
+    assert!(matches!(
+        vector_graphic_to_bb_graph(world, not_vg),
+        Err(BBGraphBridgeError::NotAVectorGraphic(e)) if e == not_vg
+    ));
+
+    // Endpoint that belongs to another vector graphic.
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let other_vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p0 = spawn_endpoint(world, vg, vec2(0., 0.));
+    let p1 = spawn_endpoint(world, other_vg, vec2(10., 0.));
+    let e0 = spawn_edge(world, vg, EdgeVariant::Line, p0, p1);
+
+    assert!(matches!(
+        vector_graphic_to_bb_graph(world, vg),
+        Err(BBGraphBridgeError::MissingEndpoint { edge, endpoint }) if edge == e0 && endpoint == p1
+    ));
+}
+
+#[cfg(feature = "changeset")]
+mod changeset {
+    use bb_vector_network::glam::vec2 as bb_vec2;
+    use bevy_spts_uid::extension::EntityCommandsExt;
+    use bevy_spts_changeset::{
+        commands_ext::WorldChangesetExt, events::ChangesetEvent, resource::ChangesetResource,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct BridgeChangeset;
+
+    fn build_changeset_app() -> App {
+        let mut app = build_app();
+        app.add_event::<ChangesetEvent>();
+        app.insert_resource(ChangesetResource::<BridgeChangeset>::new());
+        app.register_type::<Transform>()
+            .register_type::<Endpoint>()
+            .register_type::<Edge>()
+            .register_type::<EdgeVariant>();
+        app
+    }
+
+    /// Spawns `graph` into a new VectorGraphic and snapshots it back.
+    fn round_trip(world: &mut World, graph: &BBGraph) -> (BBGraph, BBGraphUidMap, BBGraphUidMap) {
+        let (vg, vg_uid) = world.spawn(VectorGraphicBundle::default()).id_uid();
+        vg_uid.register(world, vg);
+        let mut commands = world.changeset();
+        let spawned = spawn_bb_graph(&mut commands, graph, vg_uid).unwrap();
+        let changeset = commands.build();
+
+        ChangesetResource::<BridgeChangeset>::context_scope(world, |world, cx| {
+            changeset.apply(world, cx).unwrap();
+        });
+
+        let vg = vg_uid.entity(world).unwrap();
+        let (snapshot, map) = vector_graphic_to_bb_graph(world, vg).unwrap();
+        (snapshot, spawned, map)
+    }
+
+    #[test]
+    fn it_spawns_a_bb_graph_as_entities() {
+        let mut app = build_changeset_app();
+        let world = app.world_mut();
+
+        // Triangle where the last edge runs against the others.
+        let mut graph = BBGraph::new();
+        let (_, e0) = graph.line(bb_vec2(0., 0.), bb_vec2(10., 0.));
+        let (e1_idx, e1) = graph.cubic_from(
+            e0.end_idx(),
+            bb_vec2(15., 5.),
+            bb_vec2(10., 10.),
+            bb_vec2(5., 10.),
+        );
+        let (e2_idx, _) = graph.line_from_to(e0.start_idx(), e1.end_idx());
+        graph.update_regions().unwrap();
+
+        let (snapshot, spawned, map) = round_trip(world, &graph);
+        assert_eq!(spawned.endpoints_len(), 3);
+        assert_eq!(spawned.edges_len(), 3);
+        assert_eq!(snapshot.nodes_count(), 3);
+        assert_eq!(snapshot.edges_count(), 3);
+        assert_eq!(snapshot.regions.len(), 1);
+
+        // Every endpoint is linked to two edges.
+        for endpoint in world.query::<&Endpoint>().iter(world) {
+            assert_eq!(endpoint.edge_count(), 2);
+        }
+
+        // The edge against the others keeps its direction.
+        let e2_uid = spawned.edge_uid(e2_idx).unwrap();
+        let e2_snapshot = snapshot.edge(map.edge_idx(e2_uid).unwrap()).unwrap();
+        let ends = [e2_snapshot.start_idx(), e2_snapshot.end_idx()]
+            .map(|node_idx| map.endpoint_uid(node_idx).unwrap());
+        let expected =
+            [e0.start_idx(), e1.end_idx()].map(|node_idx| spawned.endpoint_uid(node_idx).unwrap());
+        assert_eq!(ends, expected);
+
+        // Control points survive the round trip.
+        let e1_uid = spawned.edge_uid(e1_idx).unwrap();
+        let BBEdge::Cubic { ctrl1, ctrl2, .. } =
+            *snapshot.edge(map.edge_idx(e1_uid).unwrap()).unwrap()
+        else {
+            panic!("Expected a cubic edge.");
+        };
+        let ctrls = [ctrl1.to_array(), ctrl2.to_array()];
+        assert_eq!(ctrls, [[15., 5.], [10., 10.]]);
+    }
+
+    #[test]
+    fn it_spawns_branching_nodes() {
+        let mut app = build_changeset_app();
+        let world = app.world_mut();
+
+        let mut graph = BBGraph::new();
+        let (_, e0) = graph.line(bb_vec2(0., 0.), bb_vec2(10., 0.));
+        graph.line_from(e0.start_idx(), bb_vec2(0., 10.));
+        graph.line_from(e0.start_idx(), bb_vec2(-10., 0.));
+
+        let (snapshot, spawned, map) = round_trip(world, &graph);
+        assert_eq!(snapshot.nodes_count(), 4);
+        assert_eq!(snapshot.edges_count(), 3);
+
+        let center_uid = spawned.endpoint_uid(e0.start_idx()).unwrap();
+        let center_idx = map.node_idx(center_uid).unwrap();
+        assert_eq!(snapshot.node(center_idx).unwrap().adjacents().len(), 3);
+
+        let center = center_uid.entity(world).unwrap();
+        let endpoint = world.get::<Endpoint>(center).unwrap();
+        assert_eq!(endpoint.next_edges().len(), 3);
+        assert_eq!(endpoint.edge_count(), 3);
+        assert_eq!(endpoint.other_edge_uid(&endpoint.next_edges()[0]), None);
+    }
+}
@@ -0,0 +1,149 @@
+use bevy::{math::vec2, prelude::*};
+
+use bevy_spts_uid::{Uid, UidRegistry};
+use bevy_spts_vectorgraphic::{
+    commands_ext::VectorGraphicWorldExt,
+    lyon_tessellation::path::{Path, PathEvent},
+    prelude::*,
+};
+
+mod common;
+use common::{build_app, spawn_endpoint, spawn_line};
+
+/// Runs only the path building systems, so no render plugins are required.
+fn build_path_app() -> App {
+    let mut app = build_app();
+    app.add_systems(Update, sys_collect_vector_graph_path_endpoints);
+    app
+}
+
+fn endpoint(world: &World, uid: Uid) -> &Endpoint {
+    world.get::<Endpoint>(uid.entity(world).unwrap()).unwrap()
+}
+
+/// Returns the number of (closed subpaths, open subpaths, segments) in the path.
+fn count_path(path: &Path) -> (usize, usize, usize) {
+    let (mut closed, mut open, mut segments) = (0, 0, 0);
+    for event in path.iter() {
+        match event {
+            PathEvent::Begin { .. } => (),
+            PathEvent::End { close: true, .. } => closed += 1,
+            PathEvent::End { close: false, .. } => open += 1,
+            _ => segments += 1,
+        }
+    }
+    (closed, open, segments)
+}
+
+/// Builds the paths of the VectorGraphic and returns (fill, stroke) counts, see [count_path].
+fn build_paths(app: &mut App, vg: Entity) -> ((usize, usize, usize), (usize, usize, usize)) {
+    app.update();
+    let path_storage = app.world().get::<VectorGraphicPathStorage>(vg).unwrap();
+    (
+        count_path(path_storage.fill_path().unwrap()),
+        count_path(path_storage.path().unwrap()),
+    )
+}
+
+/// A square split in two by a line between the middle of its top and bottom edges.
+///
+/// ```text
+/// p3 -- p4 -- p5
+/// |     |     |
+/// p0 -- p1 -- p2
+/// ```
+fn build_split_square(world: &mut World) -> (Entity, [Uid; 6], [Uid; 7]) {
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p = [
+        vec2(0., 0.),
+        vec2(50., 0.),
+        vec2(100., 0.),
+        vec2(0., 100.),
+        vec2(50., 100.),
+        vec2(100., 100.),
+    ]
+    .map(|position| spawn_endpoint(world, vg, position));
+    let e = [
+        (p[0], p[1]),
+        (p[1], p[2]),
+        (p[2], p[5]),
+        (p[5], p[4]),
+        (p[4], p[3]),
+        (p[3], p[0]),
+        (p[1], p[4]),
+    ]
+    .map(|(prev, next)| spawn_line(world, vg, prev, next));
+    (vg, p, e)
+}
+
+#[test]
+fn it_links_any_number_of_edges() {
+    let mut app = build_path_app();
+    let world = app.world_mut();
+    let (_, p, e) = build_split_square(world);
+
+    let junction = endpoint(world, p[1]);
+    assert_eq!(junction.edge_count(), 3);
+    assert_eq!(junction.prev_edges(), &[e[0]]);
+    assert_eq!(junction.next_edges(), &[e[1], e[6]]);
+    assert_eq!(junction.other_edge_uid(&e[0]), None);
+
+    let corner = endpoint(world, p[0]);
+    assert_eq!(corner.other_edge_uid(&e[0]), Some(&e[5]));
+
+    world.resource_scope(|world, mut reg: Mut<UidRegistry>| {
+        world.despawn_edge(&mut reg, e[6]);
+    });
+    assert_eq!(endpoint(world, p[1]).next_edges(), &[e[1]]);
+    assert_eq!(endpoint(world, p[4]).edge_count(), 2);
+}
+
+#[test]
+fn it_fills_each_face() {
+    let mut app = build_path_app();
+    let (vg, _, _) = build_split_square(app.world_mut());
+
+    let (fill, stroke) = build_paths(&mut app, vg);
+    // Both halves of the square, each with 4 edges.
+    assert_eq!(fill, (2, 0, 8));
+    // Chains between the two junctions, every edge is stroked once.
+    assert_eq!(stroke, (0, 3, 7));
+}
+
+#[test]
+fn it_doesnt_fill_dangling_edges() {
+    let mut app = build_path_app();
+    let world = app.world_mut();
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p = [
+        vec2(0., 0.),
+        vec2(100., 0.),
+        vec2(50., 100.),
+        vec2(50., -50.),
+    ]
+    .map(|position| spawn_endpoint(world, vg, position));
+    spawn_line(world, vg, p[0], p[1]);
+    spawn_line(world, vg, p[1], p[2]);
+    spawn_line(world, vg, p[2], p[0]);
+    // Branches off of the triangle.
+    spawn_line(world, vg, p[0], p[3]);
+
+    let (fill, stroke) = build_paths(&mut app, vg);
+    assert_eq!(fill, (1, 0, 3));
+    assert_eq!(stroke, (1, 1, 4));
+}
+
+#[test]
+fn it_fills_open_chains_as_if_closed() {
+    let mut app = build_path_app();
+    let world = app.world_mut();
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p = [vec2(0., 0.), vec2(100., 0.), vec2(50., 100.)]
+        .map(|position| spawn_endpoint(world, vg, position));
+    spawn_line(world, vg, p[0], p[1]);
+    spawn_line(world, vg, p[2], p[1]);
+
+    let (fill, stroke) = build_paths(&mut app, vg);
+    assert_eq!(fill, (0, 1, 2));
+    assert_eq!(stroke, (0, 1, 2));
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "render")]
+
 // use bevy_app::App;
 // use bevy_ecs::{entity::Entity, query::With, world::World};
 // use bevy_hierarchy::BuildWorldChildren;
@@ -123,3 +125,60 @@
 //     assert_eq!(result.len(), 1);
 //     assert_eq!(vg, *result.first().unwrap());
 // }
+
+use bevy::{prelude::*, sprite::Mesh2dHandle};
+
+use bevy_spts_vectorgraphic::prelude::*;
+
+mod common;
+use common::{build_remesh_app, spawn_square};
+
+/// Runs the change detection, path, remesh and upload systems without the render plugins.  No
+/// `AssetPlugin` is added so dropped meshes are never freed, every mesh that is added stays in
+/// `Assets<Mesh>`.
+fn build_upload_app() -> App {
+    let mut app = build_remesh_app(RemeshMode::default());
+    app.init_resource::<Assets<Mesh>>();
+    app.add_systems(
+        Update,
+        sys_upload_vector_graphic_mesh.after(sys_apply_remesh_tasks),
+    );
+    app
+}
+
+#[test]
+fn it_reuses_the_mesh_asset_when_remeshing() {
+    let mut app = build_upload_app();
+    let (vg, p) = spawn_square(app.world_mut(), Vec2::ZERO, 100.);
+
+    app.update();
+    let handle = app.world().get::<Mesh2dHandle>(vg).unwrap().clone();
+    let mesh_count = app.world().resource::<Assets<Mesh>>().len();
+    assert_eq!(mesh_count, 1);
+
+    for i in 1..=5 {
+        let world = app.world_mut();
+        let endpoint = p[2].entity(world).unwrap();
+        world.get_mut::<Transform>(endpoint).unwrap().translation =
+            Vec3::new(100. + 10. * i as f32, 100., 0.);
+        app.update();
+
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), mesh_count);
+        assert_eq!(app.world().get::<Mesh2dHandle>(vg).unwrap(), &handle);
+    }
+
+    // The mesh was rewritten with the moved endpoint.
+    let meshes = app.world().resource::<Assets<Mesh>>();
+    let positions = meshes
+        .get(&handle.0)
+        .unwrap()
+        .attribute(Mesh::ATTRIBUTE_POSITION)
+        .unwrap()
+        .as_float3()
+        .unwrap();
+    let max_x = positions.iter().map(|p| p[0]).fold(f32::MIN, f32::max);
+    assert!(
+        max_x >= 150.,
+        "Expected mesh to reach moved endpoint, got {max_x}."
+    );
+}
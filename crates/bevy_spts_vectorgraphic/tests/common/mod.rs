@@ -0,0 +1,80 @@
+use bevy::{math::vec2, prelude::*};
+
+use bevy_spts_uid::{extension::EntityCommandsExt, Uid, UidRegistry};
+use bevy_spts_vectorgraphic::{
+    commands_ext::VectorGraphicWorldExt, prelude::*, RemeshTaskBuffers, SptsTessellators,
+};
+
+/// An app with only a `UidRegistry`, tests add the systems they need.
+#[allow(dead_code)]
+pub fn build_app() -> App {
+    let mut app = App::new();
+    app.insert_resource(UidRegistry::default());
+    app
+}
+
+/// Runs the change detection, path and remesh systems without the render plugins.
+#[allow(dead_code)]
+pub fn build_remesh_app(remesh_mode: RemeshMode) -> App {
+    let mut app = build_app();
+    app.add_plugins(TaskPoolPlugin::default());
+    app.insert_resource(remesh_mode)
+        .init_resource::<SptsTessellators>()
+        .init_resource::<RemeshTaskBuffers>();
+    app.add_systems(
+        Update,
+        (
+            sys_check_vector_graphic_children_changed,
+            sys_collect_vector_graph_path_endpoints,
+            sys_remesh_vector_graphic,
+            sys_apply_remesh_tasks,
+        )
+            .chain(),
+    );
+    app
+}
+
+#[allow(dead_code)]
+pub fn spawn_endpoint(world: &mut World, vg: Entity, position: Vec2) -> Uid {
+    let (entity, uid) = world
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(position.extend(0.))),
+            Endpoint::default(),
+        ))
+        .set_parent(vg)
+        .id_uid();
+    uid.register(world, entity);
+    uid
+}
+
+#[allow(dead_code)]
+pub fn spawn_edge(
+    world: &mut World,
+    vg: Entity,
+    edge_variant: EdgeVariant,
+    prev_endpoint: Uid,
+    next_endpoint: Uid,
+) -> Uid {
+    let mut edge = world.spawn_edge(edge_variant, prev_endpoint, next_endpoint);
+    edge.set_parent(vg);
+    let (entity, uid) = (edge.id(), *edge.get::<Uid>().unwrap());
+    uid.register(world, entity);
+    uid
+}
+
+#[allow(dead_code)]
+pub fn spawn_line(world: &mut World, vg: Entity, prev_endpoint: Uid, next_endpoint: Uid) -> Uid {
+    spawn_edge(world, vg, EdgeVariant::Line, prev_endpoint, next_endpoint)
+}
+
+/// Spawns a VectorGraphic with a square of `size` at `origin`, returning it and its corners.
+#[allow(dead_code)]
+pub fn spawn_square(world: &mut World, origin: Vec2, size: f32) -> (Entity, [Uid; 4]) {
+    let vg = world.spawn(VectorGraphicBundle::default()).id();
+    let p = [vec2(0., 0.), vec2(1., 0.), vec2(1., 1.), vec2(0., 1.)]
+        .map(|corner| spawn_endpoint(world, vg, origin + corner * size));
+    for i in 0..p.len() {
+        spawn_line(world, vg, p[i], p[(i + 1) % p.len()]);
+    }
+    (vg, p)
+}
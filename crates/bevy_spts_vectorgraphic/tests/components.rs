@@ -0,0 +1,29 @@
+use bevy::math::vec2;
+
+use bevy_spts_vectorgraphic::prelude::*;
+
+#[test]
+fn it_calculates_tight_edge_bounds() {
+    let (prev, next) = (vec2(0., 0.), vec2(10., 0.));
+    assert_eq!(
+        EdgeVariant::Line.bounds(prev, vec2(10., 5.)),
+        (vec2(0., 0.), vec2(10., 5.))
+    );
+
+    // The curve peaks halfway to the control point.
+    let (min, max) = EdgeVariant::Quadratic {
+        ctrl1: vec2(5., 10.),
+    }
+    .bounds(prev, next);
+    assert_eq!(min, vec2(0., 0.));
+    assert!(max.distance(vec2(10., 5.)) < 0.001);
+
+    let (min, max) = EdgeVariant::Cubic {
+        ctrl1: vec2(-10., 20.),
+        ctrl2: vec2(20., 20.),
+    }
+    .bounds(prev, next);
+    assert!(min.x < 0. && min.x > -10.);
+    assert!(max.x > 10. && max.x < 20.);
+    assert!((max.y - 15.).abs() < 0.001);
+}
@@ -0,0 +1,231 @@
+#![cfg(feature = "render")]
+
+use bevy::{
+    color::palettes::css::{BLACK, BLUE, LIME, RED},
+    math::{vec2, Affine2},
+    prelude::*,
+};
+
+use bevy_spts_vectorgraphic::prelude::*;
+
+fn red_to_blue() -> Vec<GradientStop> {
+    vec![
+        GradientStop::new(0., RED.into()),
+        GradientStop::new(1., BLUE.into()),
+    ]
+}
+
+fn assert_color_eq(a: LinearRgba, b: impl Into<LinearRgba>) {
+    let b = b.into();
+    assert!(
+        a.to_vec4().abs_diff_eq(b.to_vec4(), 0.001),
+        "Expected {a:?} to equal {b:?}."
+    );
+}
+
+#[test]
+fn it_paints_linear_gradients() {
+    let gradient = LinearGradient::new(vec2(0., 0.), vec2(100., 0.), red_to_blue());
+    let paint = VectorGraphicPaint::gradient(&gradient.clone().into());
+    let halfway = LinearRgba::from(RED).mix(&BLUE.into(), 0.5);
+
+    assert_color_eq(paint.color_at(vec2(0., 0.)), RED);
+    assert_color_eq(paint.color_at(vec2(50., 25.)), halfway);
+    assert_color_eq(paint.color_at(vec2(100., 0.)), BLUE);
+    // Padded outside of the gradient.
+    assert_color_eq(paint.color_at(vec2(-50., 0.)), RED);
+    assert_color_eq(paint.color_at(vec2(150., 0.)), BLUE);
+
+    let paint =
+        VectorGraphicPaint::gradient(&gradient.clone().with_spread(SpreadMode::Repeat).into());
+    assert_color_eq(paint.color_at(vec2(150., 0.)), halfway);
+    assert_color_eq(
+        paint.color_at(vec2(125., 0.)),
+        LinearRgba::from(RED).mix(&BLUE.into(), 0.25),
+    );
+
+    let paint = VectorGraphicPaint::gradient(&gradient.with_spread(SpreadMode::Reflect).into());
+    assert_color_eq(paint.color_at(vec2(150., 0.)), halfway);
+    assert_color_eq(
+        paint.color_at(vec2(125., 0.)),
+        LinearRgba::from(RED).mix(&BLUE.into(), 0.75),
+    );
+}
+
+#[test]
+fn it_paints_radial_gradients_in_local_space() {
+    // Stretched horizontally then moved so that the gradient is an ellipse centered at (100, 0).
+    let transform = Affine2::from_scale_angle_translation(vec2(2., 1.), 0., vec2(100., 0.));
+    let gradient = RadialGradient::new(Vec2::ZERO, 10., red_to_blue()).with_transform(transform);
+    let paint = VectorGraphicPaint::gradient(&gradient.into());
+
+    assert_color_eq(paint.color_at(vec2(100., 0.)), RED);
+    assert_color_eq(paint.color_at(vec2(120., 0.)), BLUE);
+    assert_color_eq(paint.color_at(vec2(100., 10.)), BLUE);
+    assert_color_eq(
+        paint.color_at(vec2(110., 0.)),
+        LinearRgba::from(RED).mix(&BLUE.into(), 0.5),
+    );
+}
+
+#[test]
+fn it_paints_singular_transforms_with_the_first_stop() {
+    let transform = Affine2::from_scale(vec2(0., 1.));
+    let gradient = LinearGradient::new(vec2(0., 0.), vec2(100., 0.), red_to_blue())
+        .with_transform(transform);
+    let paint = VectorGraphicPaint::gradient(&gradient.into());
+    assert_eq!(paint, VectorGraphicPaint::solid(RED));
+
+    let gradient = RadialGradient::new(Vec2::ZERO, 10., vec![]).with_transform(transform);
+    let paint = VectorGraphicPaint::gradient(&gradient.into());
+    assert_color_eq(paint.color_at(vec2(50., 0.)), LinearRgba::NONE);
+}
+
+#[test]
+fn it_sorts_and_limits_stops() {
+    let mut stops = vec![
+        GradientStop::new(1., BLUE.into()),
+        GradientStop::new(0., RED.into()),
+        GradientStop::new(0.5, LIME.into()),
+    ];
+    let gradient = LinearGradient::new(vec2(0., 0.), vec2(100., 0.), stops.clone());
+    let paint = VectorGraphicPaint::gradient(&gradient.into());
+    assert_color_eq(paint.color_at(vec2(0., 0.)), RED);
+    assert_color_eq(paint.color_at(vec2(50., 0.)), LIME);
+    assert_color_eq(paint.color_at(vec2(100., 0.)), BLUE);
+
+    // Stops past the limit are ignored.
+    stops.extend((0..MAX_GRADIENT_STOPS).map(|_| GradientStop::new(2., BLACK.into())));
+    let gradient = LinearGradient::new(vec2(0., 0.), vec2(100., 0.), stops);
+    let paint = VectorGraphicPaint::gradient(&gradient.with_spread(SpreadMode::Repeat).into());
+    assert_color_eq(paint.color_at(vec2(50., 0.)), LIME);
+
+    let gradient = LinearGradient::new(vec2(0., 0.), vec2(100., 0.), vec![]);
+    let paint = VectorGraphicPaint::gradient(&gradient.into());
+    assert_color_eq(paint.color_at(vec2(50., 0.)), LinearRgba::NONE);
+}
+
+fn build_app() -> App {
+    let mut app = App::new();
+    app.init_resource::<Assets<VectorGraphicMaterial>>();
+    app.add_systems(Update, sys_sync_vector_graphic_material);
+    app
+}
+
+fn material(app: &App, entity: Entity) -> &VectorGraphicMaterial {
+    let handle = app
+        .world()
+        .get::<Handle<VectorGraphicMaterial>>(entity)
+        .unwrap();
+    app.world()
+        .resource::<Assets<VectorGraphicMaterial>>()
+        .get(handle)
+        .unwrap()
+}
+
+#[test]
+fn it_syncs_gradients_to_the_material() {
+    let mut app = build_app();
+    let world = app.world_mut();
+    let handle = world
+        .resource_mut::<Assets<VectorGraphicMaterial>>()
+        .add(VectorGraphicMaterial::default());
+    let gradient: Gradient =
+        LinearGradient::new(vec2(0., 0.), vec2(100., 0.), red_to_blue()).into();
+    let vg = world
+        .spawn((
+            handle,
+            FillColor(RED.into()),
+            FillGradient(gradient.clone()),
+            StrokeColor(BLACK.into()),
+        ))
+        .id();
+
+    app.update();
+    let mat = material(&app, vg);
+    assert_eq!(mat.fill(), &VectorGraphicPaint::gradient(&gradient));
+    assert_eq!(mat.stroke(), &VectorGraphicPaint::solid(BLACK));
+
+    app.world_mut()
+        .entity_mut(vg)
+        .insert(StrokeGradient(gradient.clone()))
+        .remove::<FillGradient>();
+    app.update();
+    let mat = material(&app, vg);
+    assert_eq!(mat.fill(), &VectorGraphicPaint::solid(RED));
+    assert_eq!(mat.stroke(), &VectorGraphicPaint::gradient(&gradient));
+
+    // Without a color to fall back to the paint is reset.
+    app.world_mut()
+        .entity_mut(vg)
+        .remove::<(StrokeColor, StrokeGradient)>();
+    app.update();
+    let mat = material(&app, vg);
+    assert_eq!(mat.stroke(), &VectorGraphicPaint::default());
+
+    // Removing just the color resets the paint too.
+    app.world_mut().entity_mut(vg).remove::<FillColor>();
+    app.update();
+    let mat = material(&app, vg);
+    assert_eq!(mat.fill(), &VectorGraphicPaint::default());
+}
+
+#[cfg(feature = "changeset")]
+mod changeset {
+    use bevy_spts_changeset::{
+        commands_ext::WorldChangesetExt, events::ChangesetEvent, resource::ChangesetResource,
+    };
+    use bevy_spts_uid::{extension::EntityCommandsExt, UidRegistry};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct GradientChangeset;
+
+    #[test]
+    fn it_undoes_and_redoes_gradients() {
+        let mut app = build_app();
+        app.add_event::<ChangesetEvent>();
+        app.insert_resource(UidRegistry::default());
+        app.insert_resource(ChangesetResource::<GradientChangeset>::new());
+        app.register_type::<FillGradient>()
+            .register_type::<StrokeGradient>();
+
+        let world = app.world_mut();
+        let (entity, uid) = world.spawn_empty().id_uid();
+        uid.register(world, entity);
+
+        let linear: Gradient =
+            LinearGradient::new(vec2(0., 0.), vec2(100., 0.), red_to_blue()).into();
+        let radial: Gradient = RadialGradient::new(vec2(50., 50.), 25., red_to_blue())
+            .with_spread(SpreadMode::Reflect)
+            .with_transform(Affine2::from_angle(1.))
+            .into();
+
+        let mut commands = world.changeset();
+        commands.entity(uid).insert(FillGradient(linear.clone()));
+        let insert = commands.build();
+
+        let mut commands = world.changeset();
+        commands.entity(uid).apply(FillGradient(radial.clone()));
+        let apply = commands.build();
+
+        ChangesetResource::<GradientChangeset>::context_scope(world, |world, cx| {
+            let undo_insert = insert.apply(world, cx).unwrap();
+            assert_eq!(world.get::<FillGradient>(entity).unwrap().0, linear);
+
+            let undo_apply = apply.apply(world, cx).unwrap();
+            assert_eq!(world.get::<FillGradient>(entity).unwrap().0, radial);
+
+            let redo_apply = undo_apply.apply(world, cx).unwrap();
+            assert_eq!(world.get::<FillGradient>(entity).unwrap().0, linear);
+
+            let redo_insert = undo_insert.apply(world, cx).unwrap();
+            assert!(world.get::<FillGradient>(entity).is_none());
+
+            redo_insert.apply(world, cx).unwrap();
+            redo_apply.apply(world, cx).unwrap();
+            assert_eq!(world.get::<FillGradient>(entity).unwrap().0, radial);
+        });
+    }
+}
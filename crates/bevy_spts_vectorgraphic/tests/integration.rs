@@ -1,6 +1,6 @@
 use bevy::{math::vec2, prelude::*};
 
-use bevy_spts_uid::extension::EntityCommandsExt;
+use bevy_spts_uid::{extension::EntityCommandsExt, UidRegistry};
 use bevy_spts_vectorgraphic::{commands_ext::VectorGraphicWorldExt, prelude::*};
 
 pub fn build_endpoints(
@@ -15,11 +15,12 @@ pub fn build_endpoints(
     ),
 ) {
     let vg = world.spawn(VectorGraphicBundle::default()).id();
-    let p0 = world
+    let (e0, p0) = world
         .spawn((TransformBundle::default(), Endpoint::default()))
         .set_parent(vg)
-        .uid();
-    let p1 = world
+        .id_uid();
+    p0.register(world, e0);
+    let (e1, p1) = world
         .spawn((
             TransformBundle {
                 local: Transform {
@@ -31,9 +32,10 @@ pub fn build_endpoints(
             Endpoint::default(),
         ))
         .set_parent(vg)
-        .uid();
+        .id_uid();
+    p1.register(world, e1);
 
-    let p2 = world
+    let (e2, p2) = world
         .spawn((
             TransformBundle {
                 local: Transform {
@@ -45,9 +47,10 @@ pub fn build_endpoints(
             Endpoint::default(),
         ))
         .set_parent(vg)
-        .uid();
+        .id_uid();
+    p2.register(world, e2);
 
-    let p3 = world
+    let (e3, p3) = world
         .spawn((
             TransformBundle {
                 local: Transform {
@@ -59,7 +62,8 @@ pub fn build_endpoints(
             Endpoint::default(),
         ))
         .set_parent(vg)
-        .uid();
+        .id_uid();
+    p3.register(world, e3);
 
     (vg, (p0, p1, p2, p3))
 }
@@ -110,11 +114,10 @@ pub fn retry(attempts: usize, method: impl Fn()) {
 #[test]
 pub fn it_works_with_a_closed_shape() {
     let mut app = App::new();
-    app.add_plugins(MinimalPlugins)
-        .add_plugins(AssetPlugin::default());
+    app.add_plugins(MinimalPlugins);
 
-    app.add_plugins(VectorGraphicPlugin);
-    app.init_resource::<Assets<Mesh>>();
+    app.add_plugins(VectorGraphicCorePlugin);
+    app.insert_resource(UidRegistry::default());
 
     app.update();
 
@@ -129,17 +132,21 @@ pub fn it_works_with_a_closed_shape() {
     let world = &mut app.world_mut();
     let result = world.query::<&VectorGraphicPathStorage>().single(world);
     assert!(result.path().is_some());
+
+    // Tessellated on the CPU without the render plugin.
+    let mesh = world.query::<&VectorGraphicMesh>().single(world);
+    assert!(!mesh.positions.is_empty());
+    assert_eq!(mesh.positions.len(), mesh.shape_mix.len());
 }
 
 #[test]
 pub fn it_works_with_an_open_shape() {
     retry(10, || {
         let mut app = App::new();
-        app.add_plugins(MinimalPlugins)
-            .add_plugins(AssetPlugin::default());
+        app.add_plugins(MinimalPlugins);
 
-        app.add_plugins(VectorGraphicPlugin);
-        app.init_resource::<Assets<Mesh>>();
+        app.add_plugins(VectorGraphicCorePlugin);
+        app.insert_resource(UidRegistry::default());
 
         app.update();
 
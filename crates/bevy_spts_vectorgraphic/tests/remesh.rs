@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use bevy::{math::vec2, prelude::*};
+
+use bevy_spts_vectorgraphic::prelude::*;
+
+mod common;
+use common::{build_remesh_app, spawn_square};
+
+/// Returns the (min, max) of the positions in the mesh of the VectorGraphic.
+fn mesh_bounds(world: &World, vg: Entity) -> Option<(Vec2, Vec2)> {
+    let mesh = world.get::<VectorGraphicMesh>(vg)?;
+    // Every index has to point at a vertex of this mesh.
+    assert!(mesh
+        .indices
+        .iter()
+        .all(|i| (*i as usize) < mesh.positions.len()));
+    mesh.positions.iter().fold(None, |bounds, p| {
+        let p = vec2(p[0], p[1]);
+        Some(bounds.map_or((p, p), |(min, max): (Vec2, Vec2)| (min.min(p), max.max(p))))
+    })
+}
+
+/// Updates the app until `predicate` returns true, panicking if it takes too long.
+fn update_until(app: &mut App, predicate: impl Fn(&World) -> bool) {
+    for _ in 0..200 {
+        app.update();
+        if predicate(app.world()) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    panic!("Timed out waiting for the remesh tasks to finish.");
+}
+
+#[test]
+fn it_remeshes_many_vector_graphics_in_parallel() {
+    let mut app = build_remesh_app(RemeshMode::Synchronous);
+    let world = app.world_mut();
+    let squares: Vec<_> = (0..32)
+        .map(|i| {
+            let origin = vec2(i as f32 * 200., 0.);
+            spawn_square(world, origin, 10. + i as f32).0
+        })
+        .collect();
+
+    app.update();
+
+    // Each mesh only contains the geometry of its own square, even though the squares were
+    // tessellated into shared buffers.
+    for (i, vg) in squares.iter().enumerate() {
+        let (min, max) = mesh_bounds(app.world(), *vg).unwrap();
+        let origin = vec2(i as f32 * 200., 0.);
+        let size = 10. + i as f32;
+        assert!(
+            min.abs_diff_eq(origin, 1.),
+            "Square {i} min {min} != {origin}."
+        );
+        assert!(
+            max.abs_diff_eq(origin + size, 1.),
+            "Square {i} max {max} != {}.",
+            origin + size
+        );
+    }
+}
+
+#[test]
+fn it_remeshes_in_a_later_frame_when_deferred() {
+    let mut app = build_remesh_app(RemeshMode::Deferred);
+    let (vg, p) = spawn_square(app.world_mut(), Vec2::ZERO, 100.);
+
+    update_until(&mut app, |world| world.get::<RemeshTask>(vg).is_none());
+    let (_, max) = mesh_bounds(app.world(), vg).unwrap();
+    assert!(max.abs_diff_eq(vec2(100., 100.), 1.));
+
+    let world = app.world_mut();
+    let endpoint = p[2].entity(world).unwrap();
+    world.get_mut::<Transform>(endpoint).unwrap().translation = Vec3::new(150., 150., 0.);
+
+    update_until(&mut app, |world| world.get::<RemeshTask>(vg).is_none());
+    let (_, max) = mesh_bounds(app.world(), vg).unwrap();
+    assert!(max.abs_diff_eq(vec2(150., 150.), 1.));
+}
+
+#[test]
+fn it_keeps_remeshing_while_edited_when_deferred() {
+    let mut app = build_remesh_app(RemeshMode::Deferred);
+    let (vg, p) = spawn_square(app.world_mut(), Vec2::ZERO, 100.);
+    update_until(&mut app, |world| world.get::<RemeshTask>(vg).is_none());
+
+    // Moving the endpoint every frame mustn't keep cancelling the task before it finishes.
+    let mut max_x_while_moving = 100.;
+    for i in 1..=20 {
+        let world = app.world_mut();
+        let endpoint = p[2].entity(world).unwrap();
+        world.get_mut::<Transform>(endpoint).unwrap().translation =
+            Vec3::new(100. + 5. * i as f32, 100., 0.);
+        app.update();
+        let (_, max) = mesh_bounds(app.world(), vg).unwrap();
+        max_x_while_moving = max.x;
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(
+        max_x_while_moving > 150.,
+        "Expected the mesh to follow the endpoint, got {max_x_while_moving}."
+    );
+
+    update_until(&mut app, |world| world.get::<RemeshTask>(vg).is_none());
+    let (_, max) = mesh_bounds(app.world(), vg).unwrap();
+    // The miter of the stroke sticks out past the sharp corner.
+    assert!(max.abs_diff_eq(vec2(200., 100.), 2.));
+}
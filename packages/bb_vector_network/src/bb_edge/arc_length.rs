@@ -0,0 +1,160 @@
+use glam::Vec2;
+
+use crate::prelude::*;
+
+/// Maximum error, in world units, of each segment of an arc length table made by
+/// [BBEdge::length], [BBEdge::t_at_length] and [BBEdge::point_at_length].
+pub const DEFAULT_LENGTH_TOLERANCE: f32 = 0.01;
+/// Curves are always subdivided at least this many times so symmetric curves, whose midpoint lies
+/// on the chord, aren't mistaken for straight lines.
+const MIN_SUBDIVISION_DEPTH: u32 = 2;
+const MAX_SUBDIVISION_DEPTH: u32 = 16;
+
+/// A lookup table between the distance along an edge and its bezier `t` value.  Build it once
+/// with [BBEdge::arc_length_table] when making many queries on the same edge.
+#[derive(Debug, Clone)]
+pub struct BBArcLengthTable {
+    /// (t, distance from the start of the edge), sorted by both.
+    entries: Vec<(f32, f32)>,
+}
+
+impl BBArcLengthTable {
+    /// The total length of the edge.
+    pub fn length(&self) -> f32 {
+        self.entries.last().map_or(0., |(_, length)| *length)
+    }
+
+    /// Returns the `t` value at a given distance along the edge.  `distance` is clamped to the
+    /// length of the edge.
+    ///
+    /// * `distance`: Distance from the start of the edge
+    pub fn t_at_length(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0., self.length());
+        let i = self
+            .entries
+            .partition_point(|(_, length)| *length < distance)
+            .clamp(1, self.entries.len() - 1);
+
+        let (t0, l0) = self.entries[i - 1];
+        let (t1, l1) = self.entries[i];
+        if l1 - l0 <= 0. {
+            return t0;
+        }
+        t0 + (t1 - t0) * (distance - l0) / (l1 - l0)
+    }
+}
+
+impl BBEdge {
+    /// Builds a lookup table between distance along the edge and `t`.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `tolerance`: Maximum error of the distances in the table
+    pub fn arc_length_table(&self, graph: &BBGraph, tolerance: f32) -> BBArcLengthTable {
+        let start = self.start_pos(graph);
+        let end = self.end_pos(graph);
+        let mut entries = vec![(0., 0.)];
+
+        match self {
+            BBEdge::Line { .. } => entries.push((1., start.distance(end))),
+            _ => {
+                self.subdivide_arc_length(graph, (0., start), (1., end), tolerance, 0, &mut entries)
+            }
+        }
+
+        BBArcLengthTable { entries }
+    }
+
+    fn subdivide_arc_length(
+        &self,
+        graph: &BBGraph,
+        (t0, p0): (f32, Vec2),
+        (t1, p1): (f32, Vec2),
+        tolerance: f32,
+        depth: u32,
+        entries: &mut Vec<(f32, f32)>,
+    ) {
+        let tm = (t0 + t1) / 2.;
+        let pm = self.t_point(graph, tm);
+        let chord = p0.distance(p1);
+        let (first, second) = (p0.distance(pm), pm.distance(p1));
+        let halves = first + second;
+
+        // Segments need to be flat and evenly parameterised (so interpolating `t` is accurate).
+        let is_flat = halves - chord <= tolerance && (first - second).abs() <= tolerance;
+        if (depth >= MIN_SUBDIVISION_DEPTH && is_flat) || depth >= MAX_SUBDIVISION_DEPTH {
+            let (_, length) = *entries.last().unwrap();
+            entries.push((tm, length + first));
+            entries.push((t1, length + halves));
+            return;
+        }
+
+        self.subdivide_arc_length(graph, (t0, p0), (tm, pm), tolerance, depth + 1, entries);
+        self.subdivide_arc_length(graph, (tm, pm), (t1, p1), tolerance, depth + 1, entries);
+    }
+
+    /// Calculates the length of the edge.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    pub fn length(&self, graph: &BBGraph) -> f32 {
+        self.arc_length_table(graph, DEFAULT_LENGTH_TOLERANCE)
+            .length()
+    }
+
+    /// Returns the `t` value at a given distance along the edge, `distance` is clamped to the
+    /// length of the edge.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `distance`: Distance from the start of the edge
+    pub fn t_at_length(&self, graph: &BBGraph, distance: f32) -> f32 {
+        self.arc_length_table(graph, DEFAULT_LENGTH_TOLERANCE)
+            .t_at_length(distance)
+    }
+
+    /// Returns the point at a given distance along the edge, `distance` is clamped to the length
+    /// of the edge.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `distance`: Distance from the start of the edge
+    pub fn point_at_length(&self, graph: &BBGraph, distance: f32) -> Vec2 {
+        self.t_point(graph, self.t_at_length(graph, distance))
+    }
+
+    /// Calculates the derivative of the bezier/line at `t`.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `t`: Position along the edge
+    pub fn t_derivative(&self, graph: &BBGraph, t: f32) -> Vec2 {
+        let start = self.start_pos(graph);
+        let end = self.end_pos(graph);
+        match self {
+            BBEdge::Line { .. } => end - start,
+            BBEdge::Quadratic { ctrl1, .. } => {
+                2. * (1. - t) * (*ctrl1 - start) + 2. * t * (end - *ctrl1)
+            }
+            BBEdge::Cubic { ctrl1, ctrl2, .. } => {
+                let mt = 1. - t;
+                3. * mt * mt * (*ctrl1 - start)
+                    + 6. * mt * t * (*ctrl2 - *ctrl1)
+                    + 3. * t * t * (end - *ctrl2)
+            }
+        }
+    }
+
+    /// Returns the normalised direction of the edge at `t`.  Falls back to the direction of
+    /// nearby points where the derivative is zero (i.e. a control point on an end point).
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `t`: Position along the edge
+    pub fn t_tangent(&self, graph: &BBGraph, t: f32) -> Vec2 {
+        let derivative = self.t_derivative(graph, t);
+        if derivative.length_squared() > f32::EPSILON {
+            return derivative.normalize();
+        }
+        let before = self.t_point(graph, (t - 0.001).max(0.));
+        let after = self.t_point(graph, (t + 0.001).min(1.));
+        match (after - before).try_normalize() {
+            Some(tangent) => tangent,
+            None => (self.end_pos(graph) - self.start_pos(graph)).normalize_or_zero(),
+        }
+    }
+}
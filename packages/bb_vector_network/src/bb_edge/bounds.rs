@@ -0,0 +1,54 @@
+use glam::Vec2;
+
+use crate::prelude::*;
+
+use super::cubic_point;
+
+/// Returns the roots of `a * t^2 + b * t + c` that are strictly between 0 and 1.
+fn unit_quadratic_roots(a: f32, b: f32, c: f32) -> Vec<f32> {
+    let roots = if a.abs() < f32::EPSILON {
+        if b.abs() < f32::EPSILON {
+            vec![]
+        } else {
+            vec![-c / b]
+        }
+    } else {
+        let discriminant = b * b - 4. * a * c;
+        if discriminant < 0. {
+            vec![]
+        } else {
+            let sqrt = discriminant.sqrt();
+            vec![(-b + sqrt) / (2. * a), (-b - sqrt) / (2. * a)]
+        }
+    };
+    roots.into_iter().filter(|t| *t > 0. && *t < 1.).collect()
+}
+
+/// Returns the (min, max) of the tight bounding box of a cubic bezier, calculated from its start,
+/// end and extrema.  Lines and quadratics can be passed as degree elevated cubics.
+///
+/// * `points`: The (start, ctrl1, ctrl2, end) points of the bezier
+pub fn cubic_bounds([start, ctrl1, ctrl2, end]: [Vec2; 4]) -> (Vec2, Vec2) {
+    let a = 3. * (-start + 3. * ctrl1 - 3. * ctrl2 + end);
+    let b = 6. * (start - 2. * ctrl1 + ctrl2);
+    let c = 3. * (ctrl1 - start);
+    let mut extrema = unit_quadratic_roots(a.x, b.x, c.x);
+    extrema.extend(unit_quadratic_roots(a.y, b.y, c.y));
+
+    extrema
+        .into_iter()
+        .map(|t| cubic_point([start, ctrl1, ctrl2, end], t))
+        .fold((start.min(end), start.max(end)), |(min, max), p| {
+            (min.min(p), max.max(p))
+        })
+}
+
+impl BBEdge {
+    /// Returns the (min, max) of the tight bounding box of the curve, calculated from the start,
+    /// end and extrema of the curve.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    pub fn bounds(&self, graph: &BBGraph) -> (Vec2, Vec2) {
+        cubic_bounds(self.cubic_points(graph))
+    }
+}
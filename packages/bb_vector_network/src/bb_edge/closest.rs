@@ -0,0 +1,90 @@
+use glam::Vec2;
+
+use crate::prelude::*;
+
+/// How many points along a curve are sampled to find the neighbourhood of the closest point.
+const CLOSEST_POINT_SAMPLES: usize = 32;
+/// How many golden section iterations are used to refine each neighbourhood.
+const CLOSEST_POINT_ITERATIONS: usize = 32;
+const INV_GOLDEN_RATIO: f32 = 0.618_034;
+
+impl BBEdge {
+    /// Finds the point on the edge that is closest to `p`.
+    ///
+    /// Returns `(t, point, distance)` where `t` is the position along the edge of `point`.
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `p`: The point to project onto the edge
+    pub fn closest_point(&self, graph: &BBGraph, p: Vec2) -> (f32, Vec2, f32) {
+        if let BBEdge::Line { .. } = self {
+            let start = self.start_pos(graph);
+            let delta = self.end_pos(graph) - start;
+            let t = match delta.length_squared() {
+                len_sq if len_sq < f32::EPSILON => 0.,
+                len_sq => ((p - start).dot(delta) / len_sq).clamp(0., 1.),
+            };
+            let point = start + delta * t;
+            return (t, point, point.distance(p));
+        }
+
+        let distances: Vec<f32> = (0..=CLOSEST_POINT_SAMPLES)
+            .map(|i| {
+                self.t_point(graph, i as f32 / CLOSEST_POINT_SAMPLES as f32)
+                    .distance(p)
+            })
+            .collect();
+
+        // Refine around every sample that is a local minimum, a curve can pass near the point
+        // more than once.
+        let mut best = (0., self.start_pos(graph), distances[0]);
+        for (i, distance) in distances.iter().enumerate() {
+            let is_local_minimum = (i == 0 || *distance <= distances[i - 1])
+                && (i == CLOSEST_POINT_SAMPLES || *distance <= distances[i + 1]);
+            if !is_local_minimum {
+                continue;
+            }
+            let step = 1. / CLOSEST_POINT_SAMPLES as f32;
+            let t_min = (i as f32 - 1.) * step;
+            let t_max = (i as f32 + 1.) * step;
+            let t = self.refine_closest_t(graph, p, t_min.max(0.), t_max.min(1.));
+            let point = self.t_point(graph, t);
+            let distance = point.distance(p);
+            if distance < best.2 {
+                best = (t, point, distance);
+            }
+        }
+
+        best
+    }
+
+    /// Golden section search for the `t` closest to `p` between `t_min` and `t_max`.
+    fn refine_closest_t(&self, graph: &BBGraph, p: Vec2, mut t_min: f32, mut t_max: f32) -> f32 {
+        let distance_at = |t: f32| self.t_point(graph, t).distance_squared(p);
+
+        let mut t_a = t_max - (t_max - t_min) * INV_GOLDEN_RATIO;
+        let mut t_b = t_min + (t_max - t_min) * INV_GOLDEN_RATIO;
+        let mut d_a = distance_at(t_a);
+        let mut d_b = distance_at(t_b);
+        for _ in 0..CLOSEST_POINT_ITERATIONS {
+            if d_a < d_b {
+                t_max = t_b;
+                t_b = t_a;
+                d_b = d_a;
+                t_a = t_max - (t_max - t_min) * INV_GOLDEN_RATIO;
+                d_a = distance_at(t_a);
+            } else {
+                t_min = t_a;
+                t_a = t_b;
+                d_a = d_b;
+                t_b = t_min + (t_max - t_min) * INV_GOLDEN_RATIO;
+                d_b = distance_at(t_b);
+            }
+        }
+
+        // The search never evaluates the bounds themselves, which matters at the end points.
+        [t_min, (t_min + t_max) / 2., t_max]
+            .into_iter()
+            .min_by(|a, b| distance_at(*a).total_cmp(&distance_at(*b)))
+            .unwrap()
+    }
+}
@@ -0,0 +1,29 @@
+use glam::Vec2;
+
+/// Evaluates the cubic bezier (start, ctrl1, ctrl2, end) at `t`.
+pub(crate) fn cubic_point([p0, p1, p2, p3]: [Vec2; 4], t: f32) -> Vec2 {
+    let mt = 1. - t;
+    p0 * mt * mt * mt + p1 * 3. * mt * mt * t + p2 * 3. * mt * t * t + p3 * t * t * t
+}
+
+/// The first derivative of the cubic bezier (start, ctrl1, ctrl2, end) at `t`.
+pub(crate) fn cubic_derivative([p0, p1, p2, p3]: [Vec2; 4], t: f32) -> Vec2 {
+    let mt = 1. - t;
+    (p1 - p0) * 3. * mt * mt + (p2 - p1) * 6. * mt * t + (p3 - p2) * 3. * t * t
+}
+
+/// The second derivative of the cubic bezier (start, ctrl1, ctrl2, end) at `t`.
+pub(crate) fn cubic_second_derivative([p0, p1, p2, p3]: [Vec2; 4], t: f32) -> Vec2 {
+    (p2 - p1 * 2. + p0) * 6. * (1. - t) + (p3 - p2 * 2. + p1) * 6. * t
+}
+
+/// Splits a cubic bezier in half with de Casteljau's algorithm.
+pub(crate) fn split_cubic([p0, p1, p2, p3]: [Vec2; 4]) -> ([Vec2; 4], [Vec2; 4]) {
+    let p01 = p0.lerp(p1, 0.5);
+    let p12 = p1.lerp(p2, 0.5);
+    let p23 = p2.lerp(p3, 0.5);
+    let p012 = p01.lerp(p12, 0.5);
+    let p123 = p12.lerp(p23, 0.5);
+    let middle = p012.lerp(p123, 0.5);
+    ([p0, p01, p012, middle], [middle, p123, p23, p3])
+}
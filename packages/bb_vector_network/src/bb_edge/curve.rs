@@ -38,8 +38,8 @@ impl BBEdge {
                 let start = graph.node(*start)?.position();
                 let end = graph.node(*end)?.position();
 
-                let ctrl2 = start + TWO_THIRDS * (*ctrl1 - start);
-                let ctrl1 = end + TWO_THIRDS * (*ctrl1 - end);
+                let ctrl2 = end + TWO_THIRDS * (*ctrl1 - end);
+                let ctrl1 = start + TWO_THIRDS * (*ctrl1 - start);
                 Ok(Curve::from_points(
                     v2_to_c2(start),
                     (v2_to_c2(ctrl1), v2_to_c2(ctrl2)),
@@ -134,7 +134,6 @@ impl BBEdgeCurveHelpers for Curve<Coord2> {
             }
         };
 
-        println!("Added {self:?} to graph.  characteristics: {characteristics:?}");
         (idx, edge)
     }
 }
@@ -0,0 +1,85 @@
+use glam::Vec2;
+
+use crate::prelude::*;
+
+use super::split_cubic;
+
+/// Default maximum distance, in world units, between a curve and the polyline it is flattened to.
+pub const DEFAULT_FLATTEN_TOLERANCE: f32 = 0.1;
+/// Limits flattening to 2^12 segments per edge, no matter how small the tolerance.
+const MAX_FLATTEN_DEPTH: u32 = 12;
+
+/// Distance from `p` to the line segment `a` -> `b`.
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let delta = b - a;
+    let t = match delta.length_squared() {
+        len_sq if len_sq < f32::EPSILON => 0.,
+        len_sq => ((p - a).dot(delta) / len_sq).clamp(0., 1.),
+    };
+    p.distance(a + delta * t)
+}
+
+impl BBEdge {
+    /// Returns the (start, ctrl1, ctrl2, end) points of the edge as a cubic bezier.
+    pub(crate) fn cubic_points(&self, graph: &BBGraph) -> [Vec2; 4] {
+        let start = self.start_pos(graph);
+        let end = self.end_pos(graph);
+        match self {
+            BBEdge::Line { .. } => [start, start, end, end],
+            BBEdge::Quadratic { ctrl1, .. } => [
+                start,
+                start + (*ctrl1 - start) * 2. / 3.,
+                end + (*ctrl1 - end) * 2. / 3.,
+                end,
+            ],
+            BBEdge::Cubic { ctrl1, ctrl2, .. } => [start, *ctrl1, *ctrl2, end],
+        }
+    }
+
+    /// Approximates the edge as a polyline that is never further than `tolerance` from the
+    /// curve.  Returns `(t, point)` pairs from the start of the edge (`t = 0`) to the end
+    /// (`t = 1`).
+    ///
+    /// * `graph`: The BBGraph to source the point data from
+    /// * `tolerance`: Maximum distance between the curve and the polyline
+    pub fn flatten(&self, graph: &BBGraph, tolerance: f32) -> Vec<(f32, Vec2)> {
+        let points = self.cubic_points(graph);
+        if let BBEdge::Line { .. } = self {
+            return vec![(0., points[0]), (1., points[3])];
+        }
+        flatten_cubic(points, tolerance)
+    }
+}
+
+/// Approximates a cubic bezier as a polyline that is never further than `tolerance` from the
+/// curve.  Returns `(t, point)` pairs from `t = 0` to `t = 1`.
+///
+/// * `points`: The (start, ctrl1, ctrl2, end) points of the bezier
+/// * `tolerance`: Maximum distance between the curve and the polyline
+pub(crate) fn flatten_cubic(points: [Vec2; 4], tolerance: f32) -> Vec<(f32, Vec2)> {
+    let mut out = vec![(0., points[0])];
+    subdivide_cubic(points, (0., 1.), tolerance, 0, &mut out);
+    out
+}
+
+/// Recursively splits the cubic in half until its control points are within `tolerance` of its
+/// chord.  A bezier is contained within the hull of its control points so this bounds the error.
+fn subdivide_cubic(
+    [p0, p1, p2, p3]: [Vec2; 4],
+    (t0, t1): (f32, f32),
+    tolerance: f32,
+    depth: u32,
+    out: &mut Vec<(f32, Vec2)>,
+) {
+    let error = distance_to_segment(p1, p0, p3).max(distance_to_segment(p2, p0, p3));
+    if error <= tolerance || depth >= MAX_FLATTEN_DEPTH {
+        out.push((t1, p3));
+        return;
+    }
+
+    let (first, second) = split_cubic([p0, p1, p2, p3]);
+    let tm = (t0 + t1) / 2.;
+
+    subdivide_cubic(first, (t0, tm), tolerance, depth + 1, out);
+    subdivide_cubic(second, (tm, t1), tolerance, depth + 1, out);
+}
@@ -1,10 +1,15 @@
 #![allow(dead_code)]
 
+mod arc_length;
+mod bounds;
+mod closest;
+mod cubic;
 mod curve;
+mod flatten;
 
 use std::{fmt::Display, ops::Add};
 
-use glam::{Mat2, Vec2};
+use glam::{Affine2, Mat2, Vec2};
 
 #[allow(unused_imports)]
 #[cfg(feature = "debug_draw")]
@@ -16,31 +21,37 @@ use super::{
     errors::BBResult,
 };
 
+pub use arc_length::{BBArcLengthTable, DEFAULT_LENGTH_TOLERANCE};
+pub use bounds::cubic_bounds;
+pub(crate) use cubic::{cubic_derivative, cubic_point, cubic_second_derivative, split_cubic};
 pub use curve::{ c2_to_v2, v2_to_c2, BBEdgeCurveHelpers };
+pub use flatten::DEFAULT_FLATTEN_TOLERANCE;
+pub(crate) use flatten::flatten_cubic;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 #[cfg_attr( feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-/// Represents an index position of a BBVNRegion, which are joins between two nodes.
-pub struct BBEdgeIndex(pub usize);
-impl From<usize> for BBEdgeIndex {
-    fn from(value: usize) -> Self {
-        Self(value)
-    }
+/// Represents a reference to an edge in the BBVectorNetwork.  Indices are generational, a deleted
+/// edge's index will never point to an edge that later re-uses its slot.
+pub struct BBEdgeIndex {
+    slot: usize,
+    generation: u32,
 }
-impl From<BBEdgeIndex> for usize {
-    fn from(value: BBEdgeIndex) -> Self {
-        value.0
+impl BBEdgeIndex {
+    pub fn new(slot: usize, generation: u32) -> Self {
+        Self { slot, generation }
     }
-}
-
-impl From<&mut BBEdgeIndex> for usize {
-    fn from(value: &mut BBEdgeIndex) -> Self {
-        value.0
+    /// The storage slot of this index, slots are re-used after they're deleted.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+    /// How many times the slot has been re-used, an index from an older generation is stale.
+    pub fn generation(&self) -> u32 {
+        self.generation
     }
 }
 impl Display for BBEdgeIndex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "e#{}", self.0)
+        write!(f, "e#{}v{}", self.slot, self.generation)
     }
 }
 
@@ -158,6 +169,28 @@ impl BBEdge {
         }
     }
 
+    /// Returns the (min, max) of the bounding box of the edge's start, end and control points.
+    /// The curve is always contained within this box but it's not necessarily tight.
+    ///
+    /// * `bbvn`: The BBGraph to source the point data from
+    pub fn control_bounds(&self, bbvn: &BBGraph) -> (Vec2, Vec2) {
+        let start = self.start_pos(bbvn);
+        let end = self.end_pos(bbvn);
+        let (mut min, mut max) = (start.min(end), start.max(end));
+        match self {
+            BBEdge::Line { .. } => (),
+            BBEdge::Quadratic { ctrl1, .. } => {
+                min = min.min(*ctrl1);
+                max = max.max(*ctrl1);
+            }
+            BBEdge::Cubic { ctrl1, ctrl2, .. } => {
+                min = min.min(*ctrl1).min(*ctrl2);
+                max = max.max(*ctrl1).max(*ctrl2);
+            }
+        }
+        (min, max)
+    }
+
     /// Calculates the tangent of the bezier/line at `t=0`
     ///
     /// * `bbvn`: The BBGraph to source the point data from
@@ -303,6 +336,23 @@ impl BBEdge {
         }
     }
 
+    /// Applies an affine transform to the control points of the edge, the start and end nodes
+    /// must be transformed separately.
+    ///
+    /// * `transform`: The transform to apply
+    pub fn transform(&mut self, transform: &Affine2) {
+        match self {
+            Self::Quadratic { ctrl1, .. } => {
+                *ctrl1 = transform.transform_point2(*ctrl1);
+            }
+            Self::Cubic { ctrl1, ctrl2, .. } => {
+                *ctrl1 = transform.transform_point2(*ctrl1);
+                *ctrl2 = transform.transform_point2(*ctrl2);
+            }
+            _ => (),
+        }
+    }
+
     pub fn adjacents(&self, graph: &BBGraph) -> BBResult<Vec<BBEdgeIndex>> {
         let start_adjs = graph.node(self.start_idx())?.adjacents();
         let end_adjs = graph.node(self.end_idx())?.adjacents();
@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::prelude::*;
+
+/// Nodes closer than this fraction of the size of the result are treated as the same node, it's
+/// also how far either side of an edge is sampled when labelling the faces it separates.
+const RELATIVE_TOLERANCE: f32 = 0.001;
+/// Fraction of the tolerance that curves are flattened to when testing coverage.
+pub(crate) const COVERAGE_TOLERANCE: f32 = 0.25;
+/// Tolerances are kept this many float epsilons away from the positions they're applied to.
+const MIN_TOLERANCE_EPSILONS: f32 = 64.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The boolean operation to apply when combining two graphs with [BBGraph::boolean].
+pub enum BBBooleanOp {
+    /// Keeps faces covered by either graph.
+    Union,
+    /// Keeps faces covered by both graphs.
+    Intersect,
+    /// Keeps faces covered by self but not by the other graph.
+    Difference,
+    /// Keeps faces covered by exactly one of the graphs.
+    Xor,
+}
+
+impl BBBooleanOp {
+    /// Returns true if a face with the given coverage should be kept by this operation.
+    ///
+    /// * `in_a`: Whether the face is covered by self
+    /// * `in_b`: Whether the face is covered by the other graph
+    pub fn selects(&self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BBBooleanOp::Union => in_a || in_b,
+            BBBooleanOp::Intersect => in_a && in_b,
+            BBBooleanOp::Difference => in_a && !in_b,
+            BBBooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+/// Flattened outline of a graph used to test which points it covers (even-odd).
+struct Coverage {
+    segments: Vec<(Vec2, Vec2)>,
+}
+
+impl Coverage {
+    /// * `tolerance`: Maximum distance between the curves and the flattened outline
+    fn new(graph: &BBGraph, tolerance: f32) -> BBResult<Self> {
+        let mut graph = graph.clone();
+        // Filaments don't enclose anything.
+        graph.remove_filaments()?;
+
+        let mut segments = vec![];
+        for edge in graph.edges.values() {
+            let points = edge.flatten(&graph, tolerance);
+            segments.extend(points.windows(2).map(|w| (w[0].1, w[1].1)));
+        }
+
+        Ok(Self { segments })
+    }
+
+    fn contains(&self, p: Vec2) -> bool {
+        let mut inside = false;
+        for (a, b) in &self.segments {
+            if (a.y > p.y) != (b.y > p.y) {
+                let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if x > p.x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+impl BBGraph {
+    /// Combines self with another graph, returning a new graph that only contains the outline of
+    /// the faces selected by `op`.  Both graphs are treated as even-odd filled shapes, the
+    /// regions of the result are recalculated.
+    ///
+    /// * `other`: The graph to combine with
+    /// * `op`: The boolean operation to perform
+    pub fn boolean(&self, other: &BBGraph, op: BBBooleanOp) -> BBResult<BBGraph> {
+        let mut result = self.clone();
+        result.append_graph(other);
+        let tolerance = result.scaled_tolerance();
+
+        // Side samples are `tolerance` from each edge, the outlines need to be closer than that.
+        let coverage_a = Coverage::new(self, tolerance * COVERAGE_TOLERANCE)?;
+        let coverage_b = Coverage::new(other, tolerance * COVERAGE_TOLERANCE)?;
+
+        // An edge is only part of the result if it borders a selected face and an unselected one.
+        result.keep_boundary_edges(|p| {
+            op.selects(coverage_a.contains(p), coverage_b.contains(p))
+        })?;
+        result.update_regions()?;
+
+        Ok(result)
+    }
+
+    /// Splits the edges where they cross, welds and dedupes them, then only keeps the edges that
+    /// separate a covered area from an uncovered one.  Samples either side of each edge are
+    /// [BBGraph::scaled_tolerance] away from it.
+    ///
+    /// * `covers`: Returns true if a point is inside the resulting shape
+    pub(crate) fn keep_boundary_edges(&mut self, covers: impl Fn(Vec2) -> bool) -> BBResult<()> {
+        let tolerance = self.scaled_tolerance();
+        self.expand_intersections()?;
+        self.weld_nodes(tolerance)?;
+        self.dedupe_edges()?;
+
+        let mut to_delete = vec![];
+        for (edge_idx, edge) in self.edges.iter() {
+            let (left, right) = self.edge_side_samples(edge, tolerance);
+            if covers(left) == covers(right) {
+                to_delete.push(*edge_idx);
+            }
+        }
+        to_delete.sort();
+        for edge_idx in to_delete {
+            self.delete_edge(edge_idx)?;
+        }
+        Ok(())
+    }
+
+    /// Distance used to weld nodes and sample either side of edges, relative to the size of the
+    /// graph so that it works for both tiny and huge shapes.
+    pub(crate) fn scaled_tolerance(&self) -> f32 {
+        let Some((min, max)) = self.bounds() else {
+            return f32::EPSILON;
+        };
+        let magnitude = min.abs().max(max.abs()).max_element();
+        (min.distance(max) * RELATIVE_TOLERANCE)
+            .max(magnitude * f32::EPSILON * MIN_TOLERANCE_EPSILONS)
+            .max(f32::EPSILON)
+    }
+
+    /// Copies all of the nodes and edges from another graph into self, returning the indices of
+    /// the newly added edges.
+    ///
+    /// * `other`: The graph to copy from
+    fn append_graph(&mut self, other: &BBGraph) -> Vec<BBEdgeIndex> {
+        let mut node_indices: Vec<_> = other.nodes.keys().copied().collect();
+        node_indices.sort();
+        let node_map: HashMap<BBNodeIndex, BBNodeIndex> = node_indices
+            .into_iter()
+            .map(|idx| (idx, self.add_node(other.nodes[&idx].position())))
+            .collect();
+
+        let mut edge_indices: Vec<_> = other.edges.keys().copied().collect();
+        edge_indices.sort();
+        edge_indices
+            .into_iter()
+            .map(|idx| {
+                let mut edge = other.edges[&idx];
+                edge.set_start_idx(node_map[&edge.start_idx()]);
+                edge.set_end_idx(node_map[&edge.end_idx()]);
+                let (idx, _) = self.add_edge(edge);
+                idx
+            })
+            .collect()
+    }
+}
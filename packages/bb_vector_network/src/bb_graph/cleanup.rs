@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::{bb_edge::c2_to_v2, prelude::*};
+
+/// Edges between the same nodes whose control points are all within this distance of each other
+/// are considered duplicates by [BBGraph::dedupe_edges].
+const DUPLICATE_EDGE_DISTANCE: f32 = 0.01;
+
+/// What was changed by [BBGraph::weld_nodes].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BBWeldReport {
+    /// Maps each removed node to the node it was merged into.
+    pub merged_nodes: HashMap<BBNodeIndex, BBNodeIndex>,
+    /// Edges that were removed because they collapsed to a single point.
+    pub removed_edges: Vec<BBEdgeIndex>,
+}
+
+impl BBWeldReport {
+    /// Returns true if nothing was welded.
+    pub fn is_empty(&self) -> bool {
+        self.merged_nodes.is_empty() && self.removed_edges.is_empty()
+    }
+
+    /// Returns the index that replaces `node_idx`, or `node_idx` if it wasn't merged.
+    pub fn remap_node(&self, node_idx: BBNodeIndex) -> BBNodeIndex {
+        self.merged_nodes
+            .get(&node_idx)
+            .copied()
+            .unwrap_or(node_idx)
+    }
+}
+
+/// What was changed by [BBGraph::dedupe_edges].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BBDedupeReport {
+    /// Maps each removed edge to the edge it duplicated, which was kept.
+    pub merged_edges: HashMap<BBEdgeIndex, BBEdgeIndex>,
+}
+
+impl BBDedupeReport {
+    /// Returns true if no edges were removed.
+    pub fn is_empty(&self) -> bool {
+        self.merged_edges.is_empty()
+    }
+
+    /// Returns the index that replaces `edge_idx`, or `edge_idx` if it wasn't removed.
+    pub fn remap_edge(&self, edge_idx: BBEdgeIndex) -> BBEdgeIndex {
+        self.merged_edges
+            .get(&edge_idx)
+            .copied()
+            .unwrap_or(edge_idx)
+    }
+}
+
+impl BBGraph {
+    /// Merges nodes that are within `tolerance` of each other, moving their edges onto the node
+    /// they're merged into.  Edges that collapse to a single point are removed, curves that loop
+    /// back onto the same node are kept.  Regions aren't updated, call
+    /// [BBGraph::update_regions] afterwards.
+    ///
+    /// * `tolerance`: Maximum distance between nodes that are merged
+    pub fn weld_nodes(&mut self, tolerance: f32) -> BBResult<BBWeldReport> {
+        let mut report = BBWeldReport::default();
+
+        // Sweep along x so only nearby nodes are compared.
+        let mut node_indices: Vec<_> = self.nodes.keys().copied().collect();
+        node_indices.sort_by(|a, b| {
+            let (pos_a, pos_b) = (self.nodes[a].position(), self.nodes[b].position());
+            pos_a.x.total_cmp(&pos_b.x).then(a.cmp(b))
+        });
+
+        for (i, into) in node_indices.iter().enumerate() {
+            if report.merged_nodes.contains_key(into) {
+                continue;
+            }
+            let into_pos = self.node(*into)?.position();
+            for from in &node_indices[(i + 1)..] {
+                if report.merged_nodes.contains_key(from) {
+                    continue;
+                }
+                let from_pos = self.node(*from)?.position();
+                if from_pos.x - into_pos.x > tolerance {
+                    break;
+                }
+                if into_pos.distance(from_pos) > tolerance {
+                    continue;
+                }
+
+                self.merge_node(*from, *into, tolerance, &mut report)?;
+            }
+        }
+
+        self.debug_validate(false);
+        Ok(report)
+    }
+
+    /// Moves the edges of `from` onto `into` and removes `from`.
+    fn merge_node(
+        &mut self,
+        from: BBNodeIndex,
+        into: BBNodeIndex,
+        tolerance: f32,
+        report: &mut BBWeldReport,
+    ) -> BBResult<()> {
+        let mut adjacents = self.node(from)?.adjacents.clone();
+        adjacents.sort();
+        adjacents.dedup();
+        self.take_node(from);
+        report.merged_nodes.insert(from, into);
+
+        for edge_idx in adjacents {
+            // Number of ends moved onto `into`, loops need to be listed once per end.
+            let mut moved = 0;
+            let edge = self.edge_mut(edge_idx)?;
+            if edge.start_idx() == from {
+                edge.set_start_idx(into);
+                moved += 1;
+            }
+            if edge.end_idx() == from {
+                edge.set_end_idx(into);
+                moved += 1;
+            }
+            let edge = *edge;
+
+            let into_pos = self.node(into)?.position();
+            let (min, max) = edge.control_bounds(self);
+            let collapsed = edge.start_idx() == edge.end_idx()
+                && into_pos.distance(min).max(into_pos.distance(max)) <= tolerance;
+
+            let into_adjacents = self.adjacents_mut(into)?;
+            if collapsed {
+                into_adjacents.retain(|idx| *idx != edge_idx);
+                self.take_edge(edge_idx);
+                report.removed_edges.push(edge_idx);
+            } else {
+                for _ in 0..moved {
+                    into_adjacents.push(edge_idx);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes edges that connect the same nodes with the same geometry, keeping the edge with
+    /// the lowest index.  The direction of the edges doesn't matter.  Regions aren't updated,
+    /// call [BBGraph::update_regions] afterwards.
+    pub fn dedupe_edges(&mut self) -> BBResult<BBDedupeReport> {
+        let mut report = BBDedupeReport::default();
+
+        let mut edge_indices: Vec<_> = self.edges.keys().copied().collect();
+        edge_indices.sort();
+
+        let mut by_nodes: HashMap<(BBNodeIndex, BBNodeIndex), Vec<BBEdgeIndex>> = HashMap::new();
+        for edge_idx in edge_indices {
+            let edge = *self.edge(edge_idx)?;
+            let key = (
+                edge.start_idx().min(edge.end_idx()),
+                edge.start_idx().max(edge.end_idx()),
+            );
+            let siblings = by_nodes.entry(key).or_default();
+
+            let curve = edge.directed_from(key.0).as_curve(self)?;
+            let mut duplicate_of = None;
+            for sibling_idx in siblings.iter() {
+                let sibling = self
+                    .edge(*sibling_idx)?
+                    .directed_from(key.0)
+                    .as_curve(self)?;
+                let (c1, c2) = curve.control_points;
+                let (s1, s2) = sibling.control_points;
+                let max_dist = c2_to_v2(c1)
+                    .distance(c2_to_v2(s1))
+                    .max(c2_to_v2(c2).distance(c2_to_v2(s2)));
+                if max_dist < DUPLICATE_EDGE_DISTANCE {
+                    duplicate_of = Some(*sibling_idx);
+                    break;
+                }
+            }
+
+            match duplicate_of {
+                Some(sibling_idx) => {
+                    self.delete_edge(edge_idx)?;
+                    report.merged_edges.insert(edge_idx, sibling_idx);
+                }
+                None => siblings.push(edge_idx),
+            }
+        }
+
+        self.debug_validate(false);
+        Ok(report)
+    }
+}
@@ -5,131 +5,290 @@ use flo_curves::{
 };
 use glam::Vec2;
 
-use crate::{bb_edge::{c2_to_v2, BBEdgeCurveHelpers}, prelude::*};
+use crate::{bb_edge::BBEdgeCurveHelpers, prelude::*};
 
 const MIN_DISTANCE_TO: f32 = 0.0001;
 const MIN_DISTANCE_TO_F64: f64 = 0.0001;
+/// Two cubic beziers can intersect at most 9 times, any more than this and the curves are
+/// overlapping.
+const MAX_CURVE_INTERSECTIONS: usize = 9;
+/// Curve intersections are found to within this fraction of [BBGraph::scaled_tolerance], so
+/// that small graphs are clipped as accurately as large ones.
+const CLIP_ACCURACY: f32 = 0.1;
+/// Finer accuracy on large graphs only finds spurious intersections where curves graze.
+const MAX_CLIP_ACCURACY: f32 = 0.01;
 
-// // Key for storing an intersection from edge 0 to edge 1
-// type EdgeToEdgeKey = (BBEdgeIndex, BBEdgeIndex);
-// // Value for the EdgeToEdgeKey key, stores the positions along edge 0 where it hit edge 1.
-// // The Option<f64> stores the t_position that the edge was hit at.
-// type IntersectionResult = Vec<(f64, f64, Vec2)>;
-//
-#[derive(Debug)]
-struct Intersection {
-    pub other_idx: BBEdgeIndex,
-    pub self_t: f32,
-    pub other_t: f32,
-    pub pos: Vec2,
+/// An edge as a flo_curves bezier and its bounding box.
+type EdgeCurve = (Curve<Coord2>, Bounds<Coord2>);
+
+/// A position along an edge where it should be split, and the node to split it at.
+#[derive(Debug, Clone, Copy)]
+struct SplitPoint {
+    pub t: f32,
+    pub node_idx: BBNodeIndex,
 }
 
 pub fn map_linear(v: f32, in_min: f32, in_max: f32, out_min: f32, out_max: f32) -> f32 {
     (v - in_min) * (out_max - out_min) / (in_max - in_min) + out_min
 }
 
+fn t_is_at_end(t: f32) -> bool {
+    !(MIN_DISTANCE_TO..=1. - MIN_DISTANCE_TO).contains(&t)
+}
+
+/// Calculates the intersections between two straight line segments.  Collinear segments that
+/// overlap will return the points where the overlap starts/ends.
+///
+/// Returns a list of `(t_a, t_b)` along each line.
+fn line_intersects_line(a0: Vec2, a1: Vec2, b0: Vec2, b1: Vec2) -> Vec<(f32, f32)> {
+    let da = a1 - a0;
+    let db = b1 - b0;
+    let denom = da.determinate(db);
+
+    let len_a = da.length();
+    let len_b = db.length();
+    if len_a < MIN_DISTANCE_TO || len_b < MIN_DISTANCE_TO {
+        return vec![];
+    }
+
+    if (denom / (len_a * len_b)).abs() < MIN_DISTANCE_TO {
+        // Parallel, only care about it if they're collinear.
+        let offset = (b0 - a0).determinate(da) / len_a;
+        if offset.abs() > MIN_DISTANCE_TO {
+            return vec![];
+        }
+        let project_a = |p: Vec2| (p - a0).dot(da) / (len_a * len_a);
+        let project_b = |p: Vec2| (p - b0).dot(db) / (len_b * len_b);
+
+        let mut result = vec![];
+        for (t_a, t_b) in [(project_a(b0), 0.), (project_a(b1), 1.)] {
+            if (0. ..=1.).contains(&t_a) {
+                result.push((t_a, t_b));
+            }
+        }
+        for (t_a, t_b) in [(0., project_b(a0)), (1., project_b(a1))] {
+            if (0. ..=1.).contains(&t_b) {
+                result.push((t_a, t_b));
+            }
+        }
+        return result;
+    }
+
+    let diff = b0 - a0;
+    let t_a = diff.determinate(db) / denom;
+    let t_b = diff.determinate(da) / denom;
+    if (0. ..=1.).contains(&t_a) && (0. ..=1.).contains(&t_b) {
+        vec![(t_a, t_b)]
+    } else {
+        vec![]
+    }
+}
+
 impl BBGraph {
+    /// Finds where two overlapping edges start and stop overlapping by projecting the endpoints
+    /// of each edge onto the other, once split at these points the overlapping parts share nodes
+    /// and can be merged by [BBGraph::dedupe_edges].
+    ///
+    /// Returns a list of `(t_a, t_b)` along each edge.
     ///
+    /// * `tolerance`: How close an endpoint needs to be to the other edge to end the overlap
+    fn overlap_ends(
+        &self,
+        idx_a: BBEdgeIndex,
+        idx_b: BBEdgeIndex,
+        tolerance: f32,
+    ) -> BBResult<Vec<(f32, f32)>> {
+        let edge_a = self.edge(idx_a)?;
+        let edge_b = self.edge(idx_b)?;
+
+        let mut result = vec![];
+        for t_b in [0., 1.] {
+            let (t_a, _, distance) = edge_a.closest_point(self, edge_b.t_point(self, t_b));
+            if distance < tolerance {
+                result.push((t_a, t_b));
+            }
+        }
+        for t_a in [0., 1.] {
+            let (t_b, _, distance) = edge_b.closest_point(self, edge_a.t_point(self, t_a));
+            if distance < tolerance {
+                result.push((t_a, t_b));
+            }
+        }
+
+        if result.is_empty() {
+            return Err(BBError::UnresolvedOverlap(idx_a, idx_b));
+        }
+        Ok(result)
+    }
+
+    /// Finds all of the points where edges intersect one another and splits the edges at these
+    /// points, so that the graph is planar.  Edges that cross share a newly created node and an
+    /// edge that touches the middle of another edge will split the other edge at the touching
+    /// node.
     pub fn expand_intersections(&mut self) -> BBResult<()> {
-        let beziers: BBResult<Vec<(BBEdgeIndex, Curve<Coord2>, Bounds<Coord2>)>> = self
+        let beziers: BBResult<HashMap<BBEdgeIndex, EdgeCurve>> = self
             .edges
             .iter()
             .map(|(idx, edge)| {
                 let bezier = edge.as_curve(self)?;
                 let bounds = bezier.bounding_box();
-                Ok((*idx, bezier, bounds))
+                Ok((*idx, (bezier, bounds)))
             })
             .collect();
         let beziers = beziers?;
+        let tolerance = self.scaled_tolerance();
+        let clip_accuracy = (tolerance * CLIP_ACCURACY).min(MAX_CLIP_ACCURACY) as f64;
 
-        // Collect all edge -> edge intersections
-        let mut intersections: HashMap<BBEdgeIndex, Vec<Intersection>> = HashMap::new();
-        for i in 0..beziers.len() {
-            for j in 0..beziers.len() {
-                if i == j {
-                    continue;
-                }
-                let (idx_a, bez_a, rect_a) = &beziers[i];
-                let (idx_b, bez_b, rect_b) = &beziers[j];
-                if !rect_a.overlaps(rect_b) {
-                    continue;
-                }
+        // Collect all edge -> edge intersections, the pairs are sorted so that the output is
+        // deterministic.
+        let mut split_points: HashMap<BBEdgeIndex, Vec<SplitPoint>> = HashMap::new();
+        for (idx_a, idx_b) in self.broad_phase_edge_pairs() {
+            let (bez_a, rect_a) = &beziers[&idx_a];
+            let (bez_b, rect_b) = &beziers[&idx_b];
+            if !rect_a.overlaps(rect_b) {
+                continue;
+            }
+            let edge_a = *self.edge(idx_a)?;
+            let edge_b = *self.edge(idx_b)?;
 
-                let ints: Vec<_> = curve_intersects_curve_clip(bez_a, bez_b, 0.01)
-                    .iter()
-                    .filter(|(ta, tb)| {
-                        let ta_is_at_end = ta.distance_to(&1.) < MIN_DISTANCE_TO_F64
-                            || ta.distance_to(&0.) < MIN_DISTANCE_TO_F64;
-                        let tb_is_at_end = tb.distance_to(&1.) < MIN_DISTANCE_TO_F64
-                            || tb.distance_to(&0.) < MIN_DISTANCE_TO_F64;
-                        !(ta_is_at_end || tb_is_at_end)
-                    })
-                    .map(|(ta, tb)| {
-                        let p = bez_a.point_at_pos(*ta);
-                        Intersection {
-                            other_idx: *idx_b,
-                            self_t: *ta as f32,
-                            other_t: *tb as f32,
-                            pos: c2_to_v2(p),
-                        }
-                    }).collect();
-                if ints.is_empty() {
-                    continue;
+            let ints: Vec<(f32, f32)> = match (edge_a, edge_b) {
+                (BBEdge::Line { .. }, BBEdge::Line { .. }) => line_intersects_line(
+                    edge_a.start_pos(self),
+                    edge_a.end_pos(self),
+                    edge_b.start_pos(self),
+                    edge_b.end_pos(self),
+                ),
+                _ => {
+                    let ints = curve_intersects_curve_clip(bez_a, bez_b, clip_accuracy);
+                    if ints.len() > MAX_CURVE_INTERSECTIONS {
+                        self.overlap_ends(idx_a, idx_b, tolerance)?
+                    } else {
+                        ints.iter()
+                            .filter(|(ta, tb)| {
+                                let ta_is_at_end = ta.distance_to(&1.) < MIN_DISTANCE_TO_F64
+                                    || ta.distance_to(&0.) < MIN_DISTANCE_TO_F64;
+                                let tb_is_at_end = tb.distance_to(&1.) < MIN_DISTANCE_TO_F64
+                                    || tb.distance_to(&0.) < MIN_DISTANCE_TO_F64;
+                                !(ta_is_at_end && tb_is_at_end)
+                            })
+                            .map(|(ta, tb)| (*ta as f32, *tb as f32))
+                            .collect()
+                    }
                 }
+            };
 
-                let stored_ints = intersections.entry(*idx_a).or_insert(vec![]);
-                stored_ints.extend(ints)
-            }
-        }
+            for (ta, tb) in ints {
+                let (a_at_end, b_at_end) = (t_is_at_end(ta), t_is_at_end(tb));
+                // Work out which node this intersection will be joined at, re-using the
+                // existing endpoint if one of the edges is only being touched.
+                let node_idx = match (a_at_end, b_at_end) {
+                    (true, true) => continue,
+                    (true, false) => match ta < 0.5 {
+                        true => edge_a.start_idx(),
+                        false => edge_a.end_idx(),
+                    },
+                    (false, true) => match tb < 0.5 {
+                        true => edge_b.start_idx(),
+                        false => edge_b.end_idx(),
+                    },
+                    (false, false) => {
+                        let pos = edge_a.t_point(self, ta);
+                        self.add_node(pos)
+                    }
+                };
 
-        for (_, values) in intersections.iter_mut() {
-            values.sort_by(|a, b| a.self_t.partial_cmp(&b.self_t).unwrap());
+                if !a_at_end {
+                    split_points
+                        .entry(idx_a)
+                        .or_default()
+                        .push(SplitPoint { t: ta, node_idx });
+                }
+                if !b_at_end {
+                    split_points
+                        .entry(idx_b)
+                        .or_default()
+                        .push(SplitPoint { t: tb, node_idx });
+                }
+            }
         }
 
         // Apply them to self
-        let mut endpoint_lookup: HashMap<BBEdgeIndex, Vec<(BBNodeIndex, Vec2)>> = HashMap::new();
-        for (idx_a, ints) in &intersections {
-            let edge = *self.edge(*idx_a).unwrap();
-            // 
+        let mut split_points: Vec<_> = split_points.into_iter().collect();
+        split_points.sort_by_key(|(idx, _)| *idx);
+        for (edge_idx, mut splits) in split_points {
+            splits.sort_by(|a, b| a.t.total_cmp(&b.t));
+            splits.dedup_by_key(|split| split.node_idx);
+
+            let edge = *self.edge(edge_idx)?;
             let mut prev_node = edge.start_idx();
             let mut remaining_curve = edge.as_curve(self)?;
             let mut t_offset = 0.0;
 
-            for int in ints {
-                let intersect_pos = remaining_curve.point_at_pos(int.self_t as f64);
-                let intersect_pos = c2_to_v2(intersect_pos);
-                let existing_end_node = endpoint_lookup
-                    .get(&int.other_idx)
-                    .and_then(|nodes| {
-                        nodes
-                            .iter()
-                            .find(|(idx, pos)| pos.distance(intersect_pos) < MIN_DISTANCE_TO)
-                    })
-                    .map(|(idx, _)| idx)
-                    .copied();
-
-                let t_mapped = map_linear(int.self_t, t_offset, 1., 0., 1.);
-                let (to_add, remaining) = remaining_curve.subdivide::<Curve<Coord2>>(t_mapped as f64);
-
-                let (_, added_edge) = to_add.add_to_graph_with_nodes(self, Some(prev_node), existing_end_node);
-                prev_node = added_edge.end_idx();
-
-                t_offset += int.self_t;
-                remaining_curve = remaining;
-
-                // Push added node to the lookup so it's re-used for future iters.
-                let lookup_entry = endpoint_lookup.entry(*idx_a);
-                let edge_nodes = lookup_entry.or_insert(vec![]);
-                edge_nodes.push((prev_node, int.pos));
+            for split in splits {
+                if split.node_idx == prev_node || split.node_idx == edge.end_idx() {
+                    continue;
+                }
+                match edge {
+                    BBEdge::Line { .. } => {
+                        self.line_from_to(prev_node, split.node_idx);
+                    }
+                    _ => {
+                        let t_mapped = map_linear(split.t, t_offset, 1., 0., 1.);
+                        let (to_add, remaining) =
+                            remaining_curve.subdivide::<Curve<Coord2>>(t_mapped as f64);
+                        to_add.add_to_graph_with_nodes(self, Some(prev_node), Some(split.node_idx));
+                        remaining_curve = remaining;
+                    }
+                }
+                prev_node = split.node_idx;
+                t_offset = split.t;
             }
 
-            remaining_curve.add_to_graph_with_nodes(self, Some(prev_node), Some(edge.end_idx()));
+            if prev_node == edge.start_idx() {
+                continue;
+            }
+            match edge {
+                BBEdge::Line { .. } => {
+                    self.line_from_to(prev_node, edge.end_idx());
+                }
+                _ => {
+                    remaining_curve.add_to_graph_with_nodes(self, Some(prev_node), Some(edge.end_idx()));
+                }
+            }
+            self.delete_edge(edge_idx)?;
         }
 
-        for (idx, _) in endpoint_lookup {
-            let edge = self.delete_edge(idx);
+        // Remove any nodes that were created for intersections that didn't end up being used.
+        let orphans: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| node.adjacents().is_empty())
+            .map(|(idx, _)| *idx)
+            .collect();
+        for idx in orphans {
+            self.take_node(idx);
         }
 
+        self.debug_validate(false);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use glam::vec2;
+
+    use super::line_intersects_line;
+
+    #[test]
+    fn it_intersects_crossing_lines() {
+        let result = line_intersects_line(vec2(0., 0.), vec2(2., 2.), vec2(0., 2.), vec2(2., 0.));
+        assert_eq!(result, vec![(0.5, 0.5)]);
+    }
+
+    #[test]
+    fn it_returns_overlap_of_collinear_lines() {
+        let result = line_intersects_line(vec2(0., 0.), vec2(4., 0.), vec2(2., 0.), vec2(6., 0.));
+        assert_eq!(result, vec![(0.5, 0.), (1., 0.5)]);
+    }
+}
@@ -0,0 +1,184 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use glam::{vec2, Vec2};
+
+use crate::prelude::*;
+
+/// Corners whose edges are closer than this angle to being straight can't be filleted.
+const STRAIGHT_ANGLE_TOLERANCE: f32 = 0.0001;
+
+/// Approximates a circular arc with cubic beziers, using one cubic per quarter turn.  Returns
+/// the `(start, ctrl1, ctrl2, end)` points of each cubic.
+///
+/// * `center`: Center of the circle
+/// * `radius`: Radius of the circle
+/// * `start_angle`: Angle of the start of the arc, in radians
+/// * `sweep`: Angle to sweep through, positive is counterclockwise
+pub(crate) fn arc_cubics(
+    center: Vec2,
+    radius: f32,
+    start_angle: f32,
+    sweep: f32,
+) -> Vec<[Vec2; 4]> {
+    let segments = (sweep.abs() / FRAC_PI_2).ceil().max(1.) as usize;
+    let step = sweep / segments as f32;
+    let handle = 4. / 3. * (step / 4.).tan() * radius;
+    let point_at = |angle: f32| center + radius * Vec2::from_angle(angle);
+    let tangent_at = |angle: f32| vec2(-angle.sin(), angle.cos());
+
+    (0..segments)
+        .map(|i| {
+            let a0 = start_angle + step * i as f32;
+            let a1 = a0 + step;
+            [
+                point_at(a0),
+                point_at(a0) + tangent_at(a0) * handle,
+                point_at(a1) - tangent_at(a1) * handle,
+                point_at(a1),
+            ]
+        })
+        .collect()
+}
+
+impl BBGraph {
+    /// Returns the two edges that meet at a corner node, directed away from the node.
+    ///
+    /// * `node_idx`: The corner node
+    fn corner_edges(&self, node_idx: BBNodeIndex) -> BBResult<[(BBEdgeIndex, BBEdge); 2]> {
+        let adjacents = self.node(node_idx)?.adjacents();
+        match adjacents {
+            [a, b] if a != b => Ok([
+                (*a, self.edge(*a)?.directed_from(node_idx)),
+                (*b, self.edge(*b)?.directed_from(node_idx)),
+            ]),
+            _ => Err(BBError::NotACorner(node_idx, adjacents.len())),
+        }
+    }
+
+    /// Splits both edges of a corner `distance` along their length from the corner node, then
+    /// removes the corner node along with the pieces between it and the splits.  Returns the new
+    /// end nodes of both edges.
+    ///
+    /// * `node_idx`: The corner node
+    /// * `distance`: Arc length from the corner to split each edge at
+    fn trim_corner(
+        &mut self,
+        node_idx: BBNodeIndex,
+        distance: f32,
+    ) -> BBResult<(BBNodeIndex, BBNodeIndex)> {
+        let corner_edges = self.corner_edges(node_idx)?;
+
+        let mut splits = vec![];
+        for (edge_idx, directed) in corner_edges {
+            let length = directed.length(self);
+            if !(distance > 0. && distance < length) {
+                return Err(BBError::CornerDoesNotFit(node_idx, distance));
+            }
+            // Split the stored edge rather than the directed one so the edge direction is kept.
+            let edge = *self.edge(edge_idx)?;
+            let t = directed.t_at_length(self, distance);
+            let t = if edge.start_idx() == node_idx {
+                t
+            } else {
+                1. - t
+            };
+            splits.push((edge, t));
+        }
+
+        for (edge, t) in splits {
+            edge.subdivide_without_delete(self, t)?;
+        }
+
+        // The pieces touching the corner node were added after its original two edges.
+        let pieces = self.node(node_idx)?.adjacents()[2..].to_vec();
+        let mut trimmed_ends = vec![];
+        for piece_idx in pieces {
+            trimmed_ends.push(self.edge(piece_idx)?.other_node_idx(node_idx));
+        }
+        self.delete_node(node_idx)?;
+
+        Ok((trimmed_ends[0], trimmed_ends[1]))
+    }
+
+    /// Rounds the corner at a node with a circular arc, made from cubic curves, that is tangent
+    /// to both edges.  The edges are trimmed to where the arc meets them.  The arc is exact for
+    /// straight edges and approximate for curved edges.  Regions aren't updated, call
+    /// [BBGraph::update_regions] afterwards.  Returns the edges of the arc.
+    ///
+    /// * `node_idx`: The corner node, must have exactly two adjacent edges
+    /// * `radius`: Radius of the arc
+    pub fn fillet_node(
+        &mut self,
+        node_idx: BBNodeIndex,
+        radius: f32,
+    ) -> BBResult<Vec<BBEdgeIndex>> {
+        let [(_, a), (_, b)] = self.corner_edges(node_idx)?;
+        let dir_a = a.calc_start_tangent(self)?.normalize_or_zero();
+        let dir_b = b.calc_start_tangent(self)?.normalize_or_zero();
+        let angle = dir_a.angle_between(dir_b).abs();
+        // A straight node has no corner to round.
+        if !(radius > 0. && angle > 0. && angle < PI - STRAIGHT_ANGLE_TOLERANCE) {
+            return Err(BBError::CornerDoesNotFit(node_idx, radius));
+        }
+
+        let (start_idx, end_idx) = self.trim_corner(node_idx, radius / (angle / 2.).tan())?;
+        let start = self.node(start_idx)?.position();
+        let end = self.node(end_idx)?.position();
+
+        // Direction of travel at the start of the arc, towards where the corner was.
+        let start_edge = self.edge(self.node(start_idx)?.adjacents()[0])?;
+        let tangent = -start_edge
+            .directed_from(start_idx)
+            .calc_start_tangent(self)?
+            .normalize_or_zero();
+
+        // The circle tangent to the start edge at `start` that passes through `end`.
+        let chord = end - start;
+        let counter_clockwise = tangent.perp_dot(chord) > 0.;
+        let normal = if counter_clockwise {
+            tangent.perp()
+        } else {
+            -tangent.perp()
+        };
+        let arc_radius = chord.length_squared() / (2. * chord.dot(normal));
+        let center = start + normal * arc_radius;
+
+        let angle_of = |p: Vec2| (p.y - center.y).atan2(p.x - center.x);
+        let start_angle = angle_of(start);
+        let mut sweep = angle_of(end) - start_angle;
+        if counter_clockwise {
+            sweep = sweep.rem_euclid(TAU);
+        } else {
+            sweep = -(-sweep).rem_euclid(TAU);
+        }
+
+        let cubics = arc_cubics(center, arc_radius, start_angle, sweep);
+        let mut arc = vec![];
+        let mut prev_idx = start_idx;
+        for (i, [_, ctrl1, ctrl2, end]) in cubics.iter().enumerate() {
+            let (edge_idx, edge) = if i + 1 == cubics.len() {
+                self.cubic_from_to(prev_idx, *ctrl1, *ctrl2, end_idx)
+            } else {
+                self.cubic_from(prev_idx, *ctrl1, *ctrl2, *end)
+            };
+            prev_idx = edge.end_idx();
+            arc.push(edge_idx);
+        }
+
+        self.debug_validate(false);
+        Ok(arc)
+    }
+
+    /// Bevels the corner at a node with a straight line.  The edges are trimmed `distance` along
+    /// their length from the node.  Regions aren't updated, call [BBGraph::update_regions]
+    /// afterwards.  Returns the new line edge.
+    ///
+    /// * `node_idx`: The corner node, must have exactly two adjacent edges
+    /// * `distance`: Arc length to trim from each edge
+    pub fn chamfer_node(&mut self, node_idx: BBNodeIndex, distance: f32) -> BBResult<BBEdgeIndex> {
+        let (start_idx, end_idx) = self.trim_corner(node_idx, distance)?;
+        let (edge_idx, _) = self.line_from_to(start_idx, end_idx);
+        self.debug_validate(false);
+        Ok(edge_idx)
+    }
+}
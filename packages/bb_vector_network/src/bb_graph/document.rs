@@ -0,0 +1,745 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::prelude::*;
+
+/// The current version of the [BBDocument] format, bump this and add a migration whenever the
+/// schema changes.
+///
+/// * `0`: The legacy format, a serde dump of the internal `BBGraph` maps.
+/// * `1`: Dense node and edge lists referencing each other by position.
+pub const BB_DOCUMENT_VERSION: u32 = 1;
+
+/// Magic bytes at the start of the binary format, see [BBDocument::to_bytes].
+const BINARY_MAGIC: &[u8; 4] = b"BBVN";
+/// The deepest nesting of region cycles that will be read from the binary format.
+const MAX_BINARY_CYCLE_DEPTH: usize = 256;
+
+/// A stable, versioned representation of a [BBGraph] for saving to disk.  Unlike [BBGraph],
+/// nodes, edges and regions are stored in plain lists and reference each other by their position
+/// in those lists, so changes to the in-memory layout or index types don't break saved files.
+///
+/// With the `serde` feature, deserialising a [BBDocument] migrates older versions of the format
+/// to the current one.  [BBGraph] is (de)serialised through this type.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "serde_impl::BBVersionedDocument"))]
+pub struct BBDocument {
+    pub version: u32,
+    /// Positions of the nodes.
+    pub nodes: Vec<[f32; 2]>,
+    pub edges: Vec<BBDocumentEdge>,
+    pub regions: Vec<BBDocumentRegion>,
+}
+
+/// An edge of a [BBDocument], `start` and `end` are positions in [BBDocument::nodes].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum BBDocumentEdge {
+    Line {
+        start: u32,
+        end: u32,
+    },
+    Quadratic {
+        start: u32,
+        ctrl1: [f32; 2],
+        end: u32,
+    },
+    Cubic {
+        start: u32,
+        ctrl1: [f32; 2],
+        ctrl2: [f32; 2],
+        end: u32,
+    },
+}
+
+/// A region of a [BBDocument].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BBDocumentRegion {
+    pub winding_rule: BBDocumentWindingRule,
+    pub cycle: BBDocumentCycle,
+}
+
+/// The winding rule of a [BBDocumentRegion], see [BBWindingRule].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BBDocumentWindingRule {
+    EvenOdd,
+    NonZero,
+}
+
+/// A cycle of a [BBDocumentRegion], `edges` are positions in [BBDocument::edges].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BBDocumentCycle {
+    pub filled: bool,
+    pub edges: Vec<u32>,
+    pub children: Vec<BBDocumentCycle>,
+}
+
+/// The indices given to the nodes and edges of a [BBDocument] when it's loaded by
+/// [BBGraph::from_document_with_indices], in the same order as [BBDocument::nodes] and
+/// [BBDocument::edges].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BBDocumentIndices {
+    pub nodes: Vec<BBNodeIndex>,
+    pub edges: Vec<BBEdgeIndex>,
+}
+
+impl From<BBWindingRule> for BBDocumentWindingRule {
+    fn from(value: BBWindingRule) -> Self {
+        match value {
+            BBWindingRule::Default => BBDocumentWindingRule::EvenOdd,
+            BBWindingRule::NonZero => BBDocumentWindingRule::NonZero,
+        }
+    }
+}
+
+impl From<BBDocumentWindingRule> for BBWindingRule {
+    fn from(value: BBDocumentWindingRule) -> Self {
+        match value {
+            BBDocumentWindingRule::EvenOdd => BBWindingRule::Default,
+            BBDocumentWindingRule::NonZero => BBWindingRule::NonZero,
+        }
+    }
+}
+
+impl BBGraph {
+    /// Converts the graph to a [BBDocument] for saving.  Nodes, edges and regions are written in
+    /// index order so the same graph always produces the same document.
+    ///
+    /// Fails if an edge or region references a node or edge that doesn't exist, regions aren't
+    /// updated automatically so call [BBGraph::update_regions] after changing the topology.
+    pub fn to_document(&self) -> BBResult<BBDocument> {
+        let mut node_indices: Vec<_> = self.nodes.keys().copied().collect();
+        node_indices.sort();
+        let node_ids: HashMap<_, _> = node_indices
+            .iter()
+            .enumerate()
+            .map(|(id, node_idx)| (*node_idx, id as u32))
+            .collect();
+        let node_id = |node_idx: BBNodeIndex| {
+            node_ids
+                .get(&node_idx)
+                .copied()
+                .ok_or(BBError::MissingNode(node_idx))
+        };
+
+        let mut edge_indices: Vec<_> = self.edges.keys().copied().collect();
+        edge_indices.sort();
+        let edge_ids: HashMap<_, _> = edge_indices
+            .iter()
+            .enumerate()
+            .map(|(id, edge_idx)| (*edge_idx, id as u32))
+            .collect();
+
+        let nodes = node_indices
+            .iter()
+            .map(|node_idx| self.nodes[node_idx].position().to_array())
+            .collect();
+
+        let mut edges = Vec::with_capacity(edge_indices.len());
+        for edge_idx in &edge_indices {
+            edges.push(match self.edges[edge_idx] {
+                BBEdge::Line { start, end } => BBDocumentEdge::Line {
+                    start: node_id(start)?,
+                    end: node_id(end)?,
+                },
+                BBEdge::Quadratic { start, ctrl1, end } => BBDocumentEdge::Quadratic {
+                    start: node_id(start)?,
+                    ctrl1: ctrl1.to_array(),
+                    end: node_id(end)?,
+                },
+                BBEdge::Cubic {
+                    start,
+                    ctrl1,
+                    ctrl2,
+                    end,
+                } => BBDocumentEdge::Cubic {
+                    start: node_id(start)?,
+                    ctrl1: ctrl1.to_array(),
+                    ctrl2: ctrl2.to_array(),
+                    end: node_id(end)?,
+                },
+            });
+        }
+
+        let mut region_indices: Vec<_> = self.regions.keys().copied().collect();
+        region_indices.sort();
+        let mut regions = Vec::with_capacity(region_indices.len());
+        for region_idx in region_indices {
+            let region = &self.regions[&region_idx];
+            regions.push(BBDocumentRegion {
+                winding_rule: region.winding_rule().into(),
+                cycle: document_cycle(&region.root_cycle, &edge_ids)?,
+            });
+        }
+
+        Ok(BBDocument {
+            version: BB_DOCUMENT_VERSION,
+            nodes,
+            edges,
+            regions,
+        })
+    }
+
+    /// Builds a graph from a [BBDocument].  The graph gets fresh indices, regions are restored as
+    /// saved rather than recalculated.  Use [BBGraph::from_document_with_indices] to find the
+    /// index of each document node and edge.
+    ///
+    /// * `document`: The document to load, must be of the current [BB_DOCUMENT_VERSION]
+    pub fn from_document(document: &BBDocument) -> BBResult<BBGraph> {
+        Self::from_document_with_indices(document).map(|(graph, _)| graph)
+    }
+
+    /// Builds a graph from a [BBDocument], see [BBGraph::from_document].  Also returns the index
+    /// given to each of the document's nodes and edges.
+    ///
+    /// * `document`: The document to load, must be of the current [BB_DOCUMENT_VERSION]
+    pub fn from_document_with_indices(
+        document: &BBDocument,
+    ) -> BBResult<(BBGraph, BBDocumentIndices)> {
+        if document.version != BB_DOCUMENT_VERSION {
+            return Err(BBError::UnsupportedDocumentVersion(document.version));
+        }
+
+        let mut graph = BBGraph::new();
+        let node_indices: Vec<_> = document
+            .nodes
+            .iter()
+            .map(|position| graph.add_node(Vec2::from_array(*position)))
+            .collect();
+
+        let mut edge_indices = Vec::with_capacity(document.edges.len());
+        for (id, edge) in document.edges.iter().enumerate() {
+            let node_idx = |node_id: u32| {
+                node_indices.get(node_id as usize).copied().ok_or_else(|| {
+                    BBError::InvalidDocument(format!("edge {id} references missing node {node_id}"))
+                })
+            };
+            let edge = match *edge {
+                BBDocumentEdge::Line { start, end } => BBEdge::Line {
+                    start: node_idx(start)?,
+                    end: node_idx(end)?,
+                },
+                BBDocumentEdge::Quadratic { start, ctrl1, end } => BBEdge::Quadratic {
+                    start: node_idx(start)?,
+                    ctrl1: Vec2::from_array(ctrl1),
+                    end: node_idx(end)?,
+                },
+                BBDocumentEdge::Cubic {
+                    start,
+                    ctrl1,
+                    ctrl2,
+                    end,
+                } => BBEdge::Cubic {
+                    start: node_idx(start)?,
+                    ctrl1: Vec2::from_array(ctrl1),
+                    ctrl2: Vec2::from_array(ctrl2),
+                    end: node_idx(end)?,
+                },
+            };
+            edge_indices.push(graph.add_edge(edge).0);
+        }
+
+        for document_region in &document.regions {
+            let mut region = BBRegion::new(graph_cycle(&document_region.cycle, &edge_indices)?);
+            region.set_winding_rule(document_region.winding_rule.into());
+            graph.add_region(region);
+        }
+
+        // The saved regions are up to date.
+        graph.dirty_nodes.clear();
+        let indices = BBDocumentIndices {
+            nodes: node_indices,
+            edges: edge_indices,
+        };
+        Ok((graph, indices))
+    }
+}
+
+fn document_cycle(
+    cycle: &BBCycle,
+    edge_ids: &HashMap<BBEdgeIndex, u32>,
+) -> BBResult<BBDocumentCycle> {
+    Ok(BBDocumentCycle {
+        filled: cycle.filled,
+        edges: cycle
+            .edges
+            .iter()
+            .map(|edge_idx| {
+                edge_ids
+                    .get(edge_idx)
+                    .copied()
+                    .ok_or(BBError::MissingEdge(*edge_idx))
+            })
+            .collect::<BBResult<_>>()?,
+        children: cycle
+            .children
+            .iter()
+            .map(|child| document_cycle(child, edge_ids))
+            .collect::<BBResult<_>>()?,
+    })
+}
+
+fn graph_cycle(cycle: &BBDocumentCycle, edge_indices: &[BBEdgeIndex]) -> BBResult<BBCycle> {
+    Ok(BBCycle {
+        filled: cycle.filled,
+        edges: cycle
+            .edges
+            .iter()
+            .map(|edge_id| {
+                edge_indices.get(*edge_id as usize).copied().ok_or_else(|| {
+                    BBError::InvalidDocument(format!("region references missing edge {edge_id}"))
+                })
+            })
+            .collect::<BBResult<_>>()?,
+        children: cycle
+            .children
+            .iter()
+            .map(|child| graph_cycle(child, edge_indices))
+            .collect::<BBResult<_>>()?,
+    })
+}
+
+/**
+ * Binary format
+ */
+impl BBDocument {
+    /// Encodes the document in a compact binary format, this doesn't need the `serde` feature.
+    /// The format is the magic bytes `BBVN` and the version followed by the node, edge and region
+    /// lists, all numbers are little endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = BinaryWriter::default();
+        writer.bytes.extend_from_slice(BINARY_MAGIC);
+        writer.u32(self.version);
+
+        writer.len(self.nodes.len());
+        for position in &self.nodes {
+            writer.point(*position);
+        }
+
+        writer.len(self.edges.len());
+        for edge in &self.edges {
+            match *edge {
+                BBDocumentEdge::Line { start, end } => {
+                    writer.u8(0);
+                    writer.u32(start);
+                    writer.u32(end);
+                }
+                BBDocumentEdge::Quadratic { start, ctrl1, end } => {
+                    writer.u8(1);
+                    writer.u32(start);
+                    writer.point(ctrl1);
+                    writer.u32(end);
+                }
+                BBDocumentEdge::Cubic {
+                    start,
+                    ctrl1,
+                    ctrl2,
+                    end,
+                } => {
+                    writer.u8(2);
+                    writer.u32(start);
+                    writer.point(ctrl1);
+                    writer.point(ctrl2);
+                    writer.u32(end);
+                }
+            }
+        }
+
+        writer.len(self.regions.len());
+        for region in &self.regions {
+            writer.u8(match region.winding_rule {
+                BBDocumentWindingRule::EvenOdd => 0,
+                BBDocumentWindingRule::NonZero => 1,
+            });
+            writer.cycle(&region.cycle);
+        }
+
+        writer.bytes
+    }
+
+    /// Decodes a document written by [BBDocument::to_bytes], migrating it to the current
+    /// version.
+    ///
+    /// * `bytes`: The encoded document
+    pub fn from_bytes(bytes: &[u8]) -> BBResult<BBDocument> {
+        let mut reader = BinaryReader { bytes, offset: 0 };
+        if reader.take(BINARY_MAGIC.len())? != BINARY_MAGIC {
+            return Err(BBError::InvalidDocument("missing BBVN header".to_string()));
+        }
+
+        let version = reader.u32()?;
+        let document = match version {
+            1 => reader.document_v1()?,
+            _ => return Err(BBError::UnsupportedDocumentVersion(version)),
+        };
+
+        if reader.offset != bytes.len() {
+            return Err(BBError::InvalidDocument(format!(
+                "unexpected data at byte {}",
+                reader.offset
+            )));
+        }
+        Ok(document)
+    }
+}
+
+#[derive(Default)]
+struct BinaryWriter {
+    bytes: Vec<u8>,
+}
+
+impl BinaryWriter {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    fn len(&mut self, value: usize) {
+        self.u32(value as u32);
+    }
+    fn point(&mut self, value: [f32; 2]) {
+        self.bytes.extend_from_slice(&value[0].to_le_bytes());
+        self.bytes.extend_from_slice(&value[1].to_le_bytes());
+    }
+    fn cycle(&mut self, cycle: &BBDocumentCycle) {
+        self.u8(cycle.filled as u8);
+        self.len(cycle.edges.len());
+        for edge_id in &cycle.edges {
+            self.u32(*edge_id);
+        }
+        self.len(cycle.children.len());
+        for child in &cycle.children {
+            self.cycle(child);
+        }
+    }
+}
+
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn take(&mut self, count: usize) -> BBResult<&'a [u8]> {
+        let end = self.offset + count;
+        let Some(bytes) = self.bytes.get(self.offset..end) else {
+            return Err(BBError::InvalidDocument(format!(
+                "unexpected end of data at byte {}",
+                self.offset
+            )));
+        };
+        self.offset = end;
+        Ok(bytes)
+    }
+    fn u8(&mut self) -> BBResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+    fn u32(&mut self) -> BBResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+    fn f32(&mut self) -> BBResult<f32> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+    fn point(&mut self) -> BBResult<[f32; 2]> {
+        Ok([self.f32()?, self.f32()?])
+    }
+    fn invalid<T>(&self, what: &str, value: u8) -> BBResult<T> {
+        Err(BBError::InvalidDocument(format!(
+            "invalid {what} {value} at byte {}",
+            self.offset - 1
+        )))
+    }
+    /// Reads a cycle and its children.
+    ///
+    /// * `depth`: How many cycles this cycle is nested in
+    fn cycle(&mut self, depth: usize) -> BBResult<BBDocumentCycle> {
+        if depth > MAX_BINARY_CYCLE_DEPTH {
+            return Err(BBError::InvalidDocument(format!(
+                "cycles nested more than {MAX_BINARY_CYCLE_DEPTH} deep at byte {}",
+                self.offset
+            )));
+        }
+        let filled = match self.u8()? {
+            0 => false,
+            1 => true,
+            value => return self.invalid("fill flag", value),
+        };
+        let edges = (0..self.u32()?)
+            .map(|_| self.u32())
+            .collect::<BBResult<_>>()?;
+        let children = (0..self.u32()?)
+            .map(|_| self.cycle(depth + 1))
+            .collect::<BBResult<_>>()?;
+        Ok(BBDocumentCycle {
+            filled,
+            edges,
+            children,
+        })
+    }
+
+    fn document_v1(&mut self) -> BBResult<BBDocument> {
+        let nodes = (0..self.u32()?)
+            .map(|_| self.point())
+            .collect::<BBResult<_>>()?;
+
+        let mut edges = vec![];
+        for _ in 0..self.u32()? {
+            edges.push(match self.u8()? {
+                0 => BBDocumentEdge::Line {
+                    start: self.u32()?,
+                    end: self.u32()?,
+                },
+                1 => BBDocumentEdge::Quadratic {
+                    start: self.u32()?,
+                    ctrl1: self.point()?,
+                    end: self.u32()?,
+                },
+                2 => BBDocumentEdge::Cubic {
+                    start: self.u32()?,
+                    ctrl1: self.point()?,
+                    ctrl2: self.point()?,
+                    end: self.u32()?,
+                },
+                value => return self.invalid("edge kind", value),
+            });
+        }
+
+        let mut regions = vec![];
+        for _ in 0..self.u32()? {
+            let winding_rule = match self.u8()? {
+                0 => BBDocumentWindingRule::EvenOdd,
+                1 => BBDocumentWindingRule::NonZero,
+                value => return self.invalid("winding rule", value),
+            };
+            regions.push(BBDocumentRegion {
+                winding_rule,
+                cycle: self.cycle(0)?,
+            });
+        }
+
+        Ok(BBDocument {
+            version: 1,
+            nodes,
+            edges,
+            regions,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    //! Deserialisation of every version of the format, and (de)serialisation of [BBGraph] through
+    //! [BBDocument].
+
+    use std::collections::HashMap;
+
+    use glam::Vec2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    /// Any version of the document format, the legacy format has no version field.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum BBVersionedDocument {
+        Versioned(VersionedFields),
+        Legacy(LegacyGraph),
+    }
+
+    #[derive(Deserialize)]
+    pub(super) struct VersionedFields {
+        version: u32,
+        nodes: Vec<[f32; 2]>,
+        edges: Vec<BBDocumentEdge>,
+        regions: Vec<BBDocumentRegion>,
+    }
+
+    impl TryFrom<BBVersionedDocument> for BBDocument {
+        type Error = BBError;
+
+        fn try_from(value: BBVersionedDocument) -> Result<Self, Self::Error> {
+            match value {
+                BBVersionedDocument::Versioned(fields) => match fields.version {
+                    1 => Ok(BBDocument {
+                        version: 1,
+                        nodes: fields.nodes,
+                        edges: fields.edges,
+                        regions: fields.regions,
+                    }),
+                    version => Err(BBError::UnsupportedDocumentVersion(version)),
+                },
+                BBVersionedDocument::Legacy(legacy) => legacy.migrate(),
+            }
+        }
+    }
+
+    /// Version 0, the serde derive of `BBGraph`.  Indices were a plain `usize` before they became
+    /// generational, both are accepted.
+    #[derive(Deserialize)]
+    pub(super) struct LegacyGraph {
+        nodes: Vec<(LegacyIndex, LegacyNode)>,
+        edges: Vec<(LegacyIndex, LegacyEdge)>,
+        regions: Vec<(LegacyIndex, LegacyRegion)>,
+    }
+
+    #[derive(Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[serde(untagged)]
+    enum LegacyIndex {
+        Plain(usize),
+        Generational { slot: usize, generation: u32 },
+    }
+
+    #[derive(Deserialize)]
+    struct LegacyNode {
+        position: Vec2,
+    }
+
+    #[derive(Deserialize)]
+    enum LegacyEdge {
+        Line {
+            start: LegacyIndex,
+            end: LegacyIndex,
+        },
+        Quadratic {
+            start: LegacyIndex,
+            ctrl1: Vec2,
+            end: LegacyIndex,
+        },
+        Cubic {
+            start: LegacyIndex,
+            ctrl1: Vec2,
+            ctrl2: Vec2,
+            end: LegacyIndex,
+        },
+    }
+
+    #[derive(Deserialize)]
+    struct LegacyRegion {
+        winding_rule: BBWindingRule,
+        root_cycle: LegacyCycle,
+    }
+
+    #[derive(Deserialize)]
+    struct LegacyCycle {
+        filled: bool,
+        edges: Vec<LegacyIndex>,
+        children: Vec<LegacyCycle>,
+    }
+
+    /// Maps the sparse legacy indices to positions in the sorted list of items.
+    fn legacy_ids<T>(items: &mut [(LegacyIndex, T)]) -> HashMap<LegacyIndex, u32> {
+        items.sort_by_key(|(index, _)| *index);
+        items
+            .iter()
+            .enumerate()
+            .map(|(id, (index, _))| (*index, id as u32))
+            .collect()
+    }
+
+    impl LegacyCycle {
+        fn migrate(&self, edge_ids: &HashMap<LegacyIndex, u32>) -> BBResult<BBDocumentCycle> {
+            Ok(BBDocumentCycle {
+                filled: self.filled,
+                edges: self
+                    .edges
+                    .iter()
+                    .map(|index| {
+                        edge_ids.get(index).copied().ok_or_else(|| {
+                            BBError::InvalidDocument("region references missing edge".to_string())
+                        })
+                    })
+                    .collect::<BBResult<_>>()?,
+                children: self
+                    .children
+                    .iter()
+                    .map(|child| child.migrate(edge_ids))
+                    .collect::<BBResult<_>>()?,
+            })
+        }
+    }
+
+    impl LegacyGraph {
+        fn migrate(mut self) -> BBResult<BBDocument> {
+            let node_ids = legacy_ids(&mut self.nodes);
+            let edge_ids = legacy_ids(&mut self.edges);
+            self.regions.sort_by_key(|(index, _)| *index);
+
+            let node_id = |index: LegacyIndex| {
+                node_ids.get(&index).copied().ok_or_else(|| {
+                    BBError::InvalidDocument("edge references missing node".to_string())
+                })
+            };
+
+            let mut edges = Vec::with_capacity(self.edges.len());
+            for (_, edge) in &self.edges {
+                edges.push(match *edge {
+                    LegacyEdge::Line { start, end } => BBDocumentEdge::Line {
+                        start: node_id(start)?,
+                        end: node_id(end)?,
+                    },
+                    LegacyEdge::Quadratic { start, ctrl1, end } => BBDocumentEdge::Quadratic {
+                        start: node_id(start)?,
+                        ctrl1: ctrl1.to_array(),
+                        end: node_id(end)?,
+                    },
+                    LegacyEdge::Cubic {
+                        start,
+                        ctrl1,
+                        ctrl2,
+                        end,
+                    } => BBDocumentEdge::Cubic {
+                        start: node_id(start)?,
+                        ctrl1: ctrl1.to_array(),
+                        ctrl2: ctrl2.to_array(),
+                        end: node_id(end)?,
+                    },
+                });
+            }
+
+            let regions = self
+                .regions
+                .iter()
+                .map(|(_, region)| {
+                    Ok(BBDocumentRegion {
+                        winding_rule: region.winding_rule.into(),
+                        cycle: region.root_cycle.migrate(&edge_ids)?,
+                    })
+                })
+                .collect::<BBResult<_>>()?;
+
+            Ok(BBDocument {
+                version: BB_DOCUMENT_VERSION,
+                nodes: self
+                    .nodes
+                    .iter()
+                    .map(|(_, node)| node.position.to_array())
+                    .collect(),
+                edges,
+                regions,
+            })
+        }
+    }
+
+    impl Serialize for BBGraph {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.to_document()
+                .map_err(serde::ser::Error::custom)?
+                .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for BBGraph {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let document = BBDocument::deserialize(deserializer)?;
+            BBGraph::from_document(&document).map_err(serde::de::Error::custom)
+        }
+    }
+}
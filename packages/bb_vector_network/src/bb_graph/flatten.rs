@@ -0,0 +1,107 @@
+use glam::Vec2;
+
+use crate::prelude::*;
+
+/// A vertex of a flattened polyline and where it came from in the graph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BBPolylineVertex {
+    pub position: Vec2,
+    /// The edge this vertex lies on.
+    pub edge_idx: BBEdgeIndex,
+    /// Position along the edge, in the direction the edge is stored in the graph.
+    pub t: f32,
+}
+
+/// A chain of edges flattened to line segments, see [BBGraph::flatten_edges].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BBPolyline {
+    pub vertices: Vec<BBPolylineVertex>,
+    /// If true the last vertex connects back to the first, the first vertex isn't repeated.
+    pub closed: bool,
+}
+
+impl BBPolyline {
+    /// Returns an iterator over the position of each vertex.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.vertices.iter().map(|v| v.position)
+    }
+}
+
+impl BBGraph {
+    /// Flattens a single edge to a polyline, from the start of the edge to the end.
+    ///
+    /// * `edge_idx`: The edge to flatten
+    /// * `tolerance`: Maximum distance between the curve and the polyline
+    pub fn flatten_edge(&self, edge_idx: BBEdgeIndex, tolerance: f32) -> BBResult<BBPolyline> {
+        let vertices = self
+            .edge(edge_idx)?
+            .flatten(self, tolerance)
+            .into_iter()
+            .map(|(t, position)| BBPolylineVertex {
+                position,
+                edge_idx,
+                t,
+            })
+            .collect();
+        Ok(BBPolyline {
+            vertices,
+            closed: false,
+        })
+    }
+
+    /// Flattens a continuous chain of edges to a single polyline.  If the chain ends where it
+    /// starts the polyline is closed.  Vertices where two edges meet are mapped to the later edge.
+    ///
+    /// * `edges`: A continuous chain of edges, such as the edges of a [BBCycle]
+    /// * `tolerance`: Maximum distance between the curves and the polyline
+    pub fn flatten_edges(&self, edges: &[BBEdgeIndex], tolerance: f32) -> BBResult<BBPolyline> {
+        let directed = self.edges_directed(edges)?;
+        let closed = directed.len() > 1
+            && directed.first().unwrap().1.start_idx() == directed.last().unwrap().1.end_idx();
+
+        let mut vertices = vec![];
+        for (i, (edge_idx, edge)) in directed.iter().enumerate() {
+            let reversed = edge.start_idx() != self.edge(*edge_idx)?.start_idx();
+            let mut points = edge.flatten(self, tolerance);
+            // The end point is the start of the next edge.
+            if closed || i + 1 < directed.len() {
+                points.pop();
+            }
+            vertices.extend(points.into_iter().map(|(t, position)| BBPolylineVertex {
+                position,
+                edge_idx: *edge_idx,
+                t: if reversed { 1. - t } else { t },
+            }));
+        }
+
+        Ok(BBPolyline { vertices, closed })
+    }
+
+    /// Flattens each of the cycles of a region, the root cycle first followed by its nested
+    /// cycles.
+    ///
+    /// * `region_idx`: The region to flatten
+    /// * `tolerance`: Maximum distance between the curves and the polylines
+    pub fn flatten_region(
+        &self,
+        region_idx: BBRegionIndex,
+        tolerance: f32,
+    ) -> BBResult<Vec<BBPolyline>> {
+        self.region(region_idx)?
+            .root_cycle
+            .edges_deep()
+            .iter()
+            .map(|edges| self.flatten_edges(edges, tolerance))
+            .collect()
+    }
+}
+
+impl BBCycle {
+    /// Flattens the edges of this cycle to a closed polyline, see [BBGraph::flatten_edges].
+    ///
+    /// * `graph`: The BBGraph the cycle belongs to
+    /// * `tolerance`: Maximum distance between the curves and the polyline
+    pub fn flatten(&self, graph: &BBGraph, tolerance: f32) -> BBResult<BBPolyline> {
+        graph.flatten_edges(&self.edges, tolerance)
+    }
+}
@@ -69,11 +69,13 @@ impl BBGraph {
         Ok(())
     }
 
+    /// Generates a path of all the filled cycles of the graph, see [BBGraph::fill_cycles].  The
+    /// path can be tessellated with either the even-odd or the non-zero fill rule.
     pub fn generate_fill_path(&self) -> BBResult<Path> {
         let mut builder = Path::builder();
 
-        for region in self.regions.values() {
-            self.build_path_for_edge_list(&mut builder, &region.root_cycle.edges)?
+        for edges in self.fill_cycles()? {
+            self.build_path_for_edge_list(&mut builder, &edges)?
         }
 
         Ok(builder.build())
@@ -0,0 +1,245 @@
+use glam::Vec2;
+
+use crate::{bb_edge::DEFAULT_FLATTEN_TOLERANCE, prelude::*};
+
+use super::winding::{polygon_contains, polygon_signed_area};
+
+/// Abscissae and weights of 5 point Gauss-Legendre quadrature over [-1, 1], exact for the
+/// polynomials produced by integrating along cubic beziers.
+const GAUSS_LEGENDRE: [(f32, f32); 5] = [
+    (0., 0.568_888_9),
+    (-0.538_469_3, 0.478_628_67),
+    (0.538_469_3, 0.478_628_67),
+    (-0.906_179_85, 0.236_926_88),
+    (0.906_179_85, 0.236_926_88),
+];
+
+/// Area and first moment of area of a closed chain of edges, counterclockwise chains are
+/// positive.
+#[derive(Debug, Clone, Copy, Default)]
+struct Moments {
+    area: f32,
+    moment: Vec2,
+}
+
+impl Moments {
+    /// Calculates the moments of the closed chain of `edges` using Green's theorem.
+    fn of_edges(graph: &BBGraph, edges: &[BBEdgeIndex]) -> BBResult<Self> {
+        let mut moments = Self::default();
+        for (_, edge) in graph.edges_directed(edges)? {
+            for (x, w) in GAUSS_LEGENDRE {
+                let t = (x + 1.) / 2.;
+                // Halved to map the weights onto [0, 1].
+                let w = w / 2.;
+                let p = edge.t_point(graph, t);
+                let d = edge.t_derivative(graph, t);
+                moments.area += w * (p.x * d.y - p.y * d.x) / 2.;
+                moments.moment.x += w * p.x * p.x * d.y / 2.;
+                moments.moment.y -= w * p.y * p.y * d.x / 2.;
+            }
+        }
+        Ok(moments)
+    }
+
+    /// Returns the moments with a positive area, regardless of the direction of the chain.
+    fn unsigned(self) -> Self {
+        let sign = if self.area < 0. { -1. } else { 1. };
+        Self {
+            area: self.area * sign,
+            moment: self.moment * sign,
+        }
+    }
+}
+
+impl BBCycle {
+    /// Returns the unfilled faces nested inside of this cycle.
+    fn holes(&self) -> impl Iterator<Item = &BBCycle> {
+        self.faces()
+            .into_iter()
+            .filter(move |face| !face.filled && !std::ptr::eq(*face, self))
+    }
+
+    /// Calculates the area enclosed by this cycle minus the area of its holes.  Counterclockwise
+    /// cycles have a positive area.
+    ///
+    /// * `graph`: The BBGraph the cycle belongs to
+    pub fn signed_area(&self, graph: &BBGraph) -> BBResult<f32> {
+        let outer = Moments::of_edges(graph, &self.edges)?;
+        let mut area = outer.area.abs();
+        for hole in self.holes() {
+            area -= Moments::of_edges(graph, &hole.edges)?.area.abs();
+        }
+        Ok(if outer.area < 0. { -area } else { area })
+    }
+
+    /// Calculates the centroid of the area enclosed by this cycle, excluding its holes.  Falls
+    /// back to the center of the bounds if the cycle doesn't enclose any area.
+    ///
+    /// * `graph`: The BBGraph the cycle belongs to
+    pub fn centroid(&self, graph: &BBGraph) -> BBResult<Vec2> {
+        let mut total = Moments::of_edges(graph, &self.edges)?.unsigned();
+        for hole in self.holes() {
+            let hole = Moments::of_edges(graph, &hole.edges)?.unsigned();
+            total.area -= hole.area;
+            total.moment -= hole.moment;
+        }
+
+        if total.area.abs() < f32::EPSILON {
+            let (min, max) = self.bounds(graph)?;
+            return Ok((min + max) / 2.);
+        }
+        Ok(total.moment / total.area)
+    }
+
+    /// Returns the (min, max) of the tight bounding box of this cycle's edges.
+    ///
+    /// * `graph`: The BBGraph the cycle belongs to
+    pub fn bounds(&self, graph: &BBGraph) -> BBResult<(Vec2, Vec2)> {
+        let mut bounds = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+        for edge_idx in &self.edges {
+            let (min, max) = graph.edge(*edge_idx)?.bounds(graph);
+            bounds = (bounds.0.min(min), bounds.1.max(max));
+        }
+        Ok(bounds)
+    }
+
+    /// Calculates the total length of this cycle's edges and the edges of its holes.
+    ///
+    /// * `graph`: The BBGraph the cycle belongs to
+    pub fn perimeter(&self, graph: &BBGraph) -> BBResult<f32> {
+        let mut perimeter = 0.;
+        for cycle in std::iter::once(self).chain(self.holes()) {
+            for edge_idx in &cycle.edges {
+                perimeter += graph.edge(*edge_idx)?.length(graph);
+            }
+        }
+        Ok(perimeter)
+    }
+}
+
+impl BBGraph {
+    /// Calculates the filled area of a region, including holes cut by unfilled faces of other
+    /// regions that are nested inside of it.  See [BBGraph::region_fill_cycles].
+    ///
+    /// * `region_idx`: The region to measure
+    pub fn region_area(&self, region_idx: BBRegionIndex) -> BBResult<f32> {
+        let mut area = 0.;
+        for edges in self.region_fill_cycles(region_idx)? {
+            area += Moments::of_edges(self, &edges)?.area;
+        }
+        Ok(area)
+    }
+
+    /// Calculates the centroid of the filled area of a region, see [BBGraph::region_area].
+    /// Falls back to the center of the root cycle's bounds if the region has no filled area.
+    ///
+    /// * `region_idx`: The region to measure
+    pub fn region_centroid(&self, region_idx: BBRegionIndex) -> BBResult<Vec2> {
+        // Fill cycles are wound so that holes have a negative area, they can be summed directly.
+        let mut total = Moments::default();
+        for edges in self.region_fill_cycles(region_idx)? {
+            let moments = Moments::of_edges(self, &edges)?;
+            total.area += moments.area;
+            total.moment += moments.moment;
+        }
+
+        if total.area.abs() < f32::EPSILON {
+            let (min, max) = self.region(region_idx)?.root_cycle.bounds(self)?;
+            return Ok((min + max) / 2.);
+        }
+        Ok(total.moment / total.area)
+    }
+
+    /// Finds the innermost face containing `p`, returning it if it is filled.  Returns `None` if
+    /// the point is outside of every region or inside of a hole.  Requires the regions to be up
+    /// to date, see [BBGraph::update_regions].
+    ///
+    /// * `p`: The point to test
+    pub fn region_at_point(&self, p: Vec2) -> BBResult<Option<(BBRegionIndex, &BBCycle)>> {
+        let mut region_indices: Vec<_> = self.regions.keys().copied().collect();
+        region_indices.sort();
+
+        let mut innermost: Option<(f32, BBRegionIndex, &BBCycle)> = None;
+        for region_idx in region_indices {
+            for face in self.regions[&region_idx].root_cycle.faces() {
+                let polygon: Vec<_> = self
+                    .flatten_edges(&face.edges, DEFAULT_FLATTEN_TOLERANCE)?
+                    .positions()
+                    .collect();
+                if !polygon_contains(&polygon, p) {
+                    continue;
+                }
+                let area = polygon_signed_area(&polygon).abs();
+                if innermost.is_none_or(|(smallest, ..)| area < smallest) {
+                    innermost = Some((area, region_idx, face));
+                }
+            }
+        }
+
+        Ok(innermost
+            .filter(|(_, _, face)| face.filled)
+            .map(|(_, region_idx, face)| (region_idx, face)))
+    }
+
+    /// Returns the (min, max) of the tight bounding box of every edge and node in the graph, or
+    /// `None` if the graph is empty.
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        let edge_bounds = self.edges.values().map(|edge| edge.bounds(self));
+        let node_bounds = self
+            .nodes
+            .values()
+            .map(|node| (node.position(), node.position()));
+        edge_bounds
+            .chain(node_bounds)
+            .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+    }
+
+    /// Returns the convex hull of every edge and node in the graph, counterclockwise without
+    /// repeating the first point.  Curves are flattened with [DEFAULT_FLATTEN_TOLERANCE] so the
+    /// hull can be inset from the true curve by up to that distance.
+    pub fn convex_hull(&self) -> Vec<Vec2> {
+        let mut points: Vec<_> = self.nodes.values().map(|node| node.position()).collect();
+        for edge in self.edges.values() {
+            points.extend(
+                edge.flatten(self, DEFAULT_FLATTEN_TOLERANCE)
+                    .into_iter()
+                    .map(|(_, p)| p),
+            );
+        }
+        convex_hull(points)
+    }
+}
+
+/// Andrew's monotone chain, returns the counterclockwise hull of `points` without collinear
+/// points.
+fn convex_hull(mut points: Vec<Vec2>) -> Vec<Vec2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    // Pops points from the end of the hull, while keeping `keep` points, until adding `p` turns
+    // counterclockwise.
+    fn push(hull: &mut Vec<Vec2>, keep: usize, p: Vec2) {
+        while hull.len() > keep + 1
+            && (hull[hull.len() - 1] - hull[hull.len() - 2]).perp_dot(p - hull[hull.len() - 2])
+                <= 0.
+        {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    // Lower hull left to right, then upper hull right to left back to the first point.
+    let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+    for p in &points {
+        push(&mut hull, 0, *p);
+    }
+    let keep = hull.len() - 1;
+    for p in points.iter().rev().skip(1) {
+        push(&mut hull, keep, *p);
+    }
+    hull.pop();
+    hull
+}
@@ -1,6 +1,20 @@
+pub mod boolean;
+pub mod cleanup;
 pub mod clipping;
+pub mod corner;
+pub mod document;
+pub mod flatten;
 #[cfg(feature = "lyon_path")]
 pub mod lyon;
+pub mod metrics;
+pub mod offset;
+pub mod sampling;
+pub mod simplify;
+mod slots;
+pub mod spatial;
+pub mod svg;
+pub mod validate;
+pub mod winding;
 
 use std::collections::hash_map::{self};
 
@@ -13,7 +27,7 @@ use std::{
 
 use crate::{bb_edge::v2_to_c2, prelude::Determinate};
 use flo_curves::{bezier::curve_intersects_line, line::Line2D, Line};
-use glam::{vec2, Mat2, Vec2};
+use glam::{vec2, Affine2, Mat2, Vec2};
 
 use super::{
     bb_edge::{BBEdge, BBEdgeIndex},
@@ -21,18 +35,21 @@ use super::{
     bb_region::{BBCycle, BBRegion, BBRegionIndex},
     errors::{BBError, BBResult},
 };
+use slots::BBSlotAllocator;
+use spatial::BBLazySpatialIndex;
 
+/// With the `serde` feature the graph is (de)serialised as a [document::BBDocument], which is
+/// stable across changes to this layout.
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", serde_with::serde_as)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BBGraph {
-    next_idx: usize,
-    #[cfg_attr(feature = "serde", serde_as(as = "Vec<(_, _)>"))]
+    slots: BBSlotAllocator,
     pub nodes: HashMap<BBNodeIndex, BBNode>,
-    #[cfg_attr(feature = "serde", serde_as(as = "Vec<(_, _)>"))]
     pub edges: HashMap<BBEdgeIndex, BBEdge>,
-    #[cfg_attr(feature = "serde", serde_as(as = "Vec<(_, _)>"))]
     pub regions: HashMap<BBRegionIndex, BBRegion>,
+    spatial_index: Option<BBLazySpatialIndex>,
+    /// Nodes that were added, removed, moved or had their edges changed since the last time the
+    /// regions were updated, see [BBGraph::update_regions_dirty].
+    dirty_nodes: HashSet<BBNodeIndex>,
 }
 
 impl Display for BBGraph {
@@ -57,14 +74,17 @@ impl Default for BBGraph {
 impl BBGraph {
     pub fn new() -> Self {
         Self {
-            next_idx: 0,
+            slots: BBSlotAllocator::default(),
             nodes: HashMap::new(),
             edges: HashMap::new(),
             regions: HashMap::new(),
+            spatial_index: None,
+            dirty_nodes: HashSet::new(),
         }
     }
 
-    /// Generates a new BBGraph from the edges of another BBGraph
+    /// Generates a new BBGraph from the edges of another BBGraph, the new graph shares the same
+    /// indices as `other`.
     ///
     /// * `other`:
     /// * `edges`:
@@ -72,7 +92,6 @@ impl BBGraph {
         other: &BBGraph,
         edge_indices: &[BBEdgeIndex],
     ) -> BBResult<Self> {
-        let next_idx = 0;
         let mut nodes = HashMap::new();
         let mut edges = HashMap::new();
 
@@ -98,18 +117,14 @@ impl BBGraph {
         }
 
         Ok(Self {
-            next_idx,
+            slots: other.slots.clone(),
             nodes,
             edges,
             regions: HashMap::new(),
+            spatial_index: None,
+            dirty_nodes: HashSet::new(),
         })
     }
-
-    fn get_next_idx(&mut self) -> usize {
-        let v = self.next_idx;
-        self.next_idx += 1;
-        v
-    }
 }
 
 /**
@@ -125,15 +140,31 @@ impl BBGraph {
             None => Err(BBError::MissingEdge(index)),
         }
     }
-    /// Gets a mutable reference to a Vector Network edge by ID
+    /// Gets a mutable reference to a Vector Network edge by ID, marks the edge as stale in the
+    /// spatial index.
     ///
     /// * `index`: ID of edge to get
     pub fn edge_mut(&mut self, index: BBEdgeIndex) -> BBResult<&mut BBEdge> {
+        let edge = self.edge(index)?;
+        let (start_idx, end_idx) = (edge.start_idx(), edge.end_idx());
+        self.dirty_nodes.insert(start_idx);
+        self.dirty_nodes.insert(end_idx);
+        self.spatial_mark_edge_stale(index);
+
         self.edges
             .get_mut(&index)
             .ok_or(BBError::MissingEdge(index))
     }
 
+    /// Gets a reference to a region by ID
+    ///
+    /// * `index`: ID of region to get
+    pub fn region(&self, index: BBRegionIndex) -> BBResult<&BBRegion> {
+        self.regions
+            .get(&index)
+            .ok_or(BBError::MissingRegion(index))
+    }
+
     /// Given a list of edge indices, returns the edges with all directions oriented in a
     /// continuous direction.
     ///
@@ -182,14 +213,30 @@ impl BBGraph {
             None => Err(BBError::MissingNode(index)),
         }
     }
-    /// Gets a mutable reference to a node by ID
+    /// Gets a mutable reference to a node by ID, marks the node and its edges as stale in the
+    /// spatial index.
     ///
     /// * `index`: ID of node to get
     pub fn node_mut(&mut self, index: BBNodeIndex) -> BBResult<&mut BBNode> {
+        self.node(index)?;
+        self.dirty_nodes.insert(index);
+        self.spatial_mark_node_stale(index);
+
         self.nodes
             .get_mut(&index)
             .ok_or(BBError::MissingNode(index))
     }
+    /// Gets a mutable reference to the edges connected to a node.  Unlike [BBGraph::node_mut]
+    /// the node can't be moved so the spatial index doesn't need updating.
+    ///
+    /// * `index`: ID of node to get the adjacents of
+    pub(crate) fn adjacents_mut(&mut self, index: BBNodeIndex) -> BBResult<&mut Vec<BBEdgeIndex>> {
+        self.dirty_nodes.insert(index);
+        self.nodes
+            .get_mut(&index)
+            .map(|node| &mut node.adjacents)
+            .ok_or(BBError::MissingNode(index))
+    }
 
     /// Returns the count of nodes in the graph
     pub fn nodes_count(&self) -> usize {
@@ -212,10 +259,24 @@ impl BBGraph {
     ///
     /// * `value`: Position of the node
     pub(crate) fn add_node(&mut self, position: Vec2) -> BBNodeIndex {
-        let node_idx = BBNodeIndex(self.get_next_idx());
+        let (slot, generation) = self.slots.allocate();
+        let node_idx = BBNodeIndex::new(slot, generation);
         self.nodes.insert(node_idx, BBNode::new(position));
+        self.spatial_insert_node(node_idx);
+        self.dirty_nodes.insert(node_idx);
         node_idx
     }
+    /// Removes a node from the node map and frees its index for re-use, without updating any
+    /// edges that reference it.
+    ///
+    /// * `index`: Index of the node to remove
+    pub(crate) fn take_node(&mut self, index: BBNodeIndex) -> Option<BBNode> {
+        let node = self.nodes.remove(&index)?;
+        self.slots.release(index.slot(), index.generation());
+        self.spatial_remove_node(index);
+        self.dirty_nodes.remove(&index);
+        Some(node)
+    }
     /// Removes a node from the graph by ID.  Will delete connected edges and regions.
     ///
     /// * `index`: Index of the node to delete
@@ -223,7 +284,7 @@ impl BBGraph {
         debug_assert!(self.has_node(index));
 
         let adjacents = self.node(index)?.adjacents.clone();
-        self.nodes.remove(&index);
+        self.take_node(index);
 
         for adj in adjacents {
             self.delete_edge(adj)?;
@@ -238,15 +299,26 @@ impl BBGraph {
     ///
     /// * `edge`: The edge data to add to the BBGraph
     fn add_edge(&mut self, edge: BBEdge) -> (BBEdgeIndex, BBEdge) {
-        let index = BBEdgeIndex(self.get_next_idx());
+        let (slot, generation) = self.slots.allocate();
+        let index = BBEdgeIndex::new(slot, generation);
         self.edges.insert(index, edge);
-        self.node_mut(edge.start_idx())
-            .unwrap()
-            .adjacents
-            .push(index);
-        self.node_mut(edge.end_idx()).unwrap().adjacents.push(index);
+        self.adjacents_mut(edge.start_idx()).unwrap().push(index);
+        self.adjacents_mut(edge.end_idx()).unwrap().push(index);
+        self.spatial_insert_edge(index);
         (index, edge)
     }
+    /// Removes an edge from the edge map and frees its index for re-use, without updating the
+    /// adjacents of the nodes it connects.
+    ///
+    /// * `index`: Index of the edge to remove
+    pub(crate) fn take_edge(&mut self, index: BBEdgeIndex) -> Option<BBEdge> {
+        let edge = self.edges.remove(&index)?;
+        self.slots.release(index.slot(), index.generation());
+        self.spatial_remove_edge(index);
+        self.dirty_nodes.insert(edge.start_idx());
+        self.dirty_nodes.insert(edge.end_idx());
+        Some(edge)
+    }
 
     /// Removes an edge from the graph.  If necessary, will also delete the nodes this edge
     /// connects.
@@ -254,18 +326,18 @@ impl BBGraph {
     /// * `edge_idx`: ID of the edge to delete.
     pub fn delete_edge(&mut self, edge_idx: BBEdgeIndex) -> BBResult<BBEdge> {
         let edge = *self.edge(edge_idx)?;
-        self.edges.remove(&edge_idx);
+        self.take_edge(edge_idx);
 
-        if let Ok(start) = self.node_mut(edge.start_idx()) {
-            start.adjacents.retain(|e_idx| *e_idx != edge_idx);
-            if start.adjacents().is_empty() {
+        if let Ok(start) = self.adjacents_mut(edge.start_idx()) {
+            start.retain(|e_idx| *e_idx != edge_idx);
+            if start.is_empty() {
                 self.delete_node(edge.start_idx())?;
             }
         }
 
-        if let Ok(end) = self.node_mut(edge.end_idx()) {
-            end.adjacents.retain(|e_idx| *e_idx != edge_idx);
-            if end.adjacents().is_empty() {
+        if let Ok(end) = self.adjacents_mut(edge.end_idx()) {
+            end.retain(|e_idx| *e_idx != edge_idx);
+            if end.is_empty() {
                 self.delete_node(edge.end_idx())?;
             }
         }
@@ -526,6 +598,7 @@ impl BBGraph {
         for l in self.edges.values_mut() {
             l.translate(translation);
         }
+        self.spatial_translate(translation);
     }
 
     pub fn rotate(&mut self, origin: Vec2, angle_delta: f32) {
@@ -539,6 +612,69 @@ impl BBGraph {
         for l in self.edges.values_mut() {
             l.rotate_by_matrix(origin, &rot_matrix);
         }
+        self.mark_spatial_index_stale();
+    }
+
+    /// Applies an affine transform (i.e. scale, skew, rotation, translation) to every node and
+    /// control point.  If the transform is a reflection the region cycles are reversed so they
+    /// keep their orientation.  A `Mat3` can be converted with [Affine2::from_mat3].
+    ///
+    /// * `transform`: The transform to apply
+    pub fn transform(&mut self, transform: Affine2) {
+        for v in self.nodes.values_mut() {
+            v.position = transform.transform_point2(v.position);
+        }
+        for l in self.edges.values_mut() {
+            l.transform(&transform);
+        }
+
+        if transform.matrix2.determinant() < 0. {
+            for region in self.regions.values_mut() {
+                region.root_cycle.reverse_deep();
+            }
+        }
+        self.mark_spatial_index_stale();
+    }
+
+    /// Applies an affine transform to a subset of the edges, transforming their control points
+    /// and the nodes they connect.  Edges outside of the subset that share one of these nodes
+    /// will have that end moved too.  If the transform is a reflection, region cycles that only
+    /// contain transformed edges are reversed so they keep their orientation.
+    ///
+    /// * `edges`: The edges to transform
+    /// * `transform`: The transform to apply
+    pub fn transform_subset(&mut self, edges: &[BBEdgeIndex], transform: Affine2) -> BBResult<()> {
+        let mut node_indices = HashSet::new();
+        for edge_idx in edges {
+            let edge = self.edge(*edge_idx)?;
+            node_indices.insert(edge.start_idx());
+            node_indices.insert(edge.end_idx());
+        }
+
+        for node_idx in node_indices {
+            let node = self.node_mut(node_idx)?;
+            node.position = transform.transform_point2(node.position);
+        }
+        let edges: HashSet<_> = edges.iter().copied().collect();
+        for edge_idx in &edges {
+            self.edge_mut(*edge_idx)?.transform(&transform);
+        }
+
+        if transform.matrix2.determinant() < 0. {
+            for region in self.regions.values_mut() {
+                let is_transformed = region
+                    .root_cycle
+                    .edges_deep()
+                    .iter()
+                    .flatten()
+                    .all(|edge_idx| edges.contains(edge_idx));
+                if is_transformed {
+                    region.root_cycle.reverse_deep();
+                }
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -957,42 +1093,162 @@ impl BBGraph {
     ///
     /// * `region`: The region to add
     fn add_region(&mut self, region: BBRegion) -> BBRegionIndex {
-        let index = BBRegionIndex(self.get_next_idx());
+        let (slot, generation) = self.slots.allocate();
+        let index = BBRegionIndex::new(slot, generation);
         self.regions.insert(index, region);
         index
     }
 
-    /// Recalculates all of the regions for the bb_graph.  This will make any pre-existing `BBRegionIndex` invalid.
+    /// Recalculates all of the regions for the bb_graph.  A region that encloses exactly the same
+    /// edges as before keeps its `BBRegionIndex` and winding rule, any other pre-existing
+    /// `BBRegionIndex` will become invalid.
     pub fn update_regions(&mut self) -> BBResult<Vec<BBRegionIndex>> {
-        self.regions.clear();
+        self.dirty_nodes.clear();
+        let mut previous_regions: HashMap<Vec<BBEdgeIndex>, (BBRegionIndex, BBRegion)> = self
+            .regions
+            .drain()
+            .map(|(index, region)| (region_key(&region.root_cycle), (index, region)))
+            .collect();
 
         let mut region_indices = vec![];
+        for graph in self.get_detached_graphs()? {
+            if let Some(region) = BBGraph::build_region(graph)? {
+                region_indices.push(self.insert_region(region, &mut previous_regions));
+            }
+        }
 
-        for mut graph in self.get_detached_graphs()? {
-            graph.remove_filaments()?;
+        for (index, _) in previous_regions.into_values() {
+            self.slots.release(index.slot(), index.generation());
+        }
 
-            let Some(start_id) = graph.get_left_most_node_index() else {
-                continue;
-            };
+        self.update_region_fills()?;
+        self.debug_validate(true);
 
-            let perimiter = match graph.closed_walk_with_ccw_start_and_ccw_traverse(start_id) {
-                Ok(perimiter) => perimiter,
-                Err(reason) => {
-                    println!("Skipping graph for reason {reason:?}.");
-                    continue;
-                }
-            };
-            let mut cycle = BBCycle::new(perimiter.edges);
+        Ok(region_indices)
+    }
+
+    /// Recalculates the regions of the parts of the graph that have changed since the last call
+    /// to [BBGraph::update_regions] or [BBGraph::update_regions_dirty].  Regions of the detached
+    /// graphs that weren't touched are kept as is, the rest follow the same rules as
+    /// [BBGraph::update_regions].  Returns the indices of the regions that were recalculated.
+    ///
+    /// Changes are tracked through the graph building API, [BBGraph::node_mut] and
+    /// [BBGraph::edge_mut], changes made directly to [BBGraph::nodes] or [BBGraph::edges] aren't.
+    pub fn update_regions_dirty(&mut self) -> BBResult<Vec<BBRegionIndex>> {
+        let mut dirty_nodes: Vec<_> = self.dirty_nodes.drain().collect();
+        dirty_nodes.retain(|node_idx| self.has_node(*node_idx));
+        dirty_nodes.sort();
+
+        let mut dirty_edges = HashSet::new();
+        let mut detached_edges = vec![];
+        for node_idx in dirty_nodes {
+            let edges = self.connected_edges(node_idx, &mut dirty_edges)?;
+            if !edges.is_empty() {
+                detached_edges.push(edges);
+            }
+        }
 
-            graph.extract_cycles(&mut cycle)?;
+        // Regions in a changed detached graph, or that have lost edges, need to be recalculated.
+        let mut affected: Vec<_> = self
+            .regions
+            .iter()
+            .filter(|(_, region)| {
+                region
+                    .root_cycle
+                    .edges_deep()
+                    .iter()
+                    .flatten()
+                    .any(|edge_idx| {
+                        dirty_edges.contains(edge_idx) || !self.edges.contains_key(edge_idx)
+                    })
+            })
+            .map(|(index, _)| *index)
+            .collect();
+        affected.sort();
+        let mut previous_regions: HashMap<Vec<BBEdgeIndex>, (BBRegionIndex, BBRegion)> = affected
+            .into_iter()
+            .map(|index| {
+                let region = self.regions.remove(&index).unwrap();
+                (region_key(&region.root_cycle), (index, region))
+            })
+            .collect();
+
+        let mut region_indices = vec![];
+        for edges in detached_edges {
+            let graph = BBGraph::try_new_from_other_edges(self, &edges)?;
+            if let Some(region) = BBGraph::build_region(graph)? {
+                region_indices.push(self.insert_region(region, &mut previous_regions));
+            }
+        }
 
-            let region = BBRegion::new(cycle);
-            region_indices.push(self.add_region(region));
+        for (index, _) in previous_regions.into_values() {
+            self.slots.release(index.slot(), index.generation());
         }
 
+        self.update_region_fills()?;
+        self.debug_validate(true);
+
         Ok(region_indices)
     }
 
+    /// Returns every edge connected to `node_idx` that isn't already in `visited`, adding them
+    /// to `visited`.
+    fn connected_edges(
+        &self,
+        node_idx: BBNodeIndex,
+        visited: &mut HashSet<BBEdgeIndex>,
+    ) -> BBResult<Vec<BBEdgeIndex>> {
+        let mut edges = vec![];
+        let mut queue = VecDeque::from(vec![node_idx]);
+        while let Some(node_idx) = queue.pop_front() {
+            for edge_idx in self.node(node_idx)?.adjacents() {
+                if visited.insert(*edge_idx) {
+                    edges.push(*edge_idx);
+                    queue.push_back(self.edge(*edge_idx)?.other_node_idx(node_idx));
+                }
+            }
+        }
+        Ok(edges)
+    }
+
+    /// Calculates the region of a detached graph, returns `None` if it doesn't enclose anything.
+    fn build_region(mut graph: BBGraph) -> BBResult<Option<BBRegion>> {
+        graph.remove_filaments()?;
+
+        let Some(start_id) = graph.get_left_most_node_index() else {
+            return Ok(None);
+        };
+
+        let perimiter = match graph.closed_walk_with_ccw_start_and_ccw_traverse(start_id) {
+            Ok(perimiter) => perimiter,
+            // Detached graphs that can't be walked don't enclose a region, see
+            // [BBGraph::validate] to diagnose graphs that should.
+            Err(_) => return Ok(None),
+        };
+        let mut cycle = BBCycle::new(perimiter.edges);
+
+        graph.extract_cycles(&mut cycle)?;
+
+        Ok(Some(BBRegion::new(cycle)))
+    }
+
+    /// Adds a region, re-using the index and winding rule of a previous region that enclosed
+    /// exactly the same edges.
+    fn insert_region(
+        &mut self,
+        mut region: BBRegion,
+        previous_regions: &mut HashMap<Vec<BBEdgeIndex>, (BBRegionIndex, BBRegion)>,
+    ) -> BBRegionIndex {
+        match previous_regions.remove(&region_key(&region.root_cycle)) {
+            Some((index, previous)) => {
+                region.set_winding_rule(previous.winding_rule());
+                self.regions.insert(index, region);
+                index
+            }
+            None => self.add_region(region),
+        }
+    }
+
     fn extract_cycles(&mut self, parent_cycle: &mut BBCycle) -> BBResult<()> {
         while self.nodes_count() > 0 {
             // Need to cleanup filaments as it can prevent closed walks
@@ -1073,3 +1329,11 @@ impl BBGraph {
         Ok((parent_closed_walk, nested_closed_walk))
     }
 }
+
+/// The sorted edges of a region's cycles, regions with the same key enclose the same faces.
+fn region_key(cycle: &BBCycle) -> Vec<BBEdgeIndex> {
+    let mut edges = cycle.edges_deep().concat();
+    edges.sort();
+    edges.dedup();
+    edges
+}
@@ -0,0 +1,508 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    f32::consts::PI,
+};
+
+use glam::Vec2;
+
+use crate::{
+    bb_edge::{cubic_derivative, cubic_point, cubic_second_derivative, flatten_cubic, split_cubic},
+    prelude::*,
+};
+
+use super::{
+    boolean::COVERAGE_TOLERANCE,
+    corner::arc_cubics,
+    winding::{polygon_segments, winding_number},
+};
+
+/// Maximum distance between an approximated offset curve and the true offset.
+const OFFSET_TOLERANCE: f32 = 0.01;
+/// The offset tolerance is also capped to this fraction of the offset width, so that small
+/// offsets are as accurate as large ones.
+const RELATIVE_OFFSET_TOLERANCE: f32 = 0.01;
+/// Limits how many times a cubic is split in half while approximating its offset.
+const MAX_OFFSET_DEPTH: u32 = 8;
+/// Lines that meet with directions closer than this (sine of the angle) are merged.
+const COLLINEAR_TOLERANCE: f32 = 0.0001;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How the outside of corners are joined by [BBGraph::offset].
+pub enum BBLineJoin {
+    /// Extends both sides until they meet in a point.  Falls back to a bevel when the length of
+    /// the miter, relative to the stroke width, is greater than the limit.
+    Miter(f32),
+    /// Joins both sides with a circular arc.
+    Round,
+    /// Joins both sides with a straight line.
+    Bevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How the ends of open chains are capped by [BBGraph::offset].
+pub enum BBLineCap {
+    /// Ends flat at the end of the chain.
+    Butt,
+    /// Ends with a semicircle around the end of the chain.
+    Round,
+    /// Ends flat, extended past the end of the chain by the stroke width.
+    Square,
+}
+
+/// A piece of an outline, lines are kept separate so they stay lines in the result.
+#[derive(Debug, Clone, Copy)]
+enum Segment {
+    Line(Vec2, Vec2),
+    Cubic([Vec2; 4]),
+}
+
+/// Direction from `from` to the first of `points` that isn't on top of it.
+fn direction_towards(from: Vec2, points: &[Vec2]) -> Vec2 {
+    points
+        .iter()
+        .find_map(|p| (*p - from).try_normalize())
+        .unwrap_or(Vec2::ZERO)
+}
+
+/// The normal to the right of the direction of travel.
+fn right_normal(tangent: Vec2) -> Vec2 {
+    -tangent.perp()
+}
+
+/// Signed curvature at `t`, positive when the curve turns counterclockwise.
+fn cubic_curvature(cubic: [Vec2; 4], t: f32) -> f32 {
+    let d1 = cubic_derivative(cubic, t);
+    let length = d1.length();
+    if length < f32::EPSILON {
+        return 0.;
+    }
+    d1.perp_dot(cubic_second_derivative(cubic, t)) / (length * length * length)
+}
+
+impl Segment {
+    fn start(&self) -> Vec2 {
+        match self {
+            Segment::Line(start, _) => *start,
+            Segment::Cubic([start, ..]) => *start,
+        }
+    }
+
+    fn end(&self) -> Vec2 {
+        match self {
+            Segment::Line(_, end) => *end,
+            Segment::Cubic([.., end]) => *end,
+        }
+    }
+
+    fn start_tangent(&self) -> Vec2 {
+        match self {
+            Segment::Line(start, end) => direction_towards(*start, &[*end]),
+            Segment::Cubic([p0, p1, p2, p3]) => direction_towards(*p0, &[*p1, *p2, *p3]),
+        }
+    }
+
+    fn end_tangent(&self) -> Vec2 {
+        match self {
+            Segment::Line(start, end) => -direction_towards(*end, &[*start]),
+            Segment::Cubic([p0, p1, p2, p3]) => -direction_towards(*p3, &[*p2, *p1, *p0]),
+        }
+    }
+
+    fn reversed(&self) -> Self {
+        match self {
+            Segment::Line(start, end) => Segment::Line(*end, *start),
+            Segment::Cubic([p0, p1, p2, p3]) => Segment::Cubic([*p3, *p2, *p1, *p0]),
+        }
+    }
+
+    fn is_degenerate(&self) -> bool {
+        match self {
+            Segment::Line(start, end) => start.distance(*end) < f32::EPSILON,
+            Segment::Cubic(points) => points.iter().all(|p| p.distance(points[0]) < f32::EPSILON),
+        }
+    }
+
+    /// Points along the segment, excluding the end.
+    ///
+    /// * `tolerance`: Maximum distance between the curve and the polyline
+    fn polyline(&self, tolerance: f32) -> Vec<Vec2> {
+        match self {
+            Segment::Line(start, _) => vec![*start],
+            Segment::Cubic(cubic) => {
+                let mut points = flatten_cubic(*cubic, tolerance);
+                points.pop();
+                points.into_iter().map(|(_, p)| p).collect()
+            }
+        }
+    }
+
+    /// Approximates the curve `width` to the right of this segment.
+    fn offset(&self, width: f32, tolerance: f32, out: &mut Vec<Segment>) {
+        match self {
+            Segment::Line(start, end) => {
+                let normal = right_normal(self.start_tangent()) * width;
+                out.push(Segment::Line(*start + normal, *end + normal));
+            }
+            Segment::Cubic(cubic) => offset_cubic(*cubic, width, tolerance, 0, out),
+        }
+    }
+}
+
+/// Approximates the offset of a cubic by moving its end points along their normals and scaling
+/// the handles by the change in curvature, splitting it until it is within tolerance.
+fn offset_cubic(
+    cubic: [Vec2; 4],
+    width: f32,
+    tolerance: f32,
+    depth: u32,
+    out: &mut Vec<Segment>,
+) {
+    let segment = Segment::Cubic(cubic);
+    let [p0, p1, p2, p3] = cubic;
+    let start = p0 + right_normal(segment.start_tangent()) * width;
+    let end = p3 + right_normal(segment.end_tangent()) * width;
+    let start_scale = (1. + width * cubic_curvature(cubic, 0.)).max(0.);
+    let end_scale = (1. + width * cubic_curvature(cubic, 1.)).max(0.);
+    let offset = [
+        start,
+        start + (p1 - p0) * start_scale,
+        end + (p2 - p3) * end_scale,
+        end,
+    ];
+
+    let within_tolerance = [0.25, 0.5, 0.75].iter().all(|t| {
+        let tangent = cubic_derivative(cubic, *t).normalize_or_zero();
+        let expected = cubic_point(cubic, *t) + right_normal(tangent) * width;
+        cubic_point(offset, *t).distance(expected) <= tolerance
+    });
+    if within_tolerance || depth >= MAX_OFFSET_DEPTH {
+        out.push(Segment::Cubic(offset));
+        return;
+    }
+
+    let (first, second) = split_cubic(cubic);
+    offset_cubic(first, width, tolerance, depth + 1, out);
+    offset_cubic(second, width, tolerance, depth + 1, out);
+}
+
+/// Adds the segments that join two offset segments around the original `corner`.
+fn join(
+    corner: Vec2,
+    prev: &Segment,
+    next: &Segment,
+    join: BBLineJoin,
+    tolerance: f32,
+    out: &mut Vec<Segment>,
+) {
+    let (from, to) = (prev.end(), next.start());
+    // Smooth joins only leave gaps from rounding errors.
+    if from.distance(to) < tolerance {
+        return;
+    }
+    let width = from.distance(corner);
+    let (prev_tangent, next_tangent) = (prev.end_tangent(), next.start_tangent());
+
+    // Turning right puts the offset on the inside of the corner, the sides overlap so they're
+    // connected through the corner and the overlap is removed when resolving intersections.
+    if prev_tangent.perp_dot(next_tangent) <= 0. {
+        out.push(Segment::Line(from, corner));
+        out.push(Segment::Line(corner, to));
+        return;
+    }
+
+    let turn = prev_tangent.angle_between(next_tangent).abs();
+    match join {
+        BBLineJoin::Miter(limit) if 1. / (turn / 2.).cos() <= limit => {
+            let direction = ((from - corner) + (to - corner)).normalize_or_zero();
+            let miter = corner + direction * width / (turn / 2.).cos();
+            out.push(Segment::Line(from, miter));
+            out.push(Segment::Line(miter, to));
+        }
+        BBLineJoin::Round => {
+            let start_angle = (from.y - corner.y).atan2(from.x - corner.x);
+            for cubic in arc_cubics(corner, width, start_angle, turn) {
+                out.push(Segment::Cubic(cubic));
+            }
+        }
+        BBLineJoin::Miter(_) | BBLineJoin::Bevel => out.push(Segment::Line(from, to)),
+    }
+}
+
+/// Adds the segments that cap the end of a chain, from the right side to the left side.
+fn cap(end: Vec2, tangent: Vec2, width: f32, cap: BBLineCap, out: &mut Vec<Segment>) {
+    let normal = right_normal(tangent) * width;
+    let (from, to) = (end + normal, end - normal);
+    match cap {
+        BBLineCap::Butt => out.push(Segment::Line(from, to)),
+        BBLineCap::Square => {
+            let extension = tangent * width;
+            out.push(Segment::Line(from, from + extension));
+            out.push(Segment::Line(from + extension, to + extension));
+            out.push(Segment::Line(to + extension, to));
+        }
+        BBLineCap::Round => {
+            let start_angle = normal.y.atan2(normal.x);
+            for cubic in arc_cubics(end, width, start_angle, PI) {
+                out.push(Segment::Cubic(cubic));
+            }
+        }
+    }
+}
+
+/// Offsets each segment of a path `width` to its right, joining the offset segments.  A closed
+/// path is also joined from its end back to its start.
+fn offset_path(path: &[Segment], closed: bool, width: f32, line_join: BBLineJoin) -> Vec<Segment> {
+    let tolerance = (width * RELATIVE_OFFSET_TOLERANCE).min(OFFSET_TOLERANCE);
+    let mut out: Vec<Segment> = vec![];
+    let mut first_offset = None;
+    for segment in path {
+        let mut offset = vec![];
+        segment.offset(width, tolerance, &mut offset);
+        if let Some(prev) = out.last().copied() {
+            join(segment.start(), &prev, &offset[0], line_join, tolerance, &mut out);
+        }
+        first_offset.get_or_insert(offset[0]);
+        out.extend(offset);
+    }
+    if closed {
+        let last = *out.last().unwrap();
+        join(
+            path[0].start(),
+            &last,
+            &first_offset.unwrap(),
+            line_join,
+            tolerance,
+            &mut out,
+        );
+    }
+    out
+}
+
+fn reverse_path(path: &[Segment]) -> Vec<Segment> {
+    path.iter().rev().map(Segment::reversed).collect()
+}
+
+/// Returns the closed outlines that enclose a stroke `width` either side of the path.  Every
+/// outline is wound so that the stroke is on its left.
+fn stroke_outlines(
+    path: &[Segment],
+    closed: bool,
+    width: f32,
+    line_join: BBLineJoin,
+    line_cap: BBLineCap,
+) -> Vec<Vec<Segment>> {
+    let right = offset_path(path, closed, width, line_join);
+    let left = offset_path(&reverse_path(path), closed, width, line_join);
+    if closed {
+        return vec![right, left];
+    }
+
+    let first = path.first().unwrap();
+    let last = path.last().unwrap();
+    let mut outline = right;
+    cap(
+        last.end(),
+        last.end_tangent(),
+        width,
+        line_cap,
+        &mut outline,
+    );
+    outline.extend(left);
+    cap(
+        first.start(),
+        -first.start_tangent(),
+        width,
+        line_cap,
+        &mut outline,
+    );
+    vec![outline]
+}
+
+/// Flattens closed outlines to polygons.
+///
+/// * `tolerance`: Maximum distance between the curves and the polygons
+fn outline_polygons(outlines: &[Vec<Segment>], tolerance: f32) -> Vec<Vec<Vec2>> {
+    outlines
+        .iter()
+        .map(|outline| {
+            outline
+                .iter()
+                .flat_map(|segment| segment.polyline(tolerance))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns true if the polygons wind around `p` (non-zero).
+fn polygons_contain(polygons: &[Vec<Vec2>], p: Vec2) -> bool {
+    polygons
+        .iter()
+        .map(|polygon| winding_number(polygon_segments(polygon), p))
+        .sum::<i32>()
+        != 0
+}
+
+impl BBGraph {
+    /// Converts a continuous chain of edges to outline segments.
+    fn path_segments(&self, edges: &[BBEdgeIndex]) -> BBResult<(Vec<Segment>, bool)> {
+        let directed = self.edges_directed(edges)?;
+        let closed =
+            directed.first().unwrap().1.start_idx() == directed.last().unwrap().1.end_idx();
+        let segments = directed
+            .iter()
+            .map(|(_, edge)| match edge {
+                BBEdge::Line { .. } => Segment::Line(edge.start_pos(self), edge.end_pos(self)),
+                _ => Segment::Cubic(edge.cubic_points(self)),
+            })
+            .filter(|segment| !segment.is_degenerate())
+            .collect();
+        Ok((segments, closed))
+    }
+
+    /// Adds a closed outline to the graph as a loop of edges.
+    fn add_outline(&mut self, outline: &[Segment]) {
+        let outline: Vec<_> = outline.iter().filter(|s| !s.is_degenerate()).collect();
+        let Some(first) = outline.first() else {
+            return;
+        };
+        let first_idx = self.add_node(first.start());
+        let mut prev_idx = first_idx;
+        for (i, segment) in outline.iter().enumerate() {
+            let is_last = i + 1 == outline.len();
+            let (_, edge) = match (segment, is_last) {
+                (Segment::Line(..), true) => self.line_from_to(prev_idx, first_idx),
+                (Segment::Line(_, end), false) => self.line_from(prev_idx, *end),
+                (Segment::Cubic([_, ctrl1, ctrl2, _]), true) => {
+                    self.cubic_from_to(prev_idx, *ctrl1, *ctrl2, first_idx)
+                }
+                (Segment::Cubic([_, ctrl1, ctrl2, end]), false) => {
+                    self.cubic_from(prev_idx, *ctrl1, *ctrl2, *end)
+                }
+            };
+            prev_idx = edge.end_idx();
+        }
+    }
+
+    /// Replaces pairs of collinear lines that meet at a node with no other edges with a single
+    /// line.
+    fn merge_collinear_lines(&mut self) -> BBResult<()> {
+        let mut node_indices: Vec<_> = self.nodes.keys().copied().collect();
+        node_indices.sort();
+
+        for node_idx in node_indices {
+            let Ok(node) = self.node(node_idx) else {
+                continue;
+            };
+            let &[a_idx, b_idx] = node.adjacents() else {
+                continue;
+            };
+            let a = self.edge(a_idx)?.directed_from(node_idx);
+            let b = self.edge(b_idx)?.directed_from(node_idx);
+            if a_idx == b_idx
+                || a.end_idx() == b.end_idx()
+                || !matches!(a, BBEdge::Line { .. })
+                || !matches!(b, BBEdge::Line { .. })
+            {
+                continue;
+            }
+            let dir_a = (a.end_pos(self) - node.position()).normalize_or_zero();
+            let dir_b = (b.end_pos(self) - node.position()).normalize_or_zero();
+            if dir_a.perp_dot(dir_b).abs() > COLLINEAR_TOLERANCE || dir_a.dot(dir_b) > 0. {
+                continue;
+            }
+
+            // Keeps the direction of the original lines.
+            if self.edge(a_idx)?.end_idx() == node_idx {
+                self.line_from_to(a.end_idx(), b.end_idx());
+            } else {
+                self.line_from_to(b.end_idx(), a.end_idx());
+            }
+            self.delete_edge(a_idx)?;
+            self.delete_edge(b_idx)?;
+        }
+
+        Ok(())
+    }
+
+    /// Offsets the graph, returning a new graph that only contains the outline of the result.
+    /// Filled regions are grown outwards by `distance`, or shrunk inwards if it is negative.
+    /// Edges that aren't part of a region are converted to the outline of a stroke `distance`
+    /// wide on either side.  The regions of the result are recalculated.
+    ///
+    /// * `distance`: How far to offset, negative values inset filled regions
+    /// * `line_join`: How the outside of corners are joined
+    /// * `line_cap`: How the ends of open chains are capped
+    pub fn offset(
+        &self,
+        distance: f32,
+        line_join: BBLineJoin,
+        line_cap: BBLineCap,
+    ) -> BBResult<BBGraph> {
+        let width = distance.abs();
+        let mut result = BBGraph::new();
+
+        // Fill cycles are wound counterclockwise, with clockwise holes, so the right of each
+        // cycle is always outside of the filled area.
+        let mut shape_cycles = vec![];
+        let mut shape_outlines = vec![];
+        for cycle in self.fill_cycles()? {
+            let (path, _) = self.path_segments(&cycle)?;
+            if path.is_empty() {
+                continue;
+            }
+            shape_cycles.push(cycle);
+            shape_outlines.extend(stroke_outlines(&path, true, width, line_join, line_cap));
+            result.add_outline(&path);
+        }
+
+        let mut region_edges = HashSet::new();
+        for region in self.regions.values() {
+            region_edges.extend(region.root_cycle.edges_deep().into_iter().flatten());
+        }
+        let stroke_edges: BTreeSet<_> = self
+            .edges
+            .keys()
+            .filter(|edge_idx| !region_edges.contains(*edge_idx))
+            .copied()
+            .collect();
+        let mut chain_outlines = vec![];
+        for chain in self.stroke_chains(&stroke_edges)? {
+            let (path, closed) = self.path_segments(&chain)?;
+            if path.is_empty() {
+                continue;
+            }
+            chain_outlines.extend(stroke_outlines(&path, closed, width, line_join, line_cap));
+        }
+
+        for outline in shape_outlines.iter().chain(&chain_outlines) {
+            result.add_outline(outline);
+        }
+
+        // Side samples are `scaled_tolerance` from each edge, the polygons need to be closer than
+        // that.
+        let tolerance = result.scaled_tolerance() * COVERAGE_TOLERANCE;
+        let shape_polygons = shape_cycles
+            .iter()
+            .map(|cycle| Ok(self.flatten_edges(cycle, tolerance)?.positions().collect()))
+            .collect::<BBResult<Vec<Vec<Vec2>>>>()?;
+        let shape_outlines = outline_polygons(&shape_outlines, tolerance);
+        let chain_outlines = outline_polygons(&chain_outlines, tolerance);
+        let covers = |p: Vec2| {
+            let in_shape = polygons_contain(&shape_polygons, p);
+            let in_shape_outline = polygons_contain(&shape_outlines, p);
+            let in_shape_offset = if distance < 0. {
+                in_shape && !in_shape_outline
+            } else {
+                in_shape || in_shape_outline
+            };
+            in_shape_offset || polygons_contain(&chain_outlines, p)
+        };
+
+        // Only keep the edges that separate covered and uncovered areas.
+        result.keep_boundary_edges(covers)?;
+        result.merge_collinear_lines()?;
+        result.update_regions()?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,142 @@
+use glam::Vec2;
+
+use crate::{bb_edge::BBArcLengthTable, prelude::*};
+
+/// A point sampled at a distance along a chain of edges, see [BBGraph::sample_evenly].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BBSample {
+    pub position: Vec2,
+    /// Normalised direction of travel along the chain.
+    pub tangent: Vec2,
+    /// Distance from the start of the chain.
+    pub distance: f32,
+    /// The edge this sample lies on.
+    pub edge_idx: BBEdgeIndex,
+    /// Position along the edge, in the direction the edge is stored in the graph.
+    pub t: f32,
+}
+
+struct SampledEdge {
+    edge_idx: BBEdgeIndex,
+    /// The edge, directed to be continuous with the chain.
+    edge: BBEdge,
+    /// Whether `edge` is the reverse of the edge stored in the graph.
+    reversed: bool,
+    table: BBArcLengthTable,
+    /// Distance from the start of the chain to the start of this edge.
+    start_distance: f32,
+}
+
+/// Iterator of evenly spaced samples along a chain of edges, created by
+/// [BBGraph::sample_evenly].
+pub struct BBEvenSamples<'a> {
+    graph: &'a BBGraph,
+    edges: Vec<SampledEdge>,
+    spacing: f32,
+    total_length: f32,
+    /// Index of the next sample, its distance is calculated from this rather than accumulated
+    /// so float error can't build up or stop the iterator advancing.
+    next_index: usize,
+    edge_cursor: usize,
+}
+
+impl<'a> BBEvenSamples<'a> {
+    /// The total length of the chain of edges.
+    pub fn total_length(&self) -> f32 {
+        self.total_length
+    }
+}
+
+impl<'a> Iterator for BBEvenSamples<'a> {
+    type Item = BBSample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let distance = match self.next_index {
+            0 => 0.,
+            index if self.spacing > 0. => index as f32 * self.spacing,
+            // Can't advance, only yield the first sample.
+            _ => return None,
+        };
+        // Small tolerance so float error doesn't drop the sample at the very end.
+        if self.edges.is_empty() || distance > self.total_length + f32::EPSILON * self.total_length
+        {
+            return None;
+        }
+        self.next_index += 1;
+
+        while self.edge_cursor + 1 < self.edges.len() {
+            let edge = &self.edges[self.edge_cursor];
+            if distance <= edge.start_distance + edge.table.length() {
+                break;
+            }
+            self.edge_cursor += 1;
+        }
+
+        let sampled = &self.edges[self.edge_cursor];
+        let t = sampled.table.t_at_length(distance - sampled.start_distance);
+        Some(BBSample {
+            position: sampled.edge.t_point(self.graph, t),
+            tangent: sampled.edge.t_tangent(self.graph, t),
+            distance,
+            edge_idx: sampled.edge_idx,
+            t: if sampled.reversed { 1. - t } else { t },
+        })
+    }
+}
+
+impl BBGraph {
+    /// Returns an iterator of samples spaced `spacing` apart, measured along the length of a
+    /// chain of edges.  The first sample is at the start of the chain.
+    ///
+    /// * `edges`: A continuous chain of edges, such as the edges of a [BBCycle]
+    /// * `spacing`: Distance between each sample, only the first sample is returned if it isn't
+    ///   positive
+    /// * `tolerance`: Maximum error when measuring the length of each part of an edge
+    pub fn sample_evenly(
+        &self,
+        edges: &[BBEdgeIndex],
+        spacing: f32,
+        tolerance: f32,
+    ) -> BBResult<BBEvenSamples<'_>> {
+        let mut sampled_edges = vec![];
+        let mut total_length = 0.;
+        for (edge_idx, edge) in self.edges_directed(edges)? {
+            let table = edge.arc_length_table(self, tolerance);
+            let length = table.length();
+            sampled_edges.push(SampledEdge {
+                edge_idx,
+                reversed: edge.start_idx() != self.edge(edge_idx)?.start_idx(),
+                edge,
+                table,
+                start_distance: total_length,
+            });
+            total_length += length;
+        }
+
+        Ok(BBEvenSamples {
+            graph: self,
+            edges: sampled_edges,
+            spacing,
+            total_length,
+            next_index: 0,
+            edge_cursor: 0,
+        })
+    }
+}
+
+impl BBCycle {
+    /// Returns an iterator of samples spaced `spacing` apart along the edges of this cycle, see
+    /// [BBGraph::sample_evenly].
+    ///
+    /// * `graph`: The BBGraph the cycle belongs to
+    /// * `spacing`: Distance between each sample
+    /// * `tolerance`: Maximum error when measuring the length of each part of an edge
+    pub fn sample_evenly<'a>(
+        &self,
+        graph: &'a BBGraph,
+        spacing: f32,
+        tolerance: f32,
+    ) -> BBResult<BBEvenSamples<'a>> {
+        graph.sample_evenly(&self.edges, spacing, tolerance)
+    }
+}
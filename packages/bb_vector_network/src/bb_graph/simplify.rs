@@ -0,0 +1,346 @@
+use std::{collections::HashSet, f32::consts::FRAC_PI_3};
+
+use glam::Vec2;
+
+use crate::{
+    bb_edge::{cubic_derivative, cubic_point, cubic_second_derivative},
+    prelude::*,
+};
+
+/// Nodes where the direction of the path turns by more than this many radians are kept as
+/// corners by [BBGraph::simplify].
+pub const DEFAULT_CORNER_ANGLE: f32 = FRAC_PI_3;
+/// How many times the curve parameters are refined before a fit is split in two.
+const MAX_REPARAMETERIZE_ITERATIONS: usize = 4;
+
+/// What was changed by [BBGraph::simplify].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BBSimplifyReport {
+    /// Edges that were replaced by fitted curves.
+    pub removed_edges: Vec<BBEdgeIndex>,
+    /// The fitted cubic edges.
+    pub added_edges: Vec<BBEdgeIndex>,
+}
+
+impl BBSimplifyReport {
+    /// Returns true if nothing was simplified.
+    pub fn is_empty(&self) -> bool {
+        self.removed_edges.is_empty() && self.added_edges.is_empty()
+    }
+}
+
+/// A run of edges, directed from its first node to its last, whose interior nodes can be
+/// removed.
+struct Chain {
+    edges: Vec<(BBEdgeIndex, BBEdge)>,
+    /// The chain starts and ends at the same smooth node.
+    smooth_loop: bool,
+}
+
+impl BBGraph {
+    /// Returns the turning angle at a node if it has exactly two different adjacent edges.
+    fn pass_through_angle(&self, node_idx: BBNodeIndex) -> BBResult<Option<f32>> {
+        let [a, b] = self.node(node_idx)?.adjacents() else {
+            return Ok(None);
+        };
+        if a == b {
+            return Ok(None);
+        }
+        let incoming = -self.edge(*a)?.directed_from(node_idx).t_tangent(self, 0.);
+        let outgoing = self.edge(*b)?.directed_from(node_idx).t_tangent(self, 0.);
+        Ok(Some(incoming.angle_between(outgoing).abs()))
+    }
+
+    /// Finds the chains of edges joined by smooth nodes with exactly two adjacent edges.  Chains
+    /// end at branch nodes, dead ends and corners sharper than `corner_angle`.
+    fn simplify_chains(&self, corner_angle: f32) -> BBResult<Vec<Chain>> {
+        let mut node_indices: Vec<_> = self.nodes.keys().copied().collect();
+        node_indices.sort();
+
+        let mut removable = HashSet::new();
+        for node_idx in &node_indices {
+            if self
+                .pass_through_angle(*node_idx)?
+                .is_some_and(|angle| angle <= corner_angle)
+            {
+                removable.insert(*node_idx);
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut chains = vec![];
+        // Closed loops of removable nodes are handled last, starting from any of their nodes.
+        let anchors = node_indices
+            .iter()
+            .filter(|idx| !removable.contains(*idx))
+            .chain(node_indices.iter().filter(|idx| removable.contains(*idx)));
+
+        for anchor_idx in anchors {
+            for first_idx in self.node(*anchor_idx)?.adjacents() {
+                if visited.contains(first_idx) {
+                    continue;
+                }
+
+                let mut edges = vec![];
+                let mut edge_idx = *first_idx;
+                let mut edge = self.edge(edge_idx)?.directed_from(*anchor_idx);
+                loop {
+                    visited.insert(edge_idx);
+                    edges.push((edge_idx, edge));
+                    let node_idx = edge.end_idx();
+                    if node_idx == *anchor_idx || !removable.contains(&node_idx) {
+                        break;
+                    }
+                    let node = self.node(node_idx)?;
+                    edge_idx = *node
+                        .adjacents()
+                        .iter()
+                        .find(|idx| **idx != edge_idx)
+                        .unwrap();
+                    edge = self.edge(edge_idx)?.directed_from(node_idx);
+                }
+
+                chains.push(Chain {
+                    smooth_loop: removable.contains(anchor_idx),
+                    edges,
+                });
+            }
+        }
+
+        Ok(chains)
+    }
+
+    /// Replaces chains of edges joined by smooth nodes with the fewest cubic curves that stay
+    /// within `tolerance` of the original edges.  Corners sharper than [DEFAULT_CORNER_ANGLE]
+    /// and nodes with more or less than two edges are kept.  Regions aren't updated, call
+    /// [BBGraph::update_regions] afterwards.
+    ///
+    /// * `tolerance`: Maximum distance between the original edges and the fitted curves
+    pub fn simplify(&mut self, tolerance: f32) -> BBResult<BBSimplifyReport> {
+        self.simplify_with_corner_angle(tolerance, DEFAULT_CORNER_ANGLE)
+    }
+
+    /// Like [BBGraph::simplify], but keeps corners that turn by more than `corner_angle`.
+    ///
+    /// * `tolerance`: Maximum distance between the original edges and the fitted curves
+    /// * `corner_angle`: Nodes where the path turns by more than this, in radians, are kept
+    pub fn simplify_with_corner_angle(
+        &mut self,
+        tolerance: f32,
+        corner_angle: f32,
+    ) -> BBResult<BBSimplifyReport> {
+        let mut report = BBSimplifyReport::default();
+
+        for chain in self.simplify_chains(corner_angle)? {
+            if chain.edges.len() < 2 {
+                continue;
+            }
+            let (_, first) = chain.edges[0];
+            let (_, last) = *chain.edges.last().unwrap();
+
+            // Sample the chain densely enough that a fit through the samples follows the edges.
+            let mut points = vec![first.start_pos(self)];
+            for (_, edge) in &chain.edges {
+                for (_, p) in edge.flatten(self, tolerance / 10.).into_iter().skip(1) {
+                    let prev = *points.last().unwrap();
+                    points.push((prev + p) / 2.);
+                    points.push(p);
+                }
+            }
+
+            let mut start_tangent = first.t_tangent(self, 0.);
+            let mut end_tangent = -last.t_tangent(self, 1.);
+            if chain.smooth_loop {
+                let average = (start_tangent - end_tangent).normalize_or_zero();
+                start_tangent = average;
+                end_tangent = -average;
+            }
+
+            let mut cubics = vec![];
+            fit_cubics(&points, start_tangent, end_tangent, tolerance, &mut cubics);
+            if cubics.len() >= chain.edges.len() {
+                continue;
+            }
+
+            let end_idx = last.end_idx();
+            let mut prev_idx = first.start_idx();
+            for (i, [_, ctrl1, ctrl2, end]) in cubics.iter().enumerate() {
+                let (edge_idx, edge) = if i + 1 == cubics.len() {
+                    self.cubic_from_to(prev_idx, *ctrl1, *ctrl2, end_idx)
+                } else {
+                    self.cubic_from(prev_idx, *ctrl1, *ctrl2, *end)
+                };
+                prev_idx = edge.end_idx();
+                report.added_edges.push(edge_idx);
+            }
+            // Deleting the old edges also deletes the interior nodes, which are left without
+            // any edges.
+            for (edge_idx, _) in chain.edges {
+                self.delete_edge(edge_idx)?;
+                report.removed_edges.push(edge_idx);
+            }
+        }
+
+        self.debug_validate(false);
+        Ok(report)
+    }
+}
+
+/// Parameterises the points by their distance along the polyline, from 0 to 1.
+fn chord_length_params(points: &[Vec2]) -> Vec<f32> {
+    let mut params = vec![0.];
+    for w in points.windows(2) {
+        params.push(params.last().unwrap() + w[0].distance(w[1]));
+    }
+    let total = *params.last().unwrap();
+    if total > 0. {
+        params.iter_mut().for_each(|u| *u /= total);
+    }
+    params
+}
+
+/// Least squares fit of a cubic to the points, with the control points constrained to lie along
+/// the tangents at each end.
+fn generate_cubic(
+    points: &[Vec2],
+    params: &[f32],
+    start_tangent: Vec2,
+    end_tangent: Vec2,
+) -> [Vec2; 4] {
+    let first = points[0];
+    let last = *points.last().unwrap();
+
+    let mut c = [[0.; 2]; 2];
+    let mut x = [0.; 2];
+    for (p, u) in points.iter().zip(params.iter().copied()) {
+        let mu = 1. - u;
+        let a0 = start_tangent * 3. * mu * mu * u;
+        let a1 = end_tangent * 3. * mu * u * u;
+        c[0][0] += a0.dot(a0);
+        c[0][1] += a0.dot(a1);
+        c[1][1] += a1.dot(a1);
+        let tmp = *p - cubic_point([first, first, last, last], u);
+        x[0] += a0.dot(tmp);
+        x[1] += a1.dot(tmp);
+    }
+    c[1][0] = c[0][1];
+
+    let det = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let (alpha_start, alpha_end) = if det.abs() > f32::EPSILON {
+        (
+            (x[0] * c[1][1] - x[1] * c[0][1]) / det,
+            (c[0][0] * x[1] - c[1][0] * x[0]) / det,
+        )
+    } else {
+        (0., 0.)
+    };
+
+    // Fall back to a third of the chord when the fit is degenerate.
+    let chord = first.distance(last);
+    let epsilon = chord * 1e-6;
+    if alpha_start < epsilon || alpha_end < epsilon {
+        let alpha = chord / 3.;
+        return [
+            first,
+            first + start_tangent * alpha,
+            last + end_tangent * alpha,
+            last,
+        ];
+    }
+    [
+        first,
+        first + start_tangent * alpha_start,
+        last + end_tangent * alpha_end,
+        last,
+    ]
+}
+
+/// Returns the largest distance between a point and the cubic at its parameter, and the index of
+/// that point.
+fn max_error(points: &[Vec2], params: &[f32], cubic: [Vec2; 4]) -> (f32, usize) {
+    let mut worst = (0., points.len() / 2);
+    for (i, (p, u)) in points.iter().zip(params).enumerate() {
+        let distance = cubic_point(cubic, *u).distance(*p);
+        if distance > worst.0 {
+            worst = (distance, i);
+        }
+    }
+    worst
+}
+
+/// Improves the parameters with a Newton-Raphson step towards the closest point on the cubic.
+fn reparameterize(points: &[Vec2], params: &[f32], cubic: [Vec2; 4]) -> Vec<f32> {
+    points
+        .iter()
+        .zip(params)
+        .map(|(p, u)| {
+            let delta = cubic_point(cubic, *u) - *p;
+            let d1 = cubic_derivative(cubic, *u);
+            let d2 = cubic_second_derivative(cubic, *u);
+            let denominator = d1.dot(d1) + delta.dot(d2);
+            if denominator.abs() < f32::EPSILON {
+                *u
+            } else {
+                (u - delta.dot(d1) / denominator).clamp(0., 1.)
+            }
+        })
+        .collect()
+}
+
+/// Fits cubics to the points using Schneider's algorithm, splitting at the worst point until
+/// every point is within `tolerance`.
+fn fit_cubics(
+    points: &[Vec2],
+    start_tangent: Vec2,
+    end_tangent: Vec2,
+    tolerance: f32,
+    out: &mut Vec<[Vec2; 4]>,
+) {
+    if points.len() == 2 {
+        out.push(generate_cubic(
+            points,
+            &[0., 1.],
+            start_tangent,
+            end_tangent,
+        ));
+        return;
+    }
+
+    let mut params = chord_length_params(points);
+    let mut cubic = generate_cubic(points, &params, start_tangent, end_tangent);
+    let (mut error, mut split) = max_error(points, &params, cubic);
+    if error <= tolerance {
+        out.push(cubic);
+        return;
+    }
+
+    // Close fits can often be fixed by adjusting where the points land on the curve.
+    if error <= tolerance * 4. {
+        for _ in 0..MAX_REPARAMETERIZE_ITERATIONS {
+            params = reparameterize(points, &params, cubic);
+            cubic = generate_cubic(points, &params, start_tangent, end_tangent);
+            (error, split) = max_error(points, &params, cubic);
+            if error <= tolerance {
+                out.push(cubic);
+                return;
+            }
+        }
+    }
+
+    let split = split.clamp(1, points.len() - 2);
+    let center_tangent = (points[split - 1] - points[split + 1]).normalize_or_zero();
+    fit_cubics(
+        &points[..=split],
+        start_tangent,
+        center_tangent,
+        tolerance,
+        out,
+    );
+    fit_cubics(
+        &points[split..],
+        -center_tangent,
+        end_tangent,
+        tolerance,
+        out,
+    );
+}
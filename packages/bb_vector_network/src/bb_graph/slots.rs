@@ -0,0 +1,36 @@
+/// Hands out (slot, generation) pairs for the generational indices of a [super::BBGraph], nodes,
+/// edges and regions all share the same slots.  Released slots are re-used with a bumped
+/// generation so stale indices can be detected.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BBSlotAllocator {
+    generations: Vec<u32>,
+    free: Vec<usize>,
+}
+
+impl BBSlotAllocator {
+    /// Returns a free slot and its current generation.
+    pub fn allocate(&mut self) -> (usize, u32) {
+        match self.free.pop() {
+            Some(slot) => (slot, self.generations[slot]),
+            None => {
+                self.generations.push(0);
+                (self.generations.len() - 1, 0)
+            }
+        }
+    }
+
+    /// Releases a slot so it can be re-used, does nothing if the index is already stale.
+    ///
+    /// * `slot`: The slot to release
+    /// * `generation`: The generation of the index being released
+    pub fn release(&mut self, slot: usize, generation: u32) {
+        let Some(current) = self.generations.get_mut(slot) else {
+            return;
+        };
+        if *current != generation {
+            return;
+        }
+        *current = current.wrapping_add(1);
+        self.free.push(slot);
+    }
+}
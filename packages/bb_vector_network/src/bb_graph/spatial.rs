@@ -0,0 +1,481 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use glam::Vec2;
+
+use crate::prelude::*;
+
+type Cell = (i32, i32);
+
+/// The most cells along each axis of a temporary index built for [BBGraph::broad_phase_edge_pairs].
+const MAX_TEMPORARY_CELLS: f32 = 256.;
+
+/// A uniform grid over the bounding boxes of a graph's edges and the positions of its nodes, used
+/// to speed up hit testing and intersection detection.  Enable it with
+/// [BBGraph::enable_spatial_index].
+#[derive(Debug, Clone)]
+pub struct BBSpatialIndex {
+    cell_size: f32,
+    edge_cells: HashMap<Cell, Vec<BBEdgeIndex>>,
+    edge_bounds: HashMap<BBEdgeIndex, (Vec2, Vec2)>,
+    node_cells: HashMap<Cell, Vec<BBNodeIndex>>,
+    node_positions: HashMap<BBNodeIndex, Vec2>,
+}
+
+impl BBSpatialIndex {
+    /// Creates an empty index.
+    ///
+    /// * `cell_size`: Width and height of each grid cell, ideally close to the size of an edge.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            edge_cells: HashMap::new(),
+            edge_bounds: HashMap::new(),
+            node_cells: HashMap::new(),
+            node_positions: HashMap::new(),
+        }
+    }
+
+    /// Creates an index containing all of the nodes and edges of a graph.
+    ///
+    /// * `graph`: The graph to index
+    /// * `cell_size`: Width and height of each grid cell
+    pub fn from_graph(graph: &BBGraph, cell_size: f32) -> Self {
+        let mut index = Self::new(cell_size);
+        for (node_idx, node) in graph.nodes.iter() {
+            index.insert_node(*node_idx, node.position());
+        }
+        for (edge_idx, edge) in graph.edges.iter() {
+            let (min, max) = edge.control_bounds(graph);
+            index.insert_edge(*edge_idx, min, max);
+        }
+        index
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    fn cell_of(&self, p: Vec2) -> Cell {
+        (
+            (p.x / self.cell_size).floor() as i32,
+            (p.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    fn cells_in(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = Cell> {
+        let (min_x, min_y) = self.cell_of(min);
+        let (max_x, max_y) = self.cell_of(max);
+        (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+    }
+
+    pub(crate) fn insert_edge(&mut self, edge_idx: BBEdgeIndex, min: Vec2, max: Vec2) {
+        self.remove_edge(edge_idx);
+        for cell in self.cells_in(min, max).collect::<Vec<_>>() {
+            self.edge_cells.entry(cell).or_default().push(edge_idx);
+        }
+        self.edge_bounds.insert(edge_idx, (min, max));
+    }
+
+    pub(crate) fn remove_edge(&mut self, edge_idx: BBEdgeIndex) {
+        let Some((min, max)) = self.edge_bounds.remove(&edge_idx) else {
+            return;
+        };
+        for cell in self.cells_in(min, max).collect::<Vec<_>>() {
+            if let Some(edges) = self.edge_cells.get_mut(&cell) {
+                edges.retain(|idx| *idx != edge_idx);
+                if edges.is_empty() {
+                    self.edge_cells.remove(&cell);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn insert_node(&mut self, node_idx: BBNodeIndex, position: Vec2) {
+        self.remove_node(node_idx);
+        let cell = self.cell_of(position);
+        self.node_cells.entry(cell).or_default().push(node_idx);
+        self.node_positions.insert(node_idx, position);
+    }
+
+    pub(crate) fn remove_node(&mut self, node_idx: BBNodeIndex) {
+        let Some(position) = self.node_positions.remove(&node_idx) else {
+            return;
+        };
+        let cell = self.cell_of(position);
+        if let Some(nodes) = self.node_cells.get_mut(&cell) {
+            nodes.retain(|idx| *idx != node_idx);
+            if nodes.is_empty() {
+                self.node_cells.remove(&cell);
+            }
+        }
+    }
+
+    /// Moves every node and edge in the index by `translation`, re-inserting them from their
+    /// indexed positions and bounds so nothing needs to be read from the graph.
+    ///
+    /// * `translation`: Amount to move by
+    pub(crate) fn translate(&mut self, translation: Vec2) {
+        let edge_bounds = std::mem::take(&mut self.edge_bounds);
+        let node_positions = std::mem::take(&mut self.node_positions);
+        self.edge_cells.clear();
+        self.node_cells.clear();
+        for (edge_idx, (min, max)) in edge_bounds {
+            self.insert_edge(edge_idx, min + translation, max + translation);
+        }
+        for (node_idx, position) in node_positions {
+            self.insert_node(node_idx, position + translation);
+        }
+    }
+
+    /// Returns the edges whose bounding boxes overlap a rect, sorted by index.
+    ///
+    /// * `min`: Minimum corner of the rect
+    /// * `max`: Maximum corner of the rect
+    pub fn edges_in_rect(&self, min: Vec2, max: Vec2) -> Vec<BBEdgeIndex> {
+        let mut result: Vec<_> = self
+            .cells_in(min, max)
+            .filter_map(|cell| self.edge_cells.get(&cell))
+            .flatten()
+            .filter(|edge_idx| {
+                let (e_min, e_max) = self.edge_bounds[*edge_idx];
+                bounds_overlap((e_min, e_max), (min, max))
+            })
+            .copied()
+            .collect();
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    /// Returns the nodes positioned inside of a rect, sorted by index.
+    ///
+    /// * `min`: Minimum corner of the rect
+    /// * `max`: Maximum corner of the rect
+    pub fn nodes_in_rect(&self, min: Vec2, max: Vec2) -> Vec<BBNodeIndex> {
+        let mut result: Vec<_> = self
+            .cells_in(min, max)
+            .filter_map(|cell| self.node_cells.get(&cell))
+            .flatten()
+            .filter(|node_idx| {
+                let p = self.node_positions[*node_idx];
+                p.cmpge(min).all() && p.cmple(max).all()
+            })
+            .copied()
+            .collect();
+        result.sort();
+        result
+    }
+
+    /// Returns every pair of edges whose bounding boxes overlap, sorted with the lower index
+    /// first.  This is the broad phase for intersection detection.
+    pub fn edge_pairs(&self) -> Vec<(BBEdgeIndex, BBEdgeIndex)> {
+        let mut pairs = HashSet::new();
+        for edges in self.edge_cells.values() {
+            for (i, a) in edges.iter().enumerate() {
+                for b in &edges[(i + 1)..] {
+                    let pair = (*a.min(b), *a.max(b));
+                    if pairs.contains(&pair) {
+                        continue;
+                    }
+                    if bounds_overlap(self.edge_bounds[a], self.edge_bounds[b]) {
+                        pairs.insert(pair);
+                    }
+                }
+            }
+        }
+        let mut pairs: Vec<_> = pairs.into_iter().collect();
+        pairs.sort();
+        pairs
+    }
+}
+
+/// The spatial index of a graph and the cell size to build it with.  It's built by the first
+/// spatial query, after that nodes and edges that are edited through [BBGraph::node_mut] or
+/// [BBGraph::edge_mut] are marked as stale and re-indexed by the next spatial query.
+#[derive(Debug)]
+pub(crate) struct BBLazySpatialIndex {
+    cell_size: f32,
+    state: Mutex<BBLazySpatialIndexState>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct BBLazySpatialIndexState {
+    /// `None` until the index is built, or after an edit that moves every node.
+    index: Option<BBSpatialIndex>,
+    stale_nodes: HashSet<BBNodeIndex>,
+    stale_edges: HashSet<BBEdgeIndex>,
+}
+
+impl Clone for BBLazySpatialIndex {
+    fn clone(&self) -> Self {
+        Self {
+            cell_size: self.cell_size,
+            state: Mutex::new(self.state.lock().unwrap().clone()),
+        }
+    }
+}
+
+impl BBLazySpatialIndex {
+    fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            state: Mutex::new(BBLazySpatialIndexState::default()),
+        }
+    }
+
+    /// Builds the index if it hasn't been built, otherwise re-indexes the stale nodes and edges,
+    /// then runs `f` on it.
+    fn with<R>(&self, graph: &BBGraph, f: impl FnOnce(&BBSpatialIndex) -> R) -> R {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let index = state
+            .index
+            .get_or_insert_with(|| BBSpatialIndex::from_graph(graph, self.cell_size));
+
+        for node_idx in state.stale_nodes.drain() {
+            match graph.nodes.get(&node_idx) {
+                Some(node) => index.insert_node(node_idx, node.position()),
+                None => index.remove_node(node_idx),
+            }
+        }
+        for edge_idx in state.stale_edges.drain() {
+            match graph.edges.get(&edge_idx) {
+                Some(edge) => {
+                    let (min, max) = edge.control_bounds(graph);
+                    index.insert_edge(edge_idx, min, max);
+                }
+                None => index.remove_edge(edge_idx),
+            }
+        }
+
+        f(index)
+    }
+
+    /// Returns the index if it's been built, an index that hasn't been built doesn't need
+    /// updating.
+    fn get_mut(&mut self) -> Option<&mut BBSpatialIndex> {
+        self.state.get_mut().unwrap().index.as_mut()
+    }
+
+    /// Marks a node as stale so it's re-indexed before the next spatial query.
+    fn mark_node_stale(&mut self, node_idx: BBNodeIndex) {
+        let state = self.state.get_mut().unwrap();
+        if state.index.is_some() {
+            state.stale_nodes.insert(node_idx);
+        }
+    }
+
+    /// Marks an edge as stale so it's re-indexed before the next spatial query.
+    fn mark_edge_stale(&mut self, edge_idx: BBEdgeIndex) {
+        let state = self.state.get_mut().unwrap();
+        if state.index.is_some() {
+            state.stale_edges.insert(edge_idx);
+        }
+    }
+
+    fn invalidate(&mut self) {
+        *self.state.get_mut().unwrap() = BBLazySpatialIndexState::default();
+    }
+}
+
+fn bounds_overlap(a: (Vec2, Vec2), b: (Vec2, Vec2)) -> bool {
+    a.0.cmple(b.1).all() && b.0.cmple(a.1).all()
+}
+
+impl BBGraph {
+    /// Enables the spatial index, it's kept up to date as edges are added/deleted and the graph
+    /// is transformed.  Nodes and edges edited through [BBGraph::node_mut] or
+    /// [BBGraph::edge_mut] are re-indexed before the next spatial query.  Changes made directly
+    /// to [BBGraph::nodes] or [BBGraph::edges] aren't tracked, call
+    /// [BBGraph::mark_spatial_index_stale] afterwards.
+    ///
+    /// * `cell_size`: Width and height of each grid cell, ideally close to the size of an edge.
+    pub fn enable_spatial_index(&mut self, cell_size: f32) {
+        self.spatial_index = Some(BBLazySpatialIndex::new(cell_size));
+    }
+
+    pub fn disable_spatial_index(&mut self) {
+        self.spatial_index = None;
+    }
+
+    /// Runs `f` on the spatial index, bringing it up to date first.  Returns `None` if the
+    /// spatial index is disabled.
+    ///
+    /// * `f`: Function to run on the index
+    pub fn with_spatial_index<R>(&self, f: impl FnOnce(&BBSpatialIndex) -> R) -> Option<R> {
+        self.spatial_index.as_ref().map(|lazy| lazy.with(self, f))
+    }
+
+    /// Marks the whole spatial index as stale so all nodes and edges are re-indexed by the next
+    /// spatial query, does nothing if the spatial index is disabled.
+    pub fn mark_spatial_index_stale(&mut self) {
+        if let Some(lazy) = &mut self.spatial_index {
+            lazy.invalidate();
+        }
+    }
+
+    /// Returns the spatial index if it's enabled and has been built.
+    fn built_spatial_index_mut(&mut self) -> Option<&mut BBSpatialIndex> {
+        self.spatial_index.as_mut().and_then(|lazy| lazy.get_mut())
+    }
+
+    /// Marks a node and the edges connected to it as stale in the spatial index.
+    pub(crate) fn spatial_mark_node_stale(&mut self, node_idx: BBNodeIndex) {
+        let Some(lazy) = &mut self.spatial_index else {
+            return;
+        };
+        lazy.mark_node_stale(node_idx);
+        if let Some(node) = self.nodes.get(&node_idx) {
+            for edge_idx in node.adjacents() {
+                lazy.mark_edge_stale(*edge_idx);
+            }
+        }
+    }
+
+    pub(crate) fn spatial_mark_edge_stale(&mut self, edge_idx: BBEdgeIndex) {
+        if let Some(lazy) = &mut self.spatial_index {
+            lazy.mark_edge_stale(edge_idx);
+        }
+    }
+
+    pub(crate) fn spatial_translate(&mut self, translation: Vec2) {
+        if let Some(index) = self.built_spatial_index_mut() {
+            index.translate(translation);
+        }
+    }
+
+    pub(crate) fn spatial_insert_node(&mut self, node_idx: BBNodeIndex) {
+        let Ok(position) = self.node(node_idx).map(|node| node.position()) else {
+            return;
+        };
+        if let Some(index) = self.built_spatial_index_mut() {
+            index.insert_node(node_idx, position);
+        }
+    }
+
+    pub(crate) fn spatial_remove_node(&mut self, node_idx: BBNodeIndex) {
+        if let Some(index) = self.built_spatial_index_mut() {
+            index.remove_node(node_idx);
+        }
+    }
+
+    pub(crate) fn spatial_insert_edge(&mut self, edge_idx: BBEdgeIndex) {
+        if self.built_spatial_index_mut().is_none() {
+            return;
+        }
+        let Ok((min, max)) = self.edge(edge_idx).map(|edge| edge.control_bounds(self)) else {
+            return;
+        };
+        if let Some(index) = self.built_spatial_index_mut() {
+            index.insert_edge(edge_idx, min, max);
+        }
+    }
+
+    pub(crate) fn spatial_remove_edge(&mut self, edge_idx: BBEdgeIndex) {
+        if let Some(index) = self.built_spatial_index_mut() {
+            index.remove_edge(edge_idx);
+        }
+    }
+
+    /// Returns the edges with bounding boxes near a point, sorted by index.
+    fn edge_candidates_near_point(&self, p: Vec2, tolerance: f32) -> Vec<BBEdgeIndex> {
+        let min = p - Vec2::splat(tolerance);
+        let max = p + Vec2::splat(tolerance);
+        if let Some(candidates) = self.with_spatial_index(|index| index.edges_in_rect(min, max)) {
+            return candidates;
+        }
+        let mut candidates: Vec<_> = self
+            .edges
+            .iter()
+            .filter(|(_, edge)| bounds_overlap(edge.control_bounds(self), (min, max)))
+            .map(|(edge_idx, _)| *edge_idx)
+            .collect();
+        candidates.sort();
+        candidates
+    }
+
+    /// Returns the edges that pass within `tolerance` of a point, sorted by index.
+    ///
+    /// * `p`: The point to test
+    /// * `tolerance`: The maximum distance from the point
+    pub fn edges_near_point(&self, p: Vec2, tolerance: f32) -> Vec<BBEdgeIndex> {
+        self.edge_candidates_near_point(p, tolerance)
+            .into_iter()
+            .filter(|edge_idx| {
+                let (_, _, distance) = self.edges[edge_idx].closest_point(self, p);
+                distance <= tolerance
+            })
+            .collect()
+    }
+
+    /// Finds the edge closest to a point, ignoring edges further than `max_distance` away.
+    ///
+    /// Returns `(edge_idx, t, point, distance)` where `t` is the position along the edge of
+    /// `point`.  If two edges are equally close the lower index is returned.
+    ///
+    /// * `p`: The point to test
+    /// * `max_distance`: The maximum distance from the point
+    pub fn closest_edge(
+        &self,
+        p: Vec2,
+        max_distance: f32,
+    ) -> Option<(BBEdgeIndex, f32, Vec2, f32)> {
+        self.edge_candidates_near_point(p, max_distance)
+            .into_iter()
+            .map(|edge_idx| {
+                let (t, point, distance) = self.edges[&edge_idx].closest_point(self, p);
+                (edge_idx, t, point, distance)
+            })
+            .filter(|(_, _, _, distance)| *distance <= max_distance)
+            .min_by(|a, b| a.3.total_cmp(&b.3))
+    }
+
+    /// Returns the nodes positioned inside of a rect, sorted by index.
+    ///
+    /// * `min`: Minimum corner of the rect
+    /// * `max`: Maximum corner of the rect
+    pub fn nodes_in_rect(&self, min: Vec2, max: Vec2) -> Vec<BBNodeIndex> {
+        if let Some(nodes) = self.with_spatial_index(|index| index.nodes_in_rect(min, max)) {
+            return nodes;
+        }
+        let mut result: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|(_, node)| {
+                let p = node.position();
+                p.cmpge(min).all() && p.cmple(max).all()
+            })
+            .map(|(node_idx, _)| *node_idx)
+            .collect();
+        result.sort();
+        result
+    }
+
+    /// Returns every pair of edges whose bounding boxes overlap, sorted with the lower index
+    /// first.  If the spatial index is disabled a temporary one is built.
+    pub fn broad_phase_edge_pairs(&self) -> Vec<(BBEdgeIndex, BBEdgeIndex)> {
+        if let Some(pairs) = self.with_spatial_index(|index| index.edge_pairs()) {
+            return pairs;
+        }
+        if self.edges.is_empty() {
+            return vec![];
+        }
+
+        // Size the cells to the average edge so each edge only covers a few cells, but limit how
+        // many cells the whole graph can cover.
+        let mut total_size = 0.;
+        let (mut graph_min, mut graph_max) = (Vec2::MAX, Vec2::MIN);
+        for edge in self.edges.values() {
+            let (min, max) = edge.control_bounds(self);
+            total_size += (max - min).max_element();
+            graph_min = graph_min.min(min);
+            graph_max = graph_max.max(max);
+        }
+        let cell_size = (total_size / self.edges.len() as f32)
+            .max((graph_max - graph_min).max_element() / MAX_TEMPORARY_CELLS);
+        BBSpatialIndex::from_graph(self, cell_size).edge_pairs()
+    }
+}
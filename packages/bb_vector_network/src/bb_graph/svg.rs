@@ -0,0 +1,567 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    f32::consts::{FRAC_PI_2, PI},
+};
+
+use glam::{vec2, Mat2, Vec2};
+
+use crate::prelude::*;
+
+/// Subpaths that end within this distance of where they started are welded closed.
+const WELD_TOLERANCE: f32 = 0.001;
+
+#[derive(Debug, Clone, Copy)]
+enum PathSegment {
+    Line(Vec2),
+    Quadratic(Vec2, Vec2),
+    Cubic(Vec2, Vec2, Vec2),
+}
+
+impl PathSegment {
+    fn end(&self) -> Vec2 {
+        match self {
+            PathSegment::Line(end)
+            | PathSegment::Quadratic(_, end)
+            | PathSegment::Cubic(_, _, end) => *end,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Subpath {
+    start: Vec2,
+    segments: Vec<PathSegment>,
+    closed: bool,
+}
+
+impl Subpath {
+    fn new(start: Vec2) -> Self {
+        Self {
+            start,
+            segments: vec![],
+            closed: false,
+        }
+    }
+}
+
+/// Parses SVG path data into a list of subpaths with absolute coordinates.
+struct PathParser<'a> {
+    source: &'a str,
+    pos: usize,
+    subpaths: Vec<Subpath>,
+    current: Vec2,
+    /// The last control point of the previous command if it was a cubic (for `S`) or quadratic
+    /// (for `T`) curve.
+    prev_cubic_ctrl: Option<Vec2>,
+    prev_quadratic_ctrl: Option<Vec2>,
+}
+
+impl<'a> PathParser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            pos: 0,
+            subpaths: vec![],
+            current: Vec2::ZERO,
+            prev_cubic_ctrl: None,
+            prev_quadratic_ctrl: None,
+        }
+    }
+
+    fn error(&self, pos: usize, reason: &str) -> BBError {
+        let offset = self.source[..pos].chars().count();
+        BBError::InvalidSvgPath(offset, reason.to_string())
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips whitespace and up to one comma between arguments.
+    fn skip_separator(&mut self) {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn number(&mut self) -> BBResult<f32> {
+        self.skip_separator();
+        let start = self.pos;
+        let bytes = self.source.as_bytes();
+        let digits = |pos: &mut usize| {
+            let from = *pos;
+            while bytes.get(*pos).is_some_and(|c| c.is_ascii_digit()) {
+                *pos += 1;
+            }
+            *pos > from
+        };
+
+        let mut pos = self.pos;
+        if matches!(bytes.get(pos), Some(b'-' | b'+')) {
+            pos += 1;
+        }
+        let mut has_digits = digits(&mut pos);
+        if bytes.get(pos) == Some(&b'.') {
+            pos += 1;
+            has_digits |= digits(&mut pos);
+        }
+        if !has_digits {
+            return Err(self.error(start, "expected a number"));
+        }
+        if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+            let mut exponent = pos + 1;
+            if matches!(bytes.get(exponent), Some(b'-' | b'+')) {
+                exponent += 1;
+            }
+            if digits(&mut exponent) {
+                pos = exponent;
+            }
+        }
+
+        self.pos = pos;
+        self.source[start..pos]
+            .parse()
+            .map_err(|_| self.error(start, "expected a number"))
+    }
+
+    fn point(&mut self, relative: bool) -> BBResult<Vec2> {
+        let p = vec2(self.number()?, self.number()?);
+        Ok(if relative { self.current + p } else { p })
+    }
+
+    /// Arc flags are a single `0` or `1` and don't need a separator after them.
+    fn flag(&mut self) -> BBResult<bool> {
+        self.skip_separator();
+        let value = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error(self.pos, "expected an arc flag of 0 or 1")),
+        };
+        self.pos += 1;
+        Ok(value)
+    }
+
+    fn push_segment(&mut self, segment: PathSegment) {
+        // Drawing after a `Z` starts a new subpath at the same point.
+        if self.subpaths.last().is_none_or(|subpath| subpath.closed) {
+            self.subpaths.push(Subpath::new(self.current));
+        }
+        let end = segment.end();
+        let is_empty = matches!(segment, PathSegment::Line(_))
+            && end.distance_squared(self.current) < WELD_TOLERANCE * WELD_TOLERANCE;
+        if !is_empty {
+            self.subpaths.last_mut().unwrap().segments.push(segment);
+        }
+        self.current = end;
+    }
+
+    fn parse(mut self) -> BBResult<Vec<Subpath>> {
+        let mut prev_command: Option<u8> = None;
+
+        loop {
+            self.skip_whitespace();
+            let Some(c) = self.peek() else {
+                break;
+            };
+            let command_pos = self.pos;
+            let command = if c.is_ascii_alphabetic() {
+                self.pos += 1;
+                c
+            } else {
+                match prev_command {
+                    // Pairs following a move are implicit lines.
+                    Some(b'M') => b'L',
+                    Some(b'm') => b'l',
+                    Some(b'Z' | b'z') | None => {
+                        return Err(self.error(command_pos, "expected a command"))
+                    }
+                    Some(command) => command,
+                }
+            };
+            if prev_command.is_none() && !matches!(command, b'M' | b'm') {
+                return Err(self.error(command_pos, "path data must start with a move command"));
+            }
+            let relative = command.is_ascii_lowercase();
+
+            let mut prev_cubic_ctrl = None;
+            let mut prev_quadratic_ctrl = None;
+            match command.to_ascii_uppercase() {
+                b'M' => {
+                    let p = self.point(relative)?;
+                    self.current = p;
+                    self.subpaths.push(Subpath::new(p));
+                }
+                b'Z' => {
+                    if let Some(subpath) = self.subpaths.last_mut() {
+                        let start = subpath.start;
+                        if !subpath.closed {
+                            self.push_segment(PathSegment::Line(start));
+                            self.subpaths.last_mut().unwrap().closed = true;
+                        }
+                        self.current = start;
+                    }
+                }
+                b'L' => {
+                    let p = self.point(relative)?;
+                    self.push_segment(PathSegment::Line(p));
+                }
+                b'H' => {
+                    let x = self.number()?;
+                    let x = if relative { self.current.x + x } else { x };
+                    self.push_segment(PathSegment::Line(vec2(x, self.current.y)));
+                }
+                b'V' => {
+                    let y = self.number()?;
+                    let y = if relative { self.current.y + y } else { y };
+                    self.push_segment(PathSegment::Line(vec2(self.current.x, y)));
+                }
+                b'C' => {
+                    let ctrl1 = self.point(relative)?;
+                    let ctrl2 = self.point(relative)?;
+                    let end = self.point(relative)?;
+                    self.push_segment(PathSegment::Cubic(ctrl1, ctrl2, end));
+                    prev_cubic_ctrl = Some(ctrl2);
+                }
+                b'S' => {
+                    let ctrl1 = self
+                        .prev_cubic_ctrl
+                        .map_or(self.current, |ctrl| 2. * self.current - ctrl);
+                    let ctrl2 = self.point(relative)?;
+                    let end = self.point(relative)?;
+                    self.push_segment(PathSegment::Cubic(ctrl1, ctrl2, end));
+                    prev_cubic_ctrl = Some(ctrl2);
+                }
+                b'Q' => {
+                    let ctrl1 = self.point(relative)?;
+                    let end = self.point(relative)?;
+                    self.push_segment(PathSegment::Quadratic(ctrl1, end));
+                    prev_quadratic_ctrl = Some(ctrl1);
+                }
+                b'T' => {
+                    let ctrl1 = self
+                        .prev_quadratic_ctrl
+                        .map_or(self.current, |ctrl| 2. * self.current - ctrl);
+                    let end = self.point(relative)?;
+                    self.push_segment(PathSegment::Quadratic(ctrl1, end));
+                    prev_quadratic_ctrl = Some(ctrl1);
+                }
+                b'A' => {
+                    let radii = vec2(self.number()?, self.number()?);
+                    let x_axis_rotation = self.number()?;
+                    let large_arc = self.flag()?;
+                    let sweep = self.flag()?;
+                    let end = self.point(relative)?;
+                    for segment in
+                        arc_to_cubics(self.current, radii, x_axis_rotation, large_arc, sweep, end)
+                    {
+                        self.push_segment(segment);
+                    }
+                }
+                _ => return Err(self.error(command_pos, "unknown command")),
+            }
+            self.prev_cubic_ctrl = prev_cubic_ctrl;
+            self.prev_quadratic_ctrl = prev_quadratic_ctrl;
+            prev_command = Some(command);
+        }
+
+        Ok(self.subpaths)
+    }
+}
+
+/// Signed angle from `u` to `v`.
+fn angle_between(u: Vec2, v: Vec2) -> f32 {
+    u.perp_dot(v).atan2(u.dot(v))
+}
+
+/// Converts an SVG elliptical arc to cubic curves of at most 90 degrees each.  Follows the
+/// endpoint to center conversion from the SVG spec (appendix B.2.4).
+fn arc_to_cubics(
+    from: Vec2,
+    radii: Vec2,
+    x_axis_rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Vec2,
+) -> Vec<PathSegment> {
+    if from.distance_squared(to) < f32::EPSILON {
+        return vec![];
+    }
+    let mut radii = radii.abs();
+    if radii.x < f32::EPSILON || radii.y < f32::EPSILON {
+        return vec![PathSegment::Line(to)];
+    }
+
+    let rotation = Mat2::from_angle(x_axis_rotation.to_radians());
+    let p = rotation.transpose() * ((from - to) / 2.);
+
+    // Scale up radii that are too small to reach between the points.
+    let lambda = (p / radii).length_squared();
+    if lambda > 1. {
+        radii *= lambda.sqrt();
+    }
+
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let (px2, py2) = (p.x * p.x, p.y * p.y);
+    let numerator = (rx2 * ry2 - rx2 * py2 - ry2 * px2).max(0.);
+    let sign = if large_arc == sweep { -1. } else { 1. };
+    let coefficient = sign * (numerator / (rx2 * py2 + ry2 * px2)).sqrt();
+    let center_prime = coefficient * vec2(radii.x * p.y / radii.y, -radii.y * p.x / radii.x);
+    let center = rotation * center_prime + (from + to) / 2.;
+
+    let start_vector = (p - center_prime) / radii;
+    let end_vector = (-p - center_prime) / radii;
+    let start_angle = angle_between(Vec2::X, start_vector);
+    let mut sweep_angle = angle_between(start_vector, end_vector);
+    if !sweep && sweep_angle > 0. {
+        sweep_angle -= 2. * PI;
+    } else if sweep && sweep_angle < 0. {
+        sweep_angle += 2. * PI;
+    }
+
+    let count = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.) as usize;
+    let delta = sweep_angle / count as f32;
+    let k = 4. / 3. * (delta / 4.).tan();
+    let to_world = |v: Vec2| center + rotation * (v * radii);
+
+    (0..count)
+        .map(|i| {
+            let a0 = start_angle + delta * i as f32;
+            let a1 = a0 + delta;
+            let (p0, p1) = (Vec2::from_angle(a0), Vec2::from_angle(a1));
+            let ctrl1 = to_world(p0 + k * p0.perp());
+            let ctrl2 = to_world(p1 - k * p1.perp());
+            let end = if i + 1 == count { to } else { to_world(p1) };
+            PathSegment::Cubic(ctrl1, ctrl2, end)
+        })
+        .collect()
+}
+
+impl BBGraph {
+    /// Creates a new graph from SVG path data, see [BBGraph::add_svg_path].
+    ///
+    /// * `d`: The `d` attribute of an SVG `<path>` element
+    pub fn try_new_from_svg_path(d: &str) -> BBResult<Self> {
+        let mut graph = BBGraph::new();
+        graph.add_svg_path(d)?;
+        Ok(graph)
+    }
+
+    /// Parses SVG path data and adds its edges to the graph.  Subpaths that are closed, or end
+    /// where they started, are welded into a cycle.  Arcs are converted to cubic curves.
+    ///
+    /// Returns the indices of the added edges.  Nothing is added if the path data is invalid.
+    ///
+    /// * `d`: The `d` attribute of an SVG `<path>` element
+    pub fn add_svg_path(&mut self, d: &str) -> BBResult<Vec<BBEdgeIndex>> {
+        let subpaths = PathParser::new(d).parse()?;
+
+        let mut edges = vec![];
+        for subpath in subpaths {
+            let Some(last) = subpath.segments.last() else {
+                continue;
+            };
+            let welded = subpath.closed
+                || last.end().distance_squared(subpath.start) < WELD_TOLERANCE * WELD_TOLERANCE;
+
+            let start_idx = self.add_node(subpath.start);
+            let mut prev_idx = start_idx;
+            let count = subpath.segments.len();
+            for (i, segment) in subpath.segments.into_iter().enumerate() {
+                let end_idx = if welded && i + 1 == count {
+                    start_idx
+                } else {
+                    self.add_node(segment.end())
+                };
+                let (edge_idx, _) = match segment {
+                    PathSegment::Line(_) => self.line_from_to(prev_idx, end_idx),
+                    PathSegment::Quadratic(ctrl1, _) => {
+                        self.quadratic_from_to(prev_idx, ctrl1, end_idx)
+                    }
+                    PathSegment::Cubic(ctrl1, ctrl2, _) => {
+                        self.cubic_from_to(prev_idx, ctrl1, ctrl2, end_idx)
+                    }
+                };
+                edges.push(edge_idx);
+                prev_idx = end_idx;
+            }
+        }
+
+        Ok(edges)
+    }
+}
+
+/// Formats a number for SVG output, rounded to 3 decimal places so output is stable.
+fn fmt_number(v: f32) -> String {
+    let rounded = (v * 1000.).round() / 1000.;
+    if rounded == 0. {
+        // Avoids printing `-0`.
+        return "0".to_string();
+    }
+    format!("{rounded}")
+}
+
+fn fmt_point(p: Vec2) -> String {
+    format!("{} {}", fmt_number(p.x), fmt_number(p.y))
+}
+
+impl BBGraph {
+    /// Converts a continuous chain of edges to SVG path data.  The path is closed with `Z` if the
+    /// chain ends where it starts.
+    ///
+    /// * `edges`: A continuous chain of edges, such as the edges of a [BBCycle]
+    pub fn svg_path_data(&self, edges: &[BBEdgeIndex]) -> BBResult<String> {
+        let directed = self.edges_directed(edges)?;
+        let (_, first) = directed.first().unwrap();
+        let (_, last) = directed.last().unwrap();
+
+        let mut d = format!("M{}", fmt_point(first.start_pos(self)));
+        for (_, edge) in &directed {
+            let end = fmt_point(edge.end_pos(self));
+            let segment = match edge {
+                BBEdge::Line { .. } => format!(" L{end}"),
+                BBEdge::Quadratic { ctrl1, .. } => format!(" Q{} {end}", fmt_point(*ctrl1)),
+                BBEdge::Cubic { ctrl1, ctrl2, .. } => {
+                    format!(" C{} {} {end}", fmt_point(*ctrl1), fmt_point(*ctrl2))
+                }
+            };
+            d.push_str(&segment);
+        }
+        if first.start_idx() == last.end_idx() {
+            d.push_str(" Z");
+        }
+        Ok(d)
+    }
+
+    /// Splits edges into continuous chains, chains run between dead ends or branching nodes.
+    /// Whatever remains are closed loops.
+    pub(crate) fn stroke_chains(
+        &self,
+        edges: &BTreeSet<BBEdgeIndex>,
+    ) -> BBResult<Vec<Vec<BBEdgeIndex>>> {
+        let mut node_edges: HashMap<BBNodeIndex, Vec<BBEdgeIndex>> = HashMap::new();
+        for edge_idx in edges {
+            let edge = self.edge(*edge_idx)?;
+            node_edges
+                .entry(edge.start_idx())
+                .or_default()
+                .push(*edge_idx);
+            node_edges
+                .entry(edge.end_idx())
+                .or_default()
+                .push(*edge_idx);
+        }
+        let is_chain_end = |node_idx: BBNodeIndex| node_edges[&node_idx].len() != 2;
+
+        let mut unvisited = edges.clone();
+        let mut chains = vec![];
+        for open_chains in [true, false] {
+            for edge_idx in edges {
+                if !unvisited.contains(edge_idx) {
+                    continue;
+                }
+                let edge = self.edge(*edge_idx)?;
+                let start_idx = if !open_chains || is_chain_end(edge.start_idx()) {
+                    edge.start_idx()
+                } else if is_chain_end(edge.end_idx()) {
+                    edge.end_idx()
+                } else {
+                    continue;
+                };
+
+                let mut chain = vec![];
+                let mut next = Some(*edge_idx);
+                let mut node_idx = start_idx;
+                while let Some(edge_idx) = next {
+                    unvisited.remove(&edge_idx);
+                    chain.push(edge_idx);
+                    node_idx = self.edge(edge_idx)?.other_node_idx(node_idx);
+                    if node_idx == start_idx || is_chain_end(node_idx) {
+                        break;
+                    }
+                    next = node_edges[&node_idx]
+                        .iter()
+                        .find(|edge_idx| unvisited.contains(edge_idx))
+                        .copied();
+                }
+                chains.push(chain);
+            }
+        }
+
+        Ok(chains)
+    }
+
+    /// Serializes the graph to an SVG document.  Each region becomes a filled `<path>` using the
+    /// region's winding rule, with holes for the regions nested inside of it (see
+    /// [BBGraph::region_fill_cycles]).  The remaining edges that aren't part of any region become
+    /// a single stroked `<path>`.  Regions without any filled faces are skipped.
+    pub fn to_svg(&self) -> BBResult<String> {
+        let mut region_indices: Vec<_> = self.regions.keys().copied().collect();
+        region_indices.sort();
+
+        let mut region_cycles: HashMap<BBRegionIndex, Vec<Vec<BBEdgeIndex>>> = HashMap::new();
+        for (region_idx, edges) in self.owned_fill_cycles()? {
+            region_cycles.entry(region_idx).or_default().push(edges);
+        }
+
+        let mut paths = vec![];
+        let mut region_edges = HashSet::new();
+        for region_idx in region_indices {
+            let region = self.region(region_idx)?;
+            region_edges.extend(region.root_cycle.edges_deep().into_iter().flatten());
+
+            let Some(cycles) = region_cycles.get_mut(&region_idx) else {
+                continue;
+            };
+            // Keeps the output stable, the order of the faces isn't.
+            cycles.sort_by_key(|edges| edges.iter().min().copied());
+            let d = cycles
+                .iter()
+                .map(|edges| self.svg_path_data(edges))
+                .collect::<BBResult<Vec<_>>>()?
+                .join(" ");
+            let fill_rule = match region.winding_rule() {
+                BBWindingRule::Default => "evenodd",
+                BBWindingRule::NonZero => "nonzero",
+            };
+            paths.push(format!(r#"<path d="{d}" fill-rule="{fill_rule}"/>"#));
+        }
+
+        let stroke_edges: BTreeSet<_> = self
+            .edges
+            .keys()
+            .filter(|edge_idx| !region_edges.contains(*edge_idx))
+            .copied()
+            .collect();
+        if !stroke_edges.is_empty() {
+            let d = self
+                .stroke_chains(&stroke_edges)?
+                .iter()
+                .map(|edges| self.svg_path_data(edges))
+                .collect::<BBResult<Vec<_>>>()?
+                .join(" ");
+            paths.push(format!(r#"<path d="{d}" fill="none" stroke="black"/>"#));
+        }
+
+        let (min, max) = self.bounds().unwrap_or_default();
+        let size = max - min;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {}\">\n",
+            fmt_point(min),
+            fmt_point(size)
+        );
+        for path in paths {
+            svg.push_str(&format!("  {path}\n"));
+        }
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+}
@@ -0,0 +1,219 @@
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// A broken invariant found by [BBGraph::validate].
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum BBGraphDefect {
+    #[error("Edge {edge_idx} references missing node {node_idx}.")]
+    MissingNode {
+        edge_idx: BBEdgeIndex,
+        node_idx: BBNodeIndex,
+    },
+    #[error("Node {node_idx} lists missing edge {edge_idx} as adjacent.")]
+    MissingAdjacentEdge {
+        node_idx: BBNodeIndex,
+        edge_idx: BBEdgeIndex,
+    },
+    #[error(
+        "Node {node_idx} lists edge {edge_idx} as adjacent {found} times, expected {expected}."
+    )]
+    AdjacentMismatch {
+        node_idx: BBNodeIndex,
+        edge_idx: BBEdgeIndex,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Edge {0} has zero length.")]
+    ZeroLengthEdge(BBEdgeIndex),
+    #[error("Region {region_idx} references missing edge {edge_idx}.")]
+    MissingRegionEdge {
+        region_idx: BBRegionIndex,
+        edge_idx: BBEdgeIndex,
+    },
+    #[error("Region {region_idx} has a cycle that isn't closed, edges: {edges:?}.")]
+    OpenCycle {
+        region_idx: BBRegionIndex,
+        edges: Vec<BBEdgeIndex>,
+    },
+}
+
+impl BBGraph {
+    /// Checks the invariants that the rest of the graph API relies on, returning every violation
+    /// found.  These are that:
+    ///
+    /// - Every edge's nodes exist, and list the edge in their adjacents (twice for loops).
+    /// - Every node's adjacents exist and reference the node.
+    /// - No edge has zero length.
+    /// - Every region cycle's edges exist and form a closed walk.
+    ///
+    /// Regions aren't updated automatically, so they are only expected to be valid straight
+    /// after [BBGraph::update_regions].
+    pub fn validate(&self) -> Result<(), Vec<BBGraphDefect>> {
+        let mut defects = self.structure_defects();
+        defects.extend(self.zero_length_defects());
+        defects.extend(self.region_defects());
+        match defects.is_empty() {
+            true => Ok(()),
+            false => Err(defects),
+        }
+    }
+
+    /// Finds defects in the nodes and edges of the graph.
+    fn structure_defects(&self) -> Vec<BBGraphDefect> {
+        let mut defects = vec![];
+
+        let mut edge_indices: Vec<_> = self.edges.keys().copied().collect();
+        edge_indices.sort();
+        for edge_idx in edge_indices {
+            let edge = self.edges[&edge_idx];
+            let mut node_indices = vec![edge.start_idx(), edge.end_idx()];
+            node_indices.dedup();
+
+            for node_idx in node_indices {
+                let Some(node) = self.nodes.get(&node_idx) else {
+                    defects.push(BBGraphDefect::MissingNode { edge_idx, node_idx });
+                    continue;
+                };
+                let expected =
+                    (edge.start_idx() == node_idx) as usize + (edge.end_idx() == node_idx) as usize;
+                let found = node.adjacents().iter().filter(|e| **e == edge_idx).count();
+                if expected != found {
+                    defects.push(BBGraphDefect::AdjacentMismatch {
+                        node_idx,
+                        edge_idx,
+                        expected,
+                        found,
+                    });
+                }
+            }
+        }
+
+        let mut node_indices: Vec<_> = self.nodes.keys().copied().collect();
+        node_indices.sort();
+        for node_idx in node_indices {
+            let mut adjacents = self.nodes[&node_idx].adjacents().to_vec();
+            adjacents.sort();
+            adjacents.dedup();
+            for edge_idx in adjacents {
+                match self.edges.get(&edge_idx) {
+                    None => defects.push(BBGraphDefect::MissingAdjacentEdge { node_idx, edge_idx }),
+                    // Edges that do reference the node were checked above.
+                    Some(edge) if !edge.contains_node_idx(node_idx) => {
+                        defects.push(BBGraphDefect::AdjacentMismatch {
+                            node_idx,
+                            edge_idx,
+                            expected: 0,
+                            found: self.nodes[&node_idx]
+                                .adjacents()
+                                .iter()
+                                .filter(|e| **e == edge_idx)
+                                .count(),
+                        });
+                    }
+                    Some(_) => (),
+                }
+            }
+        }
+
+        defects
+    }
+
+    /// Finds edges with zero length.  These can be built through the public API, so unlike the
+    /// other defects they aren't checked by [BBGraph::debug_validate].
+    fn zero_length_defects(&self) -> Vec<BBGraphDefect> {
+        let mut edge_indices: Vec<_> = self
+            .edges
+            .iter()
+            .filter(|(_, edge)| {
+                self.nodes.contains_key(&edge.start_idx())
+                    && self.nodes.contains_key(&edge.end_idx())
+            })
+            .map(|(edge_idx, _)| *edge_idx)
+            .collect();
+        edge_indices.sort();
+        edge_indices
+            .into_iter()
+            .filter(|edge_idx| {
+                let [start, ctrl1, ctrl2, end] = self.edges[edge_idx].cubic_points(self);
+                [ctrl1, ctrl2, end]
+                    .iter()
+                    .all(|p| p.distance(start) < f32::EPSILON)
+            })
+            .map(BBGraphDefect::ZeroLengthEdge)
+            .collect()
+    }
+
+    /// Finds defects in the cycles of the regions.
+    fn region_defects(&self) -> Vec<BBGraphDefect> {
+        let mut defects = vec![];
+
+        let mut region_indices: Vec<_> = self.regions.keys().copied().collect();
+        region_indices.sort();
+        for region_idx in region_indices {
+            for edges in self.regions[&region_idx].root_cycle.edges_deep() {
+                let missing: Vec<_> = edges
+                    .iter()
+                    .filter(|edge_idx| !self.edges.contains_key(edge_idx))
+                    .collect();
+                for edge_idx in &missing {
+                    defects.push(BBGraphDefect::MissingRegionEdge {
+                        region_idx,
+                        edge_idx: **edge_idx,
+                    });
+                }
+                if missing.is_empty() && !self.is_closed_walk(&edges) {
+                    defects.push(BBGraphDefect::OpenCycle { region_idx, edges });
+                }
+            }
+        }
+
+        defects
+    }
+
+    /// Returns true if the edges, in order, form a walk that ends where it starts.  All of the
+    /// edges must exist.
+    fn is_closed_walk(&self, edges: &[BBEdgeIndex]) -> bool {
+        let Some(first_idx) = edges.first() else {
+            return false;
+        };
+        let first = self.edges[first_idx];
+
+        // The direction of the first edge depends on the second, try both.
+        [first.start_idx(), first.end_idx()]
+            .into_iter()
+            .any(|start_idx| {
+                let mut node_idx = start_idx;
+                for edge_idx in edges {
+                    let edge = self.edges[edge_idx];
+                    node_idx = match node_idx {
+                        idx if idx == edge.start_idx() => edge.end_idx(),
+                        idx if idx == edge.end_idx() => edge.start_idx(),
+                        _ => return false,
+                    };
+                }
+                node_idx == start_idx
+            })
+    }
+
+    /// Panics with every defect in the graph, other than zero length edges, when built with the
+    /// `debug_validate` feature and debug assertions.  Called at the end of public operations
+    /// that should leave the graph valid.
+    ///
+    /// * `check_regions`: Whether the regions should be up to date
+    pub(crate) fn debug_validate(&self, check_regions: bool) {
+        #[cfg(all(debug_assertions, feature = "debug_validate"))]
+        {
+            let mut defects = self.structure_defects();
+            if check_regions {
+                defects.extend(self.region_defects());
+            }
+            if !defects.is_empty() {
+                let defects: Vec<_> = defects.iter().map(|d| d.to_string()).collect();
+                panic!("BBGraph is invalid:\n{}", defects.join("\n"));
+            }
+        }
+        #[cfg(not(all(debug_assertions, feature = "debug_validate")))]
+        let _ = check_regions;
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::{vec2, Vec2};
+
+use crate::{bb_edge::DEFAULT_FLATTEN_TOLERANCE, prelude::*};
+
+/// Calculates how many times a list of directed segments winds around a point, counterclockwise
+/// windings are positive.
+pub(crate) fn winding_number(segments: impl IntoIterator<Item = (Vec2, Vec2)>, p: Vec2) -> i32 {
+    let mut winding = 0;
+    for (a, b) in segments {
+        let side = (b - a).determinate(p - a);
+        if a.y <= p.y {
+            if b.y > p.y && side > 0. {
+                winding += 1;
+            }
+        } else if b.y <= p.y && side < 0. {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Iterates over the segments of a closed polygon.
+pub(crate) fn polygon_segments(polygon: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/// Returns true if the point is inside of the closed polygon.
+pub(crate) fn polygon_contains(polygon: &[Vec2], p: Vec2) -> bool {
+    winding_number(polygon_segments(polygon), p) != 0
+}
+
+/// Calculates the signed area of a closed polygon, counterclockwise polygons are positive.
+pub(crate) fn polygon_signed_area(polygon: &[Vec2]) -> f32 {
+    polygon_segments(polygon)
+        .map(|(a, b)| a.determinate(b))
+        .sum::<f32>()
+        / 2.
+}
+
+/// A face of a region, used to work out how it should be filled.
+struct FillFace {
+    region_idx: BBRegionIndex,
+    edges: Vec<BBEdgeIndex>,
+    filled: bool,
+    polygon: Vec<Vec2>,
+    area: f32,
+    interior: Option<Vec2>,
+}
+
+impl BBGraph {
+    /// Sets the winding rule of a region and recalculates which of its cycles are filled.
+    ///
+    /// * `index`: ID of the region to update
+    /// * `winding_rule`: The new winding rule
+    pub fn set_region_winding_rule(
+        &mut self,
+        index: BBRegionIndex,
+        winding_rule: BBWindingRule,
+    ) -> BBResult<()> {
+        self.regions
+            .get_mut(&index)
+            .ok_or(BBError::MissingRegion(index))?
+            .set_winding_rule(winding_rule);
+        self.update_region_fills()
+    }
+
+    /// Flattens a cycle into a closed polygon that follows the continuous direction of the edges.
+    /// Curves are flattened with [DEFAULT_FLATTEN_TOLERANCE], the same as
+    /// [BBGraph::region_at_point].
+    ///
+    /// * `edges`: The edges of the cycle
+    pub(crate) fn cycle_polygon(&self, edges: &[BBEdgeIndex]) -> BBResult<Vec<Vec2>> {
+        let mut polygon = vec![];
+        for (_, edge) in self.edges_directed(edges)? {
+            let mut points = edge.flatten(self, DEFAULT_FLATTEN_TOLERANCE);
+            // The end point is the start of the next edge.
+            points.pop();
+            polygon.extend(points.into_iter().map(|(_, p)| p));
+        }
+        Ok(polygon)
+    }
+
+    /// Returns a point just to the left and just to the right of the middle of an edge.
+    ///
+    /// * `edge`: The edge to sample beside
+    /// * `offset`: How far from the edge the samples are
+    pub(crate) fn edge_side_samples(&self, edge: &BBEdge, offset: f32) -> (Vec2, Vec2) {
+        let middle = edge.t_point(self, 0.5);
+        let mut tangent = edge.t_point(self, 0.51) - edge.t_point(self, 0.49);
+        if tangent.length_squared() < f32::EPSILON {
+            tangent = edge.end_pos(self) - edge.start_pos(self);
+        }
+        let normal = vec2(-tangent.y, tangent.x).normalize_or_zero() * offset;
+
+        (middle + normal, middle - normal)
+    }
+
+    /// Finds a point inside of a cycle, just beside one of the segments of its flattened
+    /// polygon.  Returns None if the cycle doesn't enclose any area.
+    ///
+    /// * `edges`: The edges of the cycle
+    pub(crate) fn cycle_interior_point(&self, edges: &[BBEdgeIndex]) -> BBResult<Option<Vec2>> {
+        let polygon = self.cycle_polygon(edges)?;
+
+        for (a, b) in polygon_segments(&polygon) {
+            let middle = a.lerp(b, 0.5);
+            let normal = vec2(a.y - b.y, b.x - a.x) * 0.001;
+            if normal.length_squared() < f32::EPSILON * f32::EPSILON {
+                continue;
+            }
+            for p in [middle + normal, middle - normal] {
+                if polygon_contains(&polygon, p) {
+                    return Ok(Some(p));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the flattened, directed segments of every edge that bounds a face.  Edges that a
+    /// face walks along twice (bridges) don't enclose anything so they are skipped.
+    fn face_winding_segments(&self) -> BBResult<Vec<(Vec2, Vec2)>> {
+        let mut bounding_edges = HashSet::new();
+        for region in self.regions.values() {
+            for face in region.root_cycle.faces() {
+                let mut uses: HashMap<BBEdgeIndex, usize> = HashMap::new();
+                for edge_idx in &face.edges {
+                    *uses.entry(*edge_idx).or_default() += 1;
+                }
+                bounding_edges.extend(
+                    uses.into_iter()
+                        .filter(|(_, count)| *count == 1)
+                        .map(|(edge_idx, _)| edge_idx),
+                );
+            }
+        }
+
+        let mut segments = vec![];
+        for edge_idx in bounding_edges {
+            let points = self
+                .edge(edge_idx)?
+                .flatten(self, DEFAULT_FLATTEN_TOLERANCE);
+            segments.extend(points.windows(2).map(|w| (w[0].1, w[1].1)));
+        }
+        Ok(segments)
+    }
+
+    /// Recalculates which cycles of each region are filled using the region's winding rule.
+    /// The winding number of a face is counted against the direction of every edge in the graph
+    /// that bounds a face, so faces nested inside of other regions are counted too.
+    pub fn update_region_fills(&mut self) -> BBResult<()> {
+        let segments = self.face_winding_segments()?;
+
+        let mut region_indices: Vec<_> = self.regions.keys().copied().collect();
+        region_indices.sort();
+
+        for region_idx in region_indices {
+            let region = self.region(region_idx)?;
+            let winding_rule = region.winding_rule();
+
+            let fills = region
+                .root_cycle
+                .faces()
+                .iter()
+                .map(|face| {
+                    let Some(p) = self.cycle_interior_point(&face.edges)? else {
+                        return Ok(false);
+                    };
+                    let winding = winding_number(segments.iter().copied(), p);
+                    Ok(winding_rule.is_filled(winding))
+                })
+                .collect::<BBResult<Vec<bool>>>()?;
+
+            let root_cycle = &mut self.regions.get_mut(&region_idx).unwrap().root_cycle;
+            let mut fills_iter = fills.iter();
+            root_cycle.for_each_face_mut(|face| face.filled = *fills_iter.next().unwrap());
+            if !root_cycle.children.is_empty() {
+                root_cycle.filled = fills.iter().any(|filled| *filled);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the edges of each face that needs to be drawn to fill the graph, ordered so the
+    /// faces are wound counterclockwise and holes are wound clockwise.  Traversing these with
+    /// [BBGraph::edges_directed] gives a path that fills correctly with either the even-odd or
+    /// the non-zero fill rule.
+    pub fn fill_cycles(&self) -> BBResult<Vec<Vec<BBEdgeIndex>>> {
+        Ok(self
+            .owned_fill_cycles()?
+            .into_iter()
+            .map(|(_, edges)| edges)
+            .collect())
+    }
+
+    /// Returns the edges that need to be drawn to fill a single region, see
+    /// [BBGraph::fill_cycles].  This includes holes cut by unfilled faces of other regions that
+    /// are nested inside of this region.
+    ///
+    /// * `region_idx`: The region to fill
+    pub fn region_fill_cycles(
+        &self,
+        region_idx: BBRegionIndex,
+    ) -> BBResult<Vec<Vec<BBEdgeIndex>>> {
+        self.region(region_idx)?;
+        Ok(self
+            .owned_fill_cycles()?
+            .into_iter()
+            .filter(|(owner_idx, _)| *owner_idx == region_idx)
+            .map(|(_, edges)| edges)
+            .collect())
+    }
+
+    /// Calculates the fill cycles along with the region each belongs to.  Filled faces belong to
+    /// their own region, holes belong to the region of the face they cut out of.
+    pub(crate) fn owned_fill_cycles(&self) -> BBResult<Vec<(BBRegionIndex, Vec<BBEdgeIndex>)>> {
+        let mut region_indices: Vec<_> = self.regions.keys().copied().collect();
+        region_indices.sort();
+
+        let mut faces = vec![];
+        for region_idx in region_indices {
+            for face in self.region(region_idx)?.root_cycle.faces() {
+                let polygon = self.cycle_polygon(&face.edges)?;
+                let area = polygon_signed_area(&polygon);
+                faces.push(FillFace {
+                    region_idx,
+                    edges: face.edges.clone(),
+                    filled: face.filled,
+                    interior: self.cycle_interior_point(&face.edges)?,
+                    polygon,
+                    area,
+                });
+            }
+        }
+
+        let mut result = vec![];
+        for (i, face) in faces.iter().enumerate() {
+            let Some(interior) = face.interior else {
+                continue;
+            };
+
+            // The smallest face that contains this face.
+            let parent = faces
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i
+                        && other.area.abs() > face.area.abs()
+                        && polygon_contains(&other.polygon, interior)
+                })
+                .min_by(|(_, a), (_, b)| a.area.abs().total_cmp(&b.area.abs()))
+                .map(|(_, other)| other);
+
+            // Each face only needs to cancel out or add to the fill of the face it's inside.
+            let parent_filled = parent.is_some_and(|parent| parent.filled);
+            let weight = face.filled as i32 - parent_filled as i32;
+            if weight == 0 {
+                continue;
+            }
+
+            let mut edges = face.edges.clone();
+            if (face.area > 0.) != (weight > 0) {
+                edges.reverse();
+            }
+            let owner_idx = match parent {
+                Some(parent) if weight < 0 => parent.region_idx,
+                _ => face.region_idx,
+            };
+            result.push((owner_idx, edges));
+        }
+
+        Ok(result)
+    }
+}
@@ -6,39 +6,28 @@ use super::bb_edge::BBEdgeIndex;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 #[cfg_attr( feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-// Represents a reference to an anchor node in the BBVectorNetwork
-pub struct BBNodeIndex(pub usize);
-impl From<usize> for BBNodeIndex {
-    fn from(value: usize) -> Self {
-        Self(value)
-    }
+// Represents a reference to an anchor node in the BBVectorNetwork.  Indices are generational, a
+// deleted node's index will never point to a node that later re-uses its slot.
+pub struct BBNodeIndex {
+    slot: usize,
+    generation: u32,
 }
-impl From<BBNodeIndex> for usize {
-    fn from(value: BBNodeIndex) -> Self {
-        value.0
+impl BBNodeIndex {
+    pub fn new(slot: usize, generation: u32) -> Self {
+        Self { slot, generation }
     }
-}
-
-impl From<&mut BBNodeIndex> for usize {
-    fn from(value: &mut BBNodeIndex) -> Self {
-        value.0
+    /// The storage slot of this index, slots are re-used after they're deleted.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+    /// How many times the slot has been re-used, an index from an older generation is stale.
+    pub fn generation(&self) -> u32 {
+        self.generation
     }
 }
 impl Display for BBNodeIndex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "n#{}", self.0)
-    }
-}
-
-// OPS
-impl std::ops::AddAssign<usize> for BBNodeIndex {
-    fn add_assign(&mut self, rhs: usize) {
-        self.0 += rhs;
-    }
-}
-impl std::ops::SubAssign<usize> for BBNodeIndex {
-    fn sub_assign(&mut self, rhs: usize) {
-        self.0 -= rhs;
+        write!(f, "n#{}v{}", self.slot, self.generation)
     }
 }
 
@@ -5,28 +5,29 @@ use flo_curves::{bezier::Curve, Coord2};
 use crate::prelude::*;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-/// Represents an index position of a BBVNRegion, which are joins between two nodes.
-pub struct BBRegionIndex(pub usize);
-impl From<usize> for BBRegionIndex {
-    fn from(value: usize) -> Self {
-        Self(value)
-    }
+#[cfg_attr( feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Represents a reference to a region in the BBVectorNetwork.  Indices are generational, a stale
+/// region index will never point to a region that later re-uses its slot.
+pub struct BBRegionIndex {
+    slot: usize,
+    generation: u32,
 }
-impl From<BBRegionIndex> for usize {
-    fn from(value: BBRegionIndex) -> Self {
-        value.0
+impl BBRegionIndex {
+    pub fn new(slot: usize, generation: u32) -> Self {
+        Self { slot, generation }
     }
-}
-
-impl From<&mut BBRegionIndex> for usize {
-    fn from(value: &mut BBRegionIndex) -> Self {
-        value.0
+    /// The storage slot of this index, slots are re-used after they're deleted.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+    /// How many times the slot has been re-used, an index from an older generation is stale.
+    pub fn generation(&self) -> u32 {
+        self.generation
     }
 }
 impl Display for BBRegionIndex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "e#{}", self.0)
+        write!(f, "r#{}v{}", self.slot, self.generation)
     }
 }
 
@@ -62,18 +63,74 @@ impl BBCycle {
         self.edges_deep_inner(&mut edges);
         edges
     }
+
+    /// Reverses the direction of this cycle and all of its nested cycles.
+    pub(crate) fn reverse_deep(&mut self) {
+        self.edges.reverse();
+        for c in &mut self.children {
+            c.reverse_deep();
+        }
+    }
+
+    fn faces_inner<'a>(&'a self, faces_out: &mut Vec<&'a BBCycle>) {
+        for c in &self.children {
+            faces_out.push(c);
+            c.faces_inner(faces_out);
+        }
+    }
+
+    /// Returns the cycles that enclose the individual faces of this cycle, this is all of the
+    /// nested cycles or self if there are none.
+    pub fn faces(&self) -> Vec<&BBCycle> {
+        if self.children.is_empty() {
+            return vec![self];
+        }
+        let mut faces = vec![];
+        self.faces_inner(&mut faces);
+        faces
+    }
+
+    fn for_each_face_mut_inner(&mut self, f: &mut impl FnMut(&mut BBCycle)) {
+        for c in &mut self.children {
+            f(c);
+            c.for_each_face_mut_inner(f);
+        }
+    }
+
+    /// Calls `f` with each of the cycles returned by [BBCycle::faces], in the same order.
+    pub(crate) fn for_each_face_mut(&mut self, mut f: impl FnMut(&mut BBCycle)) {
+        if self.children.is_empty() {
+            f(self);
+        } else {
+            self.for_each_face_mut_inner(&mut f);
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr( feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Decides which cycles of a region are filled, based on how many times the region's edges wind
+/// around them.
 pub enum BBWindingRule {
+    /// Even-odd, a cycle is filled if it is wound around an odd number of times.
+    #[default]
     Default,
+    /// A cycle is filled if it is wound around at all, uses the direction of the edges.
     NonZero,
 }
 
+impl BBWindingRule {
+    /// Returns true if a cycle with the given winding number should be filled.
+    pub fn is_filled(&self, winding: i32) -> bool {
+        match self {
+            BBWindingRule::Default => winding % 2 != 0,
+            BBWindingRule::NonZero => winding != 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[allow(dead_code)]
 pub struct BBRegion {
     winding_rule: BBWindingRule,
     pub root_cycle: BBCycle,
@@ -86,4 +143,14 @@ impl BBRegion {
             root_cycle,
         }
     }
+
+    pub fn winding_rule(&self) -> BBWindingRule {
+        self.winding_rule
+    }
+
+    /// Sets the winding rule without recalculating which cycles are filled, use
+    /// [BBGraph::set_region_winding_rule] to do both.
+    pub(crate) fn set_winding_rule(&mut self, winding_rule: BBWindingRule) {
+        self.winding_rule = winding_rule;
+    }
 }
@@ -10,18 +10,35 @@ pub enum BBError {
     MissingNode(BBNodeIndex),
     #[error("Missing referenced edge {0:?}.")]
     MissingEdge(BBEdgeIndex),
+    #[error("Missing referenced region {0:?}.")]
+    MissingRegion(BBRegionIndex),
     #[error("Found a dead end while traversing closed walk.")]
     ClosedWalkDeadEnd,
     #[error("Closed walk found too few links to be a valid cycle. Expected")]
     ClosedWalkTooSmall(usize),
     #[error("Hit the limits on traversals, edges: {0:?}.")]
     TraversalLimit(Vec<BBEdgeIndex>),
+    #[error("Invalid SVG path data at character {0}, {1}.")]
+    InvalidSvgPath(usize, String),
+    #[error("Node {0:?} has {1} adjacent edges, a corner needs exactly 2.")]
+    NotACorner(BBNodeIndex, usize),
+    #[error("A corner of size {1} does not fit at node {0:?}.")]
+    CornerDoesNotFit(BBNodeIndex, f32),
+    #[error("Unsupported document format version {0}.")]
+    UnsupportedDocumentVersion(u32),
+    #[error("Invalid document, {0}.")]
+    InvalidDocument(String),
+    #[error("Edges {0:?} and {1:?} overlap but the ends of the overlap could not be found.")]
+    UnresolvedOverlap(BBEdgeIndex, BBEdgeIndex),
 }
 
 impl BBError {
     /// Returns true if this error variant is of missing node / edge / region.
     pub fn is_missing_variant(&self) -> bool {
-        matches!(self, BBError::MissingNode(_) | BBError::MissingEdge(_))
+        matches!(
+            self,
+            BBError::MissingNode(_) | BBError::MissingEdge(_) | BBError::MissingRegion(_)
+        )
     }
 }
 
@@ -6,3 +6,6 @@ pub mod errors;
 pub mod traits;
 
 pub mod prelude;
+
+// Re-exported so dependants can build graphs with the same glam version.
+pub use glam;
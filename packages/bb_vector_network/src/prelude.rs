@@ -1,7 +1,22 @@
 pub use crate::errors::{BBError, BBResult};
 pub use crate::{
-    bb_graph::BBGraph,
-    bb_edge::{BBEdge, BBEdgeIndex},
+    bb_graph::{
+        BBGraph,
+        boolean::BBBooleanOp,
+        cleanup::{BBDedupeReport, BBWeldReport},
+        document::{
+            BBDocument, BBDocumentCycle, BBDocumentEdge, BBDocumentIndices, BBDocumentRegion,
+            BBDocumentWindingRule, BB_DOCUMENT_VERSION,
+        },
+        flatten::{BBPolyline, BBPolylineVertex},
+        offset::{BBLineCap, BBLineJoin},
+        sampling::{BBEvenSamples, BBSample},
+        simplify::BBSimplifyReport,
+        spatial::BBSpatialIndex,
+        validate::BBGraphDefect,
+    },
+    bb_edge::{BBArcLengthTable, BBEdge, BBEdgeIndex},
     bb_node::{BBNode, BBNodeIndex},
+    bb_region::{BBCycle, BBRegion, BBRegionIndex, BBWindingRule},
 };
 pub use crate::traits::Determinate;
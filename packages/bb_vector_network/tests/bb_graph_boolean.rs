@@ -0,0 +1,265 @@
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::shapes::{circle, rect};
+
+fn graph_with<R>(build: impl Fn(&mut BBGraph) -> R) -> BBGraph {
+    let mut g = BBGraph::new();
+    build(&mut g);
+    g
+}
+
+fn assert_nodes_within(g: &BBGraph, min: Vec2, max: Vec2) {
+    for node in g.nodes.values() {
+        let p = node.position();
+        assert!(
+            p.x >= min.x - 0.01 && p.y >= min.y - 0.01 && p.x <= max.x + 0.01 && p.y <= max.y + 0.01,
+            "Node {p} is outside of {min} -> {max}."
+        );
+    }
+}
+
+mod overlapping_rectangles {
+    use super::*;
+
+    fn build() -> (BBGraph, BBGraph) {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(10., 10.)));
+        let b = graph_with(|g| rect(g, vec2(5., 5.), vec2(15., 15.)));
+        (a, b)
+    }
+
+    #[test]
+    fn union() {
+        let (a, b) = build();
+        let result = a.boolean(&b, BBBooleanOp::Union).unwrap();
+
+        assert_eq!(result.edges_count(), 8);
+        assert_eq!(result.nodes_count(), 8);
+        assert_eq!(result.regions.len(), 1);
+        assert_nodes_within(&result, vec2(0., 0.), vec2(15., 15.));
+    }
+
+    #[test]
+    fn intersect() {
+        let (a, b) = build();
+        let result = a.boolean(&b, BBBooleanOp::Intersect).unwrap();
+
+        assert_eq!(result.edges_count(), 4);
+        assert_eq!(result.regions.len(), 1);
+        assert_nodes_within(&result, vec2(5., 5.), vec2(10., 10.));
+    }
+
+    #[test]
+    fn difference() {
+        let (a, b) = build();
+        let result = a.boolean(&b, BBBooleanOp::Difference).unwrap();
+
+        assert_eq!(result.edges_count(), 6);
+        assert_eq!(result.regions.len(), 1);
+        assert_nodes_within(&result, vec2(0., 0.), vec2(10., 10.));
+    }
+
+    #[test]
+    fn xor() {
+        let (a, b) = build();
+        let result = a.boolean(&b, BBBooleanOp::Xor).unwrap();
+
+        assert_eq!(result.edges_count(), 12);
+        assert_nodes_within(&result, vec2(0., 0.), vec2(15., 15.));
+    }
+}
+
+mod curves {
+    use super::*;
+
+    fn build() -> (BBGraph, BBGraph) {
+        let a = graph_with(|g| circle(g, vec2(0., 0.), 10.));
+        let b = graph_with(|g| circle(g, vec2(10., 0.), 10.));
+        (a, b)
+    }
+
+    #[test]
+    fn union() {
+        let (a, b) = build();
+        let result = a.boolean(&b, BBBooleanOp::Union).unwrap();
+
+        assert_eq!(result.edges_count(), 8);
+        assert_eq!(result.regions.len(), 1);
+        assert_nodes_within(&result, vec2(-10., -10.), vec2(20., 10.));
+    }
+
+    #[test]
+    fn intersect() {
+        let (a, b) = build();
+        let result = a.boolean(&b, BBBooleanOp::Intersect).unwrap();
+
+        assert_eq!(result.edges_count(), 4);
+        assert_eq!(result.regions.len(), 1);
+        assert_nodes_within(&result, vec2(0., -10.), vec2(10., 10.));
+
+        // The lens should be pinched at the two places the circles cross.
+        let crossing = vec2(5., 75_f32.sqrt());
+        for expected in [crossing, crossing * vec2(1., -1.)] {
+            let closest = result
+                .nodes
+                .values()
+                .map(|node| node.position().distance(expected))
+                .fold(f32::MAX, f32::min);
+            assert!(closest < 0.1, "No node near {expected}.");
+        }
+    }
+}
+
+mod shared_edges {
+    use super::*;
+
+    #[test]
+    fn union_removes_shared_edge() {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(10., 10.)));
+        let b = graph_with(|g| rect(g, vec2(10., 0.), vec2(20., 10.)));
+        let result = a.boolean(&b, BBBooleanOp::Union).unwrap();
+
+        assert_eq!(result.edges_count(), 6);
+        assert_eq!(result.nodes_count(), 6);
+        assert_eq!(result.regions.len(), 1);
+    }
+
+    #[test]
+    fn intersect_of_touching_is_empty() {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(10., 10.)));
+        let b = graph_with(|g| rect(g, vec2(10., 0.), vec2(20., 10.)));
+        let result = a.boolean(&b, BBBooleanOp::Intersect).unwrap();
+
+        assert_eq!(result.edges_count(), 0);
+        assert_eq!(result.regions.len(), 0);
+    }
+
+    #[test]
+    fn difference_keeps_shared_edge() {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(10., 10.)));
+        let b = graph_with(|g| rect(g, vec2(10., 0.), vec2(20., 10.)));
+        let result = a.boolean(&b, BBBooleanOp::Difference).unwrap();
+
+        assert_eq!(result.edges_count(), 4);
+        assert_nodes_within(&result, vec2(0., 0.), vec2(10., 10.));
+    }
+
+    #[test]
+    fn union_with_partially_shared_edge() {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(10., 10.)));
+        let b = graph_with(|g| rect(g, vec2(10., 5.), vec2(20., 15.)));
+        let result = a.boolean(&b, BBBooleanOp::Union).unwrap();
+
+        assert_eq!(result.edges_count(), 8);
+        assert_eq!(result.regions.len(), 1);
+    }
+
+    #[test]
+    fn splits_overlapping_curves_at_overlap_ends() {
+        // The second curve is the middle half of the first.
+        let mut g = BBGraph::new();
+        g.cubic(vec2(0., 0.), vec2(0., -100.), vec2(100., -100.), vec2(100., 0.));
+        g.cubic(
+            vec2(15.625, -56.25),
+            vec2(34.375, -81.25),
+            vec2(65.625, -81.25),
+            vec2(84.375, -56.25),
+        );
+
+        g.expand_intersections().unwrap();
+        assert_eq!(g.edges_count(), 4);
+        g.weld_nodes(0.01).unwrap();
+        g.dedupe_edges().unwrap();
+        assert_eq!(g.edges_count(), 3);
+        assert_eq!(g.nodes_count(), 4);
+    }
+}
+
+mod holes {
+    use super::*;
+
+    #[test]
+    fn difference_creates_hole() {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(20., 20.)));
+        let b = graph_with(|g| rect(g, vec2(5., 5.), vec2(15., 15.)));
+        let result = a.boolean(&b, BBBooleanOp::Difference).unwrap();
+
+        assert_eq!(result.edges_count(), 8);
+        assert_nodes_within(&result, vec2(0., 0.), vec2(20., 20.));
+    }
+
+    #[test]
+    fn intersect_with_shape_inside_hole_is_empty() {
+        let ring = graph_with(|g| {
+            rect(g, vec2(0., 0.), vec2(20., 20.));
+            rect(g, vec2(5., 5.), vec2(15., 15.));
+        });
+        let inner = graph_with(|g| rect(g, vec2(8., 8.), vec2(12., 12.)));
+        let result = ring.boolean(&inner, BBBooleanOp::Intersect).unwrap();
+
+        assert_eq!(result.edges_count(), 0);
+    }
+
+    #[test]
+    fn union_with_shape_inside_hole_keeps_all_outlines() {
+        let ring = graph_with(|g| {
+            rect(g, vec2(0., 0.), vec2(20., 20.));
+            rect(g, vec2(5., 5.), vec2(15., 15.));
+        });
+        let inner = graph_with(|g| rect(g, vec2(8., 8.), vec2(12., 12.)));
+        let result = ring.boolean(&inner, BBBooleanOp::Union).unwrap();
+
+        assert_eq!(result.edges_count(), 12);
+    }
+
+    #[test]
+    fn union_crossing_into_hole() {
+        let ring = graph_with(|g| {
+            rect(g, vec2(0., 0.), vec2(20., 20.));
+            rect(g, vec2(5., 5.), vec2(15., 15.));
+        });
+        // Plugs the left half of the hole.
+        let plug = graph_with(|g| rect(g, vec2(2., 2.), vec2(10., 18.)));
+        let result = ring.boolean(&plug, BBBooleanOp::Union).unwrap();
+
+        // Outer 4 edges + the 4 edges of the remaining [10,15]x[5,15] hole.
+        assert_eq!(result.edges_count(), 8);
+        assert_nodes_within(&result, vec2(0., 0.), vec2(20., 20.));
+    }
+}
+
+mod scale {
+    use super::*;
+
+    fn union_of_overlapping_rectangles(scale: f32) -> BBGraph {
+        let a = graph_with(|g| rect(g, vec2(0., 0.), vec2(10., 10.) * scale));
+        let b = graph_with(|g| rect(g, vec2(5., 5.) * scale, vec2(15., 15.) * scale));
+        a.boolean(&b, BBBooleanOp::Union).unwrap()
+    }
+
+    #[test]
+    fn small_shapes() {
+        let result = union_of_overlapping_rectangles(0.0001);
+        assert_eq!(result.edges_count(), 8);
+        assert_eq!(result.nodes_count(), 8);
+        assert_eq!(result.regions.len(), 1);
+    }
+
+    #[test]
+    fn small_curves() {
+        let a = graph_with(|g| circle(g, vec2(0., 0.), 0.01));
+        let b = graph_with(|g| circle(g, vec2(0.01, 0.), 0.01));
+        let result = a.boolean(&b, BBBooleanOp::Union).unwrap();
+        assert_eq!(result.edges_count(), 8);
+        assert_eq!(result.regions.len(), 1);
+    }
+
+    #[test]
+    fn large_shapes() {
+        let result = union_of_overlapping_rectangles(100_000.);
+        assert_eq!(result.edges_count(), 8);
+        assert_eq!(result.nodes_count(), 8);
+        assert_eq!(result.regions.len(), 1);
+    }
+}
@@ -0,0 +1,156 @@
+use bb_vector_network::prelude::*;
+use glam::vec2;
+
+mod weld_nodes {
+    use super::*;
+
+    #[test]
+    fn merges_nearby_nodes() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (e1, second) = g.line(vec2(10.001, 0.), vec2(10., 10.));
+
+        let report = g.weld_nodes(0.01).unwrap();
+        assert_eq!(g.nodes_count(), 3);
+        assert_eq!(report.merged_nodes.len(), 1);
+        assert!(report.removed_edges.is_empty());
+
+        // One of the two ends was merged into the other.
+        let kept = report.remap_node(first.end_idx());
+        assert_eq!(kept, report.remap_node(second.start_idx()));
+        let mut adjacents = g.node(kept).unwrap().adjacents().to_vec();
+        adjacents.sort();
+        assert_eq!(adjacents, vec![e0, e1]);
+        assert_eq!(g.edge(e1).unwrap().start_idx(), kept);
+        assert_eq!(g.edge(e0).unwrap().end_idx(), kept);
+    }
+
+    #[test]
+    fn leaves_distant_nodes() {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(10., 0.));
+        g.line(vec2(10.1, 0.), vec2(10., 10.));
+
+        let report = g.weld_nodes(0.01).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(g.nodes_count(), 4);
+    }
+
+    #[test]
+    fn removes_collapsed_edges() {
+        let mut g = BBGraph::new();
+        let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (tiny, e) = g.line_from(first.end_idx(), vec2(10., 0.005));
+        g.line_from(e.end_idx(), vec2(10., 10.));
+
+        let report = g.weld_nodes(0.01).unwrap();
+        assert_eq!(report.removed_edges, vec![tiny]);
+        assert_eq!(g.edges_count(), 2);
+        assert!(g.edge(tiny).is_err());
+        for node in g.nodes.values() {
+            assert!(!node.adjacents().contains(&tiny));
+        }
+    }
+
+    #[test]
+    fn keeps_curve_loops() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.cubic(
+            vec2(0., 0.),
+            vec2(10., 10.),
+            vec2(-10., 10.),
+            vec2(0.001, 0.),
+        );
+
+        let report = g.weld_nodes(0.01).unwrap();
+        assert!(report.removed_edges.is_empty());
+        let edge = g.edge(e0).unwrap();
+        assert_eq!(edge.start_idx(), edge.end_idx());
+    }
+
+    #[test]
+    fn lists_moved_loops_once_per_end() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(-10., 0.), vec2(0., 0.));
+        let (e1, second) = g.line(vec2(0.001, 0.), vec2(10., 10.));
+        let from = second.start_idx();
+        let (lp, _) = g.cubic_from_to(from, vec2(10., -10.), vec2(-10., -10.), from);
+
+        let report = g.weld_nodes(0.01).unwrap();
+        let into = first.end_idx();
+        assert_eq!(report.merged_nodes.get(&from), Some(&into));
+
+        let edge = g.edge(lp).unwrap();
+        assert_eq!((edge.start_idx(), edge.end_idx()), (into, into));
+        let mut adjacents = g.node(into).unwrap().adjacents().to_vec();
+        adjacents.sort();
+        let mut expected = vec![e0, e1, lp, lp];
+        expected.sort();
+        assert_eq!(adjacents, expected);
+    }
+
+    #[test]
+    fn closes_gaps_for_regions() {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(10., 0.));
+        g.line(vec2(10., 0.001), vec2(10., 10.));
+        g.line(vec2(10.001, 10.), vec2(0., 10.));
+        g.line(vec2(0., 10.), vec2(0.001, 0.001));
+        assert!(g.update_regions().unwrap().is_empty());
+
+        g.weld_nodes(0.01).unwrap();
+        assert_eq!(g.nodes_count(), 4);
+        assert_eq!(g.update_regions().unwrap().len(), 1);
+    }
+}
+
+mod dedupe_edges {
+    use super::*;
+
+    #[test]
+    fn removes_duplicates() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (e1, _) = g.line_from_to(first.start_idx(), first.end_idx());
+        // Same geometry in the other direction.
+        let (e2, _) = g.line_from_to(first.end_idx(), first.start_idx());
+
+        let report = g.dedupe_edges().unwrap();
+        assert_eq!(g.edges_count(), 1);
+        assert!(g.edge(e0).is_ok());
+        assert_eq!(report.remap_edge(e1), e0);
+        assert_eq!(report.remap_edge(e2), e0);
+        assert_eq!(g.node(first.start_idx()).unwrap().adjacents(), &[e0]);
+    }
+
+    #[test]
+    fn keeps_different_curves() {
+        let mut g = BBGraph::new();
+        let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.quadratic_from_to(first.start_idx(), vec2(5., 5.), first.end_idx());
+        g.quadratic_from_to(first.start_idx(), vec2(5., -5.), first.end_idx());
+        let (reversed, _) = g.quadratic_from_to(first.end_idx(), vec2(5., 5.), first.start_idx());
+
+        let report = g.dedupe_edges().unwrap();
+        assert_eq!(report.merged_edges.len(), 1);
+        assert!(report.merged_edges.contains_key(&reversed));
+        assert_eq!(g.edges_count(), 3);
+    }
+
+    #[test]
+    fn weld_then_dedupe_shared_edge() {
+        // Two squares drawn separately that share their middle edge.
+        let mut g =
+            BBGraph::try_new_from_svg_path("M0 0 h10 v10 h-10 z M10 0 h10 v10 h-10 z").unwrap();
+        assert_eq!(g.edges_count(), 8);
+
+        let weld = g.weld_nodes(0.01).unwrap();
+        assert_eq!(weld.merged_nodes.len(), 2);
+        let dedupe = g.dedupe_edges().unwrap();
+        assert_eq!(dedupe.merged_edges.len(), 1);
+
+        assert_eq!(g.nodes_count(), 6);
+        assert_eq!(g.edges_count(), 7);
+        assert_eq!(g.update_regions().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,199 @@
+use std::f32::consts::PI;
+
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::shapes::polyline;
+
+/// Checks that every point along the edges is `radius` from `center`.
+fn assert_on_circle(g: &BBGraph, edges: &[BBEdgeIndex], center: Vec2, radius: f32) {
+    for edge_idx in edges {
+        let edge = g.edge(*edge_idx).unwrap();
+        for i in 0..=10 {
+            let distance = edge.t_point(g, i as f32 / 10.).distance(center);
+            assert!(
+                (distance - radius).abs() < 0.01,
+                "Point was {distance} from {center}."
+            );
+        }
+    }
+}
+
+fn node_at(g: &BBGraph, position: Vec2) -> bool {
+    g.nodes
+        .values()
+        .any(|n| n.position().distance(position) < 0.001)
+}
+
+mod fillet {
+    use super::*;
+
+    #[test]
+    fn right_angle() {
+        let mut g = BBGraph::new();
+        let nodes = polyline(
+            &mut g,
+            &[vec2(0., 0.), vec2(10., 0.), vec2(10., 10.)],
+            false,
+        );
+
+        let arc = g.fillet_node(nodes[1], 2.).unwrap();
+        assert_eq!(arc.len(), 1);
+        assert!(g.node(nodes[1]).is_err());
+        assert!(node_at(&g, vec2(8., 0.)));
+        assert!(node_at(&g, vec2(10., 2.)));
+        assert_on_circle(&g, &arc, vec2(8., 2.), 2.);
+        assert_eq!(g.edges_count(), 3);
+        assert_eq!(g.nodes_count(), 4);
+    }
+
+    #[test]
+    fn tangent_to_edges() {
+        let mut g = BBGraph::new();
+        let nodes = polyline(&mut g, &[vec2(0., 0.), vec2(10., 0.), vec2(0., 5.)], false);
+
+        let arc = g.fillet_node(nodes[1], 1.).unwrap();
+        let first = g.edge(arc[0]).unwrap();
+        let last = g.edge(*arc.last().unwrap()).unwrap();
+        let start_tangent = first.calc_start_tangent(&g).unwrap().normalize();
+        let end_tangent = last.calc_end_tangent(&g).unwrap().normalize();
+        assert!(start_tangent.distance(vec2(1., 0.)) < 0.001);
+        assert!(end_tangent.distance(vec2(-10., 5.).normalize()) < 0.001);
+    }
+
+    #[test]
+    fn sharp_corner_uses_multiple_cubics() {
+        let mut g = BBGraph::new();
+        let angle = PI / 8.;
+        let nodes = polyline(
+            &mut g,
+            &[
+                vec2(0., 0.),
+                vec2(10., 0.),
+                vec2(10. - 10. * angle.cos(), 10. * angle.sin()),
+            ],
+            false,
+        );
+
+        let arc = g.fillet_node(nodes[1], 0.5).unwrap();
+        assert!(arc.len() > 1);
+        let center = vec2(10. - 0.5 / (angle / 2.).tan(), 0.5);
+        assert_on_circle(&g, &arc, center, 0.5);
+    }
+
+    #[test]
+    fn keeps_edge_directions() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        // Both edges point into the corner.
+        g.line_to(vec2(10., 10.), first.end_idx());
+        g.fillet_node(first.end_idx(), 2.).unwrap();
+
+        assert!(g.edge(e0).is_err());
+        let starts: Vec<_> = g
+            .edges
+            .values()
+            .filter(|e| matches!(e, BBEdge::Line { .. }))
+            .map(|e| e.start_pos(&g))
+            .collect();
+        assert!(starts.contains(&vec2(0., 0.)));
+        assert!(starts.contains(&vec2(10., 10.)));
+    }
+}
+
+mod chamfer {
+    use super::*;
+
+    #[test]
+    fn right_angle() {
+        let mut g = BBGraph::new();
+        let nodes = polyline(
+            &mut g,
+            &[vec2(0., 0.), vec2(10., 0.), vec2(10., 10.)],
+            false,
+        );
+
+        let edge_idx = g.chamfer_node(nodes[1], 3.).unwrap();
+        let edge = g.edge(edge_idx).unwrap();
+        assert!(matches!(edge, BBEdge::Line { .. }));
+        assert!(edge.start_pos(&g).distance(vec2(7., 0.)) < 0.001);
+        assert!(edge.end_pos(&g).distance(vec2(10., 3.)) < 0.001);
+        assert_eq!(g.edges_count(), 3);
+    }
+
+    #[test]
+    fn curved_edge_is_trimmed_by_length() {
+        let mut g = BBGraph::new();
+        let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (e1, _) = g.quadratic_from(first.end_idx(), vec2(20., 0.), vec2(20., 10.));
+        let length = g.edge(e1).unwrap().length(&g);
+
+        g.chamfer_node(first.end_idx(), 2.).unwrap();
+        let remaining: f32 = g
+            .edges
+            .values()
+            .filter(|e| e.end_pos(&g) == vec2(20., 10.))
+            .map(|e| e.length(&g))
+            .sum();
+        assert!((remaining - (length - 2.)).abs() < 0.01);
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn not_a_corner() {
+        let mut g = BBGraph::new();
+        let nodes = polyline(
+            &mut g,
+            &[vec2(0., 0.), vec2(10., 0.), vec2(10., 10.)],
+            false,
+        );
+        g.line_from(nodes[1], vec2(20., 0.));
+
+        assert!(matches!(
+            g.fillet_node(nodes[1], 1.),
+            Err(BBError::NotACorner(_, 3))
+        ));
+        assert!(matches!(
+            g.chamfer_node(nodes[0], 1.),
+            Err(BBError::NotACorner(_, 1))
+        ));
+    }
+
+    #[test]
+    fn does_not_fit() {
+        let mut g = BBGraph::new();
+        let nodes = polyline(&mut g, &[vec2(0., 0.), vec2(10., 0.), vec2(10., 2.)], false);
+        let edges_count = g.edges_count();
+
+        assert!(matches!(
+            g.fillet_node(nodes[1], 5.),
+            Err(BBError::CornerDoesNotFit(..))
+        ));
+        assert!(matches!(
+            g.chamfer_node(nodes[1], 2.5),
+            Err(BBError::CornerDoesNotFit(..))
+        ));
+        assert!(matches!(
+            g.fillet_node(nodes[1], 0.),
+            Err(BBError::CornerDoesNotFit(..))
+        ));
+        // Nothing was changed.
+        assert_eq!(g.edges_count(), edges_count);
+        assert!(g.node(nodes[1]).is_ok());
+    }
+
+    #[test]
+    fn straight_node() {
+        let mut g = BBGraph::new();
+        let nodes = polyline(&mut g, &[vec2(0., 0.), vec2(10., 0.), vec2(20., 0.)], false);
+
+        assert!(matches!(
+            g.fillet_node(nodes[1], 1.),
+            Err(BBError::CornerDoesNotFit(..))
+        ));
+    }
+}
@@ -0,0 +1,285 @@
+use bb_vector_network::prelude::*;
+use glam::vec2;
+
+/// A square with a cubic bulge and a quadratic filament, plus a non-zero triangle.
+fn graph() -> BBGraph {
+    let mut g = BBGraph::new();
+    let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+    let (_, e) = g.cubic_from(
+        first.end_idx(),
+        vec2(15., 3.),
+        vec2(15., 7.),
+        vec2(10., 10.),
+    );
+    let (_, e) = g.line_from(e.end_idx(), vec2(0., 10.));
+    g.line_from_to(e.end_idx(), first.start_idx());
+    g.quadratic_from(e.end_idx(), vec2(5., 15.), vec2(0., 20.));
+
+    let (_, first) = g.line(vec2(30., 0.), vec2(40., 0.));
+    let (_, e) = g.line_from(first.end_idx(), vec2(35., 10.));
+    g.line_from_to(e.end_idx(), first.start_idx());
+
+    g.update_regions().unwrap();
+    let triangle = *g
+        .regions
+        .iter()
+        .find(|(_, region)| region.root_cycle.edges.len() == 3)
+        .unwrap()
+        .0;
+    g.set_region_winding_rule(triangle, BBWindingRule::NonZero)
+        .unwrap();
+    g
+}
+
+#[test]
+fn document_round_trip() {
+    let g = graph();
+    let document = g.to_document().unwrap();
+    assert_eq!(document.version, BB_DOCUMENT_VERSION);
+    assert_eq!(document.nodes.len(), 8);
+    assert_eq!(document.edges.len(), 8);
+    assert_eq!(document.regions.len(), 2);
+
+    let loaded = BBGraph::from_document(&document).unwrap();
+    assert_eq!(loaded.nodes_count(), g.nodes_count());
+    assert_eq!(loaded.edges_count(), g.edges_count());
+    assert_eq!(loaded.regions.len(), 2);
+    assert!(loaded.validate().is_ok());
+    assert_eq!(loaded.to_document().unwrap(), document);
+
+    let non_zero = loaded
+        .regions
+        .values()
+        .filter(|region| region.winding_rule() == BBWindingRule::NonZero)
+        .count();
+    assert_eq!(non_zero, 1);
+}
+
+#[test]
+fn document_indices() {
+    let document = graph().to_document().unwrap();
+    let (loaded, indices) = BBGraph::from_document_with_indices(&document).unwrap();
+    assert_eq!(indices.nodes.len(), document.nodes.len());
+    assert_eq!(indices.edges.len(), document.edges.len());
+
+    for (node_idx, position) in indices.nodes.iter().zip(&document.nodes) {
+        assert_eq!(
+            loaded.node(*node_idx).unwrap().position().to_array(),
+            *position
+        );
+    }
+    for (edge_idx, edge) in indices.edges.iter().zip(&document.edges) {
+        let (BBDocumentEdge::Line { start, end }
+        | BBDocumentEdge::Quadratic { start, end, .. }
+        | BBDocumentEdge::Cubic { start, end, .. }) = *edge;
+        let loaded_edge = loaded.edge(*edge_idx).unwrap();
+        assert_eq!(loaded_edge.start_idx(), indices.nodes[start as usize]);
+        assert_eq!(loaded_edge.end_idx(), indices.nodes[end as usize]);
+    }
+}
+
+#[test]
+fn document_is_stable() {
+    let mut g = graph();
+    let document = g.to_document().unwrap();
+
+    // Churning the slots changes the indices but not the document.
+    let (e, _) = g.line(vec2(100., 100.), vec2(200., 100.));
+    g.delete_edge(e).unwrap();
+    let rebuilt = BBGraph::from_document(&document).unwrap();
+    assert_eq!(rebuilt.to_document().unwrap(), document);
+}
+
+#[test]
+fn document_with_stale_regions() {
+    let mut g = graph();
+    let edge_idx = *g
+        .regions
+        .values()
+        .next()
+        .unwrap()
+        .root_cycle
+        .edges
+        .first()
+        .unwrap();
+    g.delete_edge(edge_idx).unwrap();
+
+    assert!(matches!(g.to_document(), Err(BBError::MissingEdge(idx)) if idx == edge_idx));
+    g.update_regions().unwrap();
+    assert!(g.to_document().is_ok());
+}
+
+#[test]
+fn invalid_document() {
+    let mut document = graph().to_document().unwrap();
+    document
+        .edges
+        .push(BBDocumentEdge::Line { start: 0, end: 99 });
+    assert!(matches!(
+        BBGraph::from_document(&document),
+        Err(BBError::InvalidDocument(_))
+    ));
+
+    let mut document = graph().to_document().unwrap();
+    document.regions[0].cycle.edges.push(99);
+    assert!(matches!(
+        BBGraph::from_document(&document),
+        Err(BBError::InvalidDocument(_))
+    ));
+
+    let mut document = graph().to_document().unwrap();
+    document.version = BB_DOCUMENT_VERSION + 1;
+    assert!(matches!(
+        BBGraph::from_document(&document),
+        Err(BBError::UnsupportedDocumentVersion(_))
+    ));
+}
+
+#[test]
+fn binary_round_trip() {
+    let document = graph().to_document().unwrap();
+    let bytes = document.to_bytes();
+    assert_eq!(&bytes[..4], b"BBVN");
+    assert_eq!(BBDocument::from_bytes(&bytes).unwrap(), document);
+
+    let empty = BBGraph::new().to_document().unwrap();
+    assert_eq!(BBDocument::from_bytes(&empty.to_bytes()).unwrap(), empty);
+}
+
+#[test]
+fn binary_errors() {
+    let bytes = graph().to_document().unwrap().to_bytes();
+
+    assert!(matches!(
+        BBDocument::from_bytes(b"SVG!"),
+        Err(BBError::InvalidDocument(_))
+    ));
+    assert!(matches!(
+        BBDocument::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(BBError::InvalidDocument(_))
+    ));
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(matches!(
+        BBDocument::from_bytes(&trailing),
+        Err(BBError::InvalidDocument(_))
+    ));
+
+    // A region with cycles nested far deeper than any real graph.
+    let mut nested = b"BBVN".to_vec();
+    for count in [BB_DOCUMENT_VERSION, 0, 0, 1] {
+        nested.extend_from_slice(&count.to_le_bytes());
+    }
+    nested.push(0);
+    for _ in 0..100_000 {
+        nested.push(0);
+        nested.extend_from_slice(&0u32.to_le_bytes());
+        nested.extend_from_slice(&1u32.to_le_bytes());
+    }
+    assert!(matches!(
+        BBDocument::from_bytes(&nested),
+        Err(BBError::InvalidDocument(_))
+    ));
+
+    let mut future = bytes.clone();
+    future[4..8].copy_from_slice(&(BB_DOCUMENT_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        BBDocument::from_bytes(&future),
+        Err(BBError::UnsupportedDocumentVersion(v)) if v == BB_DOCUMENT_VERSION + 1
+    ));
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::*;
+
+    #[test]
+    fn json_round_trip() {
+        let g = graph();
+        let json = serde_json::to_string(&g).unwrap();
+        assert!(json.starts_with(r#"{"version":1,"#));
+
+        let loaded: BBGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.to_document().unwrap(), g.to_document().unwrap());
+    }
+
+    #[test]
+    fn json_edge_format() {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(1., 2.));
+        let json = serde_json::to_string(&g.to_document().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":1,"nodes":[[0.0,0.0],[1.0,2.0]],"edges":[{"kind":"line","start":0,"end":1}],"regions":[]}"#
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_json() {
+        // A triangle saved by the `serde` derive of `BBGraph` before indices were generational.
+        let json = r#"{
+            "next_idx": 3,
+            "nodes": [
+                [2, {"position": [5.0, 10.0], "adjacents": [1, 2]}],
+                [0, {"position": [0.0, 0.0], "adjacents": [0, 2]}],
+                [1, {"position": [10.0, 0.0], "adjacents": [0, 1]}]
+            ],
+            "edges": [
+                [0, {"Line": {"start": 0, "end": 1}}],
+                [1, {"Quadratic": {"start": 1, "ctrl1": [10.0, 10.0], "end": 2}}],
+                [2, {"Line": {"start": 2, "end": 0}}]
+            ],
+            "regions": [
+                [0, {"winding_rule": "NonZero", "root_cycle": {"filled": true, "edges": [0, 1, 2], "children": []}}]
+            ]
+        }"#;
+
+        let document: BBDocument = serde_json::from_str(json).unwrap();
+        assert_eq!(document.version, BB_DOCUMENT_VERSION);
+        assert_eq!(document.nodes, vec![[0., 0.], [10., 0.], [5., 10.]]);
+        assert_eq!(
+            document.edges[1],
+            BBDocumentEdge::Quadratic {
+                start: 1,
+                ctrl1: [10., 10.],
+                end: 2
+            }
+        );
+        assert_eq!(
+            document.regions[0].winding_rule,
+            BBDocumentWindingRule::NonZero
+        );
+
+        let g: BBGraph = serde_json::from_str(json).unwrap();
+        assert_eq!(g.nodes_count(), 3);
+        assert_eq!(g.edges_count(), 3);
+        assert!(g.validate().is_ok());
+    }
+
+    #[test]
+    fn migrates_generational_json() {
+        let json = r#"{
+            "slots": {"generations": [0, 0, 1], "free": []},
+            "nodes": [
+                [{"slot": 0, "generation": 0}, {"position": [0.0, 0.0], "adjacents": []}],
+                [{"slot": 1, "generation": 0}, {"position": [10.0, 0.0], "adjacents": []}]
+            ],
+            "edges": [
+                [{"slot": 2, "generation": 1}, {"Line": {"start": {"slot": 0, "generation": 0}, "end": {"slot": 1, "generation": 0}}}]
+            ],
+            "regions": []
+        }"#;
+
+        let g: BBGraph = serde_json::from_str(json).unwrap();
+        assert_eq!(g.nodes_count(), 2);
+        assert_eq!(g.edges_count(), 1);
+    }
+
+    #[test]
+    fn rejects_future_json() {
+        let json = r#"{"version":99,"nodes":[],"edges":[],"regions":[]}"#;
+        let err = serde_json::from_str::<BBDocument>(json).unwrap_err();
+        assert!(err.to_string().contains("version 99"));
+    }
+}
@@ -0,0 +1,160 @@
+use bb_vector_network::prelude::*;
+use glam::vec2;
+
+mod common;
+use crate::common::asserts::assert_near_edges;
+
+/// Checks that every segment of the polyline stays within `tolerance` of its source edges.
+fn assert_polyline_within_tolerance(g: &BBGraph, polyline: &BBPolyline, tolerance: f32) {
+    let count = polyline.vertices.len();
+    let segments = if polyline.closed { count } else { count - 1 };
+    for i in 0..segments {
+        let a = polyline.vertices[i];
+        let b = polyline.vertices[(i + 1) % count];
+        let points: Vec<_> = (1..10)
+            .map(|s| a.position.lerp(b.position, s as f32 / 10.))
+            .collect();
+        assert_near_edges(g, &[a.edge_idx, b.edge_idx], &points, tolerance * 1.01);
+    }
+}
+
+/// Checks that each vertex is at the position of its `edge_idx` and `t`.
+fn assert_mapping(g: &BBGraph, polyline: &BBPolyline) {
+    for v in &polyline.vertices {
+        let expected = g.edge(v.edge_idx).unwrap().t_point(g, v.t);
+        assert!(
+            expected.distance(v.position) < 0.001,
+            "{v:?} should be at {expected}."
+        );
+    }
+}
+
+mod flatten_edge {
+    use super::*;
+
+    #[test]
+    fn line() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+
+        let polyline = g.flatten_edge(e0, 0.1).unwrap();
+        let positions: Vec<_> = polyline.positions().collect();
+        assert_eq!(positions, vec![vec2(0., 0.), vec2(10., 0.)]);
+        assert!(!polyline.closed);
+    }
+
+    #[test]
+    fn cubic_within_tolerance() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.cubic(
+            vec2(0., 0.),
+            vec2(0., 100.),
+            vec2(100., -100.),
+            vec2(100., 0.),
+        );
+
+        for tolerance in [1., 0.1, 0.01] {
+            let polyline = g.flatten_edge(e0, tolerance).unwrap();
+            assert_polyline_within_tolerance(&g, &polyline, tolerance);
+            assert_mapping(&g, &polyline);
+            assert_eq!(polyline.vertices.first().unwrap().t, 0.);
+            assert_eq!(polyline.vertices.last().unwrap().t, 1.);
+        }
+    }
+
+    #[test]
+    fn smaller_tolerance_adds_vertices() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.quadratic(vec2(0., 0.), vec2(50., 100.), vec2(100., 0.));
+
+        let coarse = g.flatten_edge(e0, 1.).unwrap();
+        let fine = g.flatten_edge(e0, 0.01).unwrap();
+        assert!(fine.vertices.len() > coarse.vertices.len());
+        assert_polyline_within_tolerance(&g, &fine, 0.01);
+    }
+
+    #[test]
+    fn missing_edge() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.delete_edge(e0).unwrap();
+
+        assert!(g.flatten_edge(e0, 0.1).is_err());
+    }
+}
+
+mod flatten_edges {
+    use super::*;
+
+    #[test]
+    fn open_chain_with_reversed_edge() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        // Stored pointing back towards the first edge.
+        let (e1, _) = g.quadratic_to(vec2(20., 0.), vec2(15., 10.), first.end_idx());
+
+        let polyline = g.flatten_edges(&[e0, e1], 0.1).unwrap();
+        assert!(!polyline.closed);
+        assert_mapping(&g, &polyline);
+        assert_polyline_within_tolerance(&g, &polyline, 0.1);
+
+        let positions: Vec<_> = polyline.positions().collect();
+        assert_eq!(positions.first(), Some(&vec2(0., 0.)));
+        assert_eq!(positions.last(), Some(&vec2(20., 0.)));
+        // The shared vertex is mapped to the start of the second edge, in the chain's direction.
+        assert_eq!(polyline.vertices[1].edge_idx, e1);
+        assert_eq!(polyline.vertices[1].t, 1.);
+        assert_eq!(polyline.vertices.last().unwrap().t, 0.);
+    }
+
+    #[test]
+    fn closed_cycle() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (e1, e) = g.line_from(first.end_idx(), vec2(10., 10.));
+        let (e2, _) = g.cubic_from_to(e.end_idx(), vec2(5., 15.), vec2(0., 15.), first.start_idx());
+        g.update_regions().unwrap();
+        let region = g.regions.values().next().unwrap();
+
+        let polyline = region.root_cycle.flatten(&g, 0.01).unwrap();
+        assert!(polyline.closed);
+        assert_mapping(&g, &polyline);
+        assert_polyline_within_tolerance(&g, &polyline, 0.01);
+
+        let positions: Vec<_> = polyline.positions().collect();
+        for corner in [vec2(0., 0.), vec2(10., 0.), vec2(10., 10.)] {
+            assert_eq!(positions.iter().filter(|p| **p == corner).count(), 1);
+        }
+        let mut edges: Vec<_> = polyline.vertices.iter().map(|v| v.edge_idx).collect();
+        edges.dedup();
+        edges.sort();
+        let mut expected = vec![e0, e1, e2];
+        expected.sort();
+        assert_eq!(edges, expected);
+    }
+}
+
+#[test]
+fn flatten_region() {
+    let mut g = BBGraph::new();
+    let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+    let (_, e) = g.line_from(first.end_idx(), vec2(10., 10.));
+    let (_, e) = g.line_from(e.end_idx(), vec2(0., 10.));
+    g.line_from_to(e.end_idx(), first.start_idx());
+    let region_idx = g.update_regions().unwrap()[0];
+
+    let polylines = g.flatten_region(region_idx, 0.1).unwrap();
+    assert!(!polylines.is_empty());
+    let polyline = &polylines[0];
+    assert!(polyline.closed);
+    assert_eq!(polyline.vertices.len(), 4);
+    let positions: Vec<_> = polyline.positions().collect();
+    let area = positions
+        .iter()
+        .zip(positions.iter().cycle().skip(1))
+        .map(|(a, b)| a.determinate(*b))
+        .sum::<f32>()
+        .abs()
+        / 2.;
+    assert_eq!(area, 100.);
+}
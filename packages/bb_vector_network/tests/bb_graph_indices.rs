@@ -0,0 +1,96 @@
+use bb_vector_network::prelude::*;
+use glam::vec2;
+
+mod common;
+use crate::common::shapes::triangle;
+
+mod stale_indices {
+    use super::*;
+
+    #[test]
+    fn deleted_edge_is_missing_after_slot_is_reused() {
+        let mut g = BBGraph::new();
+        let (stale, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.delete_edge(stale).unwrap();
+
+        let (fresh, _) = g.line(vec2(0., 10.), vec2(10., 10.));
+        assert_eq!(fresh.slot(), stale.slot());
+        assert_ne!(fresh, stale);
+
+        assert!(matches!(g.edge(stale), Err(BBError::MissingEdge(_))));
+        assert!(g.edge(fresh).is_ok());
+    }
+
+    #[test]
+    fn deleted_node_is_missing_after_slot_is_reused() {
+        let mut g = BBGraph::new();
+        let (_, edge) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let stale = edge.start_idx();
+        g.delete_node(stale).unwrap();
+
+        let (_, edge) = g.line(vec2(0., 10.), vec2(10., 10.));
+        // Nodes and edges share slots so the stale slot could be re-used by either.
+        let reused = g.nodes.keys().map(|idx| idx.slot())
+            .chain(g.edges.keys().map(|idx| idx.slot()))
+            .any(|slot| slot == stale.slot());
+        assert!(reused);
+        assert_ne!(edge.start_idx(), stale);
+        assert_ne!(edge.end_idx(), stale);
+
+        assert!(matches!(g.node(stale), Err(BBError::MissingNode(_))));
+    }
+}
+
+mod region_identity {
+    use super::*;
+
+    #[test]
+    fn unchanged_region_keeps_index() {
+        let mut g = BBGraph::new();
+        triangle(&mut g, vec2(0., 0.));
+        let first = g.update_regions().unwrap();
+        let second = g.update_regions().unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn unchanged_region_keeps_winding_rule() {
+        let mut g = BBGraph::new();
+        triangle(&mut g, vec2(0., 0.));
+        let edges = triangle(&mut g, vec2(100., 0.));
+        let regions = g.update_regions().unwrap();
+        for region_idx in &regions {
+            g.set_region_winding_rule(*region_idx, BBWindingRule::NonZero)
+                .unwrap();
+        }
+
+        // Change only the second triangle.
+        g.delete_edge(edges[0]).unwrap();
+        let updated = g.update_regions().unwrap();
+
+        assert_eq!(updated.len(), 1);
+        assert!(regions.contains(&updated[0]));
+        assert_eq!(
+            g.region(updated[0]).unwrap().winding_rule(),
+            BBWindingRule::NonZero
+        );
+    }
+
+    #[test]
+    fn changed_region_gets_new_index() {
+        let mut g = BBGraph::new();
+        let edges = triangle(&mut g, vec2(0., 0.));
+        let before = g.update_regions().unwrap();
+
+        let (_, e) = g.line_from(g.edge(edges[0]).unwrap().start_idx(), vec2(-5., 5.));
+        g.line_from_to(e.end_idx(), g.edge(edges[1]).unwrap().end_idx());
+        let after = g.update_regions().unwrap();
+
+        assert_ne!(before, after);
+        assert!(matches!(
+            g.region(before[0]),
+            Err(BBError::MissingRegion(_))
+        ));
+    }
+}
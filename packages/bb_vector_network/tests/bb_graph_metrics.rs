@@ -0,0 +1,230 @@
+use std::f32::consts::PI;
+
+use bb_vector_network::prelude::*;
+use glam::vec2;
+
+mod common;
+use crate::common::asserts::{assert_near, assert_near_v2};
+
+/// Returns the region containing `edge_idx`, region order isn't stable.
+fn region_with_edge(g: &BBGraph, edge_idx: BBEdgeIndex) -> BBRegionIndex {
+    *g.regions
+        .iter()
+        .find(|(_, region)| {
+            region
+                .root_cycle
+                .edges_deep()
+                .iter()
+                .flatten()
+                .any(|e| *e == edge_idx)
+        })
+        .unwrap()
+        .0
+}
+
+mod cycle_metrics {
+    use super::*;
+
+    #[test]
+    fn square() {
+        let mut g = BBGraph::try_new_from_svg_path("M0 0 h10 v10 h-10 z").unwrap();
+        let region_idx = g.update_regions().unwrap()[0];
+        let cycle = &g.region(region_idx).unwrap().root_cycle;
+
+        assert_near(cycle.signed_area(&g).unwrap().abs(), 100., 0.001);
+        assert_near_v2(cycle.centroid(&g).unwrap(), vec2(5., 5.), 0.001);
+        assert_eq!(cycle.bounds(&g).unwrap(), (vec2(0., 0.), vec2(10., 10.)));
+        assert_near(cycle.perimeter(&g).unwrap(), 40., 0.001);
+    }
+
+    #[test]
+    fn direction_sets_sign() {
+        let mut g = BBGraph::try_new_from_svg_path("M0 0 h10 v10 h-10 z").unwrap();
+        let region_idx = g.update_regions().unwrap()[0];
+        let mut cycle = g.region(region_idx).unwrap().root_cycle.clone();
+
+        let area = cycle.signed_area(&g).unwrap();
+        cycle.edges.reverse();
+        assert_near(cycle.signed_area(&g).unwrap(), -area, 0.001);
+    }
+
+    #[test]
+    fn circle() {
+        let mut g = BBGraph::try_new_from_svg_path("M20 10 A10 10 0 0 1 0 10 A10 10 0 0 1 20 10 Z")
+            .unwrap();
+        let region_idx = g.update_regions().unwrap()[0];
+        let cycle = &g.region(region_idx).unwrap().root_cycle;
+
+        // Cubic arcs are within a few hundredths of a percent of a true circle.
+        assert_near(cycle.signed_area(&g).unwrap().abs(), PI * 100., 0.1);
+        assert_near_v2(cycle.centroid(&g).unwrap(), vec2(10., 10.), 0.001);
+        assert_near(cycle.perimeter(&g).unwrap(), PI * 20., 0.01);
+        let (min, max) = cycle.bounds(&g).unwrap();
+        assert_near_v2(min, vec2(0., 0.), 0.01);
+        assert_near_v2(max, vec2(20., 20.), 0.01);
+    }
+
+    #[test]
+    fn curved_triangle_centroid() {
+        // The area under y = 10 - x^2 / 10 between x = -10 and 10, exactly representable as a
+        // quadratic bezier.
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(10., 0.), vec2(-10., 0.));
+        g.quadratic_from_to(first.end_idx(), vec2(0., 20.), first.start_idx());
+        g.update_regions().unwrap();
+        let region_idx = region_with_edge(&g, e0);
+        let cycle = &g.region(region_idx).unwrap().root_cycle;
+
+        assert_near(cycle.signed_area(&g).unwrap().abs(), 400. / 3., 0.01);
+        assert_near_v2(cycle.centroid(&g).unwrap(), vec2(0., 4.), 0.001);
+    }
+}
+
+mod edge_bounds {
+    use super::*;
+
+    #[test]
+    fn tight_bounds_exclude_control_points() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.cubic(vec2(0., 0.), vec2(0., 20.), vec2(10., -20.), vec2(10., 0.));
+        let (e1, _) = g.quadratic(vec2(0., 0.), vec2(5., 10.), vec2(10., 0.));
+
+        let (min, max) = g.edge(e0).unwrap().bounds(&g);
+        assert_eq!((min.x, max.x), (0., 10.));
+        // The extrema of the cubic are at t = 0.5 ± sqrt(3) / 6.
+        assert_near(max.y, 10. / 3f32.sqrt(), 0.001);
+        assert_near(min.y, -10. / 3f32.sqrt(), 0.001);
+
+        let (min, max) = g.edge(e1).unwrap().bounds(&g);
+        assert_eq!(min, vec2(0., 0.));
+        assert_near_v2(max, vec2(10., 5.), 0.001);
+    }
+}
+
+mod region_metrics {
+    use super::*;
+
+    #[test]
+    fn nested_hole_is_subtracted() {
+        let mut g = BBGraph::try_new_from_svg_path("M0 0 h20 v20 h-20 z M5 5 h5 v5 h-5 z").unwrap();
+        g.update_regions().unwrap();
+        let outer_idx = region_with_edge(&g, *g.edges.keys().min().unwrap());
+
+        assert_near(g.region_area(outer_idx).unwrap(), 375., 0.001);
+        // Centroid of the square minus the hole.
+        let expected = (vec2(10., 10.) * 400. - vec2(7.5, 7.5) * 25.) / 375.;
+        assert_near_v2(g.region_centroid(outer_idx).unwrap(), expected, 0.001);
+    }
+}
+
+mod region_at_point {
+    use super::*;
+
+    #[test]
+    fn innermost_filled_face() {
+        let mut g =
+            BBGraph::try_new_from_svg_path("M0 0 h20 v20 h-20 z M5 5 h10 v10 h-10 z").unwrap();
+        g.update_regions().unwrap();
+        let outer_idx = region_with_edge(&g, *g.edges.keys().min().unwrap());
+
+        let (region_idx, cycle) = g.region_at_point(vec2(2., 2.)).unwrap().unwrap();
+        assert_eq!(region_idx, outer_idx);
+        assert!(cycle.filled);
+        // Inside of the hole.
+        assert!(g.region_at_point(vec2(10., 10.)).unwrap().is_none());
+        assert!(g.region_at_point(vec2(30., 10.)).unwrap().is_none());
+    }
+
+    #[test]
+    fn curved_boundary() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(10., 0.), vec2(-10., 0.));
+        g.quadratic_from_to(first.end_idx(), vec2(0., 20.), first.start_idx());
+        g.update_regions().unwrap();
+        let region_idx = region_with_edge(&g, e0);
+
+        // The curve peaks at y = 10, above the line between its end points.
+        assert_eq!(
+            g.region_at_point(vec2(0., 9.)).unwrap().map(|(idx, _)| idx),
+            Some(region_idx)
+        );
+        assert!(g.region_at_point(vec2(0., 11.)).unwrap().is_none());
+    }
+}
+
+mod graph_bounds {
+    use super::*;
+
+    #[test]
+    fn hugs_curves() {
+        let mut g = BBGraph::new();
+        g.cubic(vec2(0., 0.), vec2(0., 20.), vec2(10., -20.), vec2(10., 0.));
+        g.line(vec2(5., 0.), vec2(20., 2.));
+
+        let (min, max) = g.bounds().unwrap();
+        assert_eq!((min.x, max.x), (0., 20.));
+        assert_near(max.y, 10. / 3f32.sqrt(), 0.001);
+        assert_near(min.y, -10. / 3f32.sqrt(), 0.001);
+    }
+
+    #[test]
+    fn includes_nodes_without_edges() {
+        assert_eq!(BBGraph::new().bounds(), None);
+
+        let mut g = BBGraph::from_document(&BBDocument {
+            version: BB_DOCUMENT_VERSION,
+            nodes: vec![[-5., 3.]],
+            edges: vec![],
+            regions: vec![],
+        })
+        .unwrap();
+        g.line(vec2(0., 0.), vec2(10., 10.));
+        assert_eq!(g.bounds(), Some((vec2(-5., 0.), vec2(10., 10.))));
+    }
+}
+
+mod convex_hull {
+    use super::*;
+
+    #[test]
+    fn skips_interior_and_collinear_points() {
+        let g = BBGraph::try_new_from_svg_path("M0 0 h5 h5 v10 h-10 z M2 2 L8 8").unwrap();
+
+        let hull = g.convex_hull();
+        assert_eq!(
+            hull,
+            vec![vec2(0., 0.), vec2(10., 0.), vec2(10., 10.), vec2(0., 10.)]
+        );
+    }
+
+    #[test]
+    fn follows_curves() {
+        let mut g = BBGraph::new();
+        let (_, first) = g.line(vec2(10., 0.), vec2(-10., 0.));
+        g.quadratic_from_to(first.end_idx(), vec2(0., 20.), first.start_idx());
+
+        let hull = g.convex_hull();
+        // Counterclockwise, and the peak of the curve is at y = 10 rather than the control point.
+        let area: f32 = hull
+            .iter()
+            .zip(hull.iter().cycle().skip(1))
+            .map(|(a, b)| a.perp_dot(*b))
+            .sum();
+        assert!(area > 0.);
+        let top = hull.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        assert_near(top, 10., 0.001);
+        for p in &hull {
+            assert!(p.y >= 0. && p.y <= 10. + 0.001);
+        }
+    }
+
+    #[test]
+    fn degenerate_graphs() {
+        assert!(BBGraph::new().convex_hull().is_empty());
+
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(10., 0.));
+        g.line(vec2(5., 0.), vec2(20., 0.));
+        assert_eq!(g.convex_hull(), vec![vec2(0., 0.), vec2(20., 0.)]);
+    }
+}
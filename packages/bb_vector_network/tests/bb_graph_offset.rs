@@ -0,0 +1,257 @@
+use std::f32::consts::PI;
+
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::{asserts::assert_near, shapes::square};
+
+/// Total filled area of the graph.
+fn filled_area(g: &BBGraph) -> f32 {
+    g.regions
+        .keys()
+        .map(|idx| g.region_area(*idx).unwrap())
+        .sum()
+}
+
+fn bounds(g: &BBGraph) -> (Vec2, Vec2) {
+    g.edges.values().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(min, max), edge| {
+            let (e_min, e_max) = edge.bounds(g);
+            (min.min(e_min), max.max(e_max))
+        },
+    )
+}
+
+fn assert_bounds(g: &BBGraph, min: Vec2, max: Vec2) {
+    let (actual_min, actual_max) = bounds(g);
+    assert!(
+        actual_min.distance(min) < 0.01 && actual_max.distance(max) < 0.01,
+        "Bounds were {actual_min} -> {actual_max}, expected {min} -> {max}."
+    );
+}
+
+/// Checks that the result is made of closed outlines, every node joins exactly two edges.
+fn assert_clean(g: &BBGraph) {
+    for (node_idx, node) in &g.nodes {
+        assert_eq!(
+            node.adjacents().len(),
+            2,
+            "{node_idx} isn't on a closed outline."
+        );
+    }
+}
+
+/// A filled square with its min corner at the origin.
+fn filled_square(size: f32) -> BBGraph {
+    let mut g = BBGraph::new();
+    square(&mut g, Vec2::ZERO, size);
+    g.update_regions().unwrap();
+    g
+}
+
+mod shapes {
+    use super::*;
+
+    #[test]
+    fn outset_miter() {
+        let result = filled_square(10.)
+            .offset(1., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 4);
+        assert_bounds(&result, vec2(-1., -1.), vec2(11., 11.));
+        assert_near(filled_area(&result), 144., 0.01);
+    }
+
+    #[test]
+    fn outset_round() {
+        let result = filled_square(10.)
+            .offset(1., BBLineJoin::Round, BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 8);
+        assert_near(filled_area(&result), 140. + PI, 0.01);
+    }
+
+    #[test]
+    fn outset_bevel() {
+        let result = filled_square(10.)
+            .offset(1., BBLineJoin::Bevel, BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_near(filled_area(&result), 142., 0.01);
+    }
+
+    #[test]
+    fn miter_limit_falls_back_to_bevel() {
+        // A right angle miter is sqrt(2) times the stroke width.
+        let result = filled_square(10.)
+            .offset(1., BBLineJoin::Miter(1.2), BBLineCap::Butt)
+            .unwrap();
+
+        assert_near(filled_area(&result), 142., 0.01);
+    }
+
+    #[test]
+    fn inset() {
+        let result = filled_square(10.)
+            .offset(-1., BBLineJoin::Round, BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 4);
+        assert_bounds(&result, vec2(1., 1.), vec2(9., 9.));
+        assert_near(filled_area(&result), 64., 0.01);
+    }
+
+    #[test]
+    fn inset_past_nothing() {
+        let result = filled_square(10.)
+            .offset(-6., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_eq!(result.edges_count(), 0);
+    }
+
+    #[test]
+    fn outset_shrinks_holes() {
+        let mut g =
+            BBGraph::try_new_from_svg_path("M0 0 h20 v20 h-20 z M5 5 h10 v10 h-10 z").unwrap();
+        g.update_regions().unwrap();
+
+        let result = g
+            .offset(1., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 8);
+        assert_near(filled_area(&result), 22. * 22. - 8. * 8., 0.01);
+    }
+
+    #[test]
+    fn outset_circle() {
+        let mut g =
+            BBGraph::try_new_from_svg_path("M10 0 A10 10 0 0 1 -10 0 A10 10 0 0 1 10 0 Z").unwrap();
+        g.update_regions().unwrap();
+
+        let result = g
+            .offset(2., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_bounds(&result, vec2(-12., -12.), vec2(12., 12.));
+        assert_near(filled_area(&result), PI * 144., 0.2);
+        for edge in result.edges.values() {
+            for i in 0..=10 {
+                let distance = edge.t_point(&result, i as f32 / 10.).length();
+                assert!(
+                    (distance - 12.).abs() < 0.02,
+                    "Point was {distance} from center."
+                );
+            }
+        }
+    }
+}
+
+mod strokes {
+    use super::*;
+
+    fn line() -> BBGraph {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(10., 0.));
+        g
+    }
+
+    #[test]
+    fn butt_cap() {
+        let result = line()
+            .offset(1., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 4);
+        assert_bounds(&result, vec2(0., -1.), vec2(10., 1.));
+        assert_near(filled_area(&result), 20., 0.01);
+    }
+
+    #[test]
+    fn square_cap() {
+        let result = line()
+            .offset(1., BBLineJoin::Miter(4.), BBLineCap::Square)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_bounds(&result, vec2(-1., -1.), vec2(11., 1.));
+        assert_near(filled_area(&result), 24., 0.01);
+    }
+
+    #[test]
+    fn round_cap() {
+        let result = line()
+            .offset(1., BBLineJoin::Miter(4.), BBLineCap::Round)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_bounds(&result, vec2(-1., -1.), vec2(11., 1.));
+        assert_near(filled_area(&result), 20. + PI, 0.01);
+    }
+
+    #[test]
+    fn negative_distance_strokes_the_same() {
+        let result = line()
+            .offset(-1., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_near(filled_area(&result), 20., 0.01);
+    }
+
+    #[test]
+    fn corner_overlap_is_resolved() {
+        // The inside of the corner overlaps itself.
+        let mut g = BBGraph::new();
+        let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.line_from(first.end_idx(), vec2(10., 10.));
+
+        let result = g
+            .offset(1., BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 6);
+        assert_bounds(&result, vec2(0., -1.), vec2(11., 10.));
+        assert_near(filled_area(&result), 11. * 2. + 9. * 2., 0.01);
+    }
+}
+
+mod scale {
+    use super::*;
+
+    #[test]
+    fn small_shapes() {
+        let result = filled_square(0.01)
+            .offset(0.001, BBLineJoin::Miter(4.), BBLineCap::Butt)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_eq!(result.edges_count(), 4);
+        assert_near(filled_area(&result), 0.012 * 0.012, 1e-7);
+    }
+
+    #[test]
+    fn small_strokes() {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(0.01, 0.));
+
+        let result = g
+            .offset(0.001, BBLineJoin::Miter(4.), BBLineCap::Round)
+            .unwrap();
+
+        assert_clean(&result);
+        assert_near(filled_area(&result), 0.01 * 0.002 + PI * 0.001 * 0.001, 1e-7);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use bb_vector_network::prelude::*;
+use glam::vec2;
+
+mod common;
+use crate::common::shapes::square;
+
+/// The sorted edges of every region, used to compare regions between graphs.
+fn region_keys(g: &BBGraph) -> HashSet<Vec<BBEdgeIndex>> {
+    g.regions
+        .values()
+        .map(|region| {
+            let mut edges = region.root_cycle.edges_deep().concat();
+            edges.sort();
+            edges.dedup();
+            edges
+        })
+        .collect()
+}
+
+#[test]
+fn no_changes() {
+    let mut g = BBGraph::new();
+    square(&mut g, vec2(0., 0.), 10.);
+    let regions = g.update_regions().unwrap();
+
+    assert!(g.update_regions_dirty().unwrap().is_empty());
+    assert_eq!(g.regions.keys().copied().collect::<Vec<_>>(), regions);
+}
+
+#[test]
+fn from_scratch_matches_full_update() {
+    let mut g = BBGraph::new();
+    square(&mut g, vec2(0., 0.), 10.);
+    square(&mut g, vec2(20., 0.), 10.);
+    let mut full = g.clone();
+
+    assert_eq!(g.update_regions_dirty().unwrap().len(), 2);
+    full.update_regions().unwrap();
+    assert_eq!(region_keys(&g), region_keys(&full));
+}
+
+#[test]
+fn only_moved_component_is_recalculated() {
+    let mut g = BBGraph::new();
+    let a = square(&mut g, vec2(0., 0.), 10.)[0];
+    square(&mut g, vec2(20., 0.), 10.);
+    g.update_regions().unwrap();
+    let before = region_keys(&g);
+    let indices: HashSet<_> = g.regions.keys().copied().collect();
+
+    g.node_mut(a).unwrap().set_position(vec2(-5., -5.));
+    let updated = g.update_regions_dirty().unwrap();
+
+    assert_eq!(updated.len(), 1);
+    // The moved square still encloses the same edges so both handles are kept.
+    assert_eq!(g.regions.keys().copied().collect::<HashSet<_>>(), indices);
+    assert_eq!(region_keys(&g), before);
+}
+
+#[test]
+fn deleted_edge_removes_region() {
+    let mut g = BBGraph::new();
+    let a = square(&mut g, vec2(0., 0.), 10.)[0];
+    square(&mut g, vec2(20., 0.), 10.);
+    g.update_regions().unwrap();
+    let untouched: Vec<_> = g
+        .regions
+        .iter()
+        .filter(|(_, region)| {
+            !region
+                .root_cycle
+                .edges
+                .contains(&g.node(a).unwrap().adjacents()[0])
+        })
+        .map(|(index, _)| *index)
+        .collect();
+
+    let edge_idx = g.node(a).unwrap().adjacents()[0];
+    g.delete_edge(edge_idx).unwrap();
+
+    assert!(g.update_regions_dirty().unwrap().is_empty());
+    assert_eq!(g.regions.keys().copied().collect::<Vec<_>>(), untouched);
+}
+
+#[test]
+fn new_and_joined_components() {
+    let mut g = BBGraph::new();
+    let a = square(&mut g, vec2(0., 0.), 10.)[0];
+    g.update_regions().unwrap();
+
+    // A new square only recalculates itself.
+    let b = square(&mut g, vec2(20., 0.), 10.)[0];
+    assert_eq!(g.update_regions_dirty().unwrap().len(), 1);
+    assert_eq!(g.regions.len(), 2);
+
+    // Joining the squares recalculates both, they're now one detached graph with one region.
+    g.line_from_to(a, b);
+    assert_eq!(g.update_regions_dirty().unwrap().len(), 1);
+    assert_eq!(g.regions.len(), 1);
+}
+
+#[test]
+fn keeps_winding_rule_of_untouched_regions() {
+    let mut g = BBGraph::new();
+    square(&mut g, vec2(0., 0.), 10.);
+    let region_idx = g.update_regions().unwrap()[0];
+    g.set_region_winding_rule(region_idx, BBWindingRule::NonZero)
+        .unwrap();
+
+    square(&mut g, vec2(20., 0.), 10.);
+    g.update_regions_dirty().unwrap();
+    assert_eq!(
+        g.region(region_idx).unwrap().winding_rule(),
+        BBWindingRule::NonZero
+    );
+}
@@ -0,0 +1,160 @@
+use std::f32::consts::PI;
+
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::shapes::circle;
+
+mod arc_length {
+    use super::*;
+
+    #[test]
+    fn line_length() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.line(vec2(0., 0.), vec2(30., 40.));
+
+        assert_eq!(e.length(&g), 50.);
+        assert_eq!(e.t_at_length(&g, 25.), 0.5);
+        assert_eq!(e.point_at_length(&g, 10.), vec2(6., 8.));
+    }
+
+    #[test]
+    fn straight_cubic_is_evenly_spaced() {
+        let mut g = BBGraph::new();
+        // Control points bunched at the start so `t` is not evenly spaced.
+        let (_, e) = g.cubic(vec2(0., 0.), vec2(1., 0.), vec2(2., 0.), vec2(100., 0.));
+
+        assert!((e.length(&g) - 100.).abs() < 0.01);
+        for d in [10., 25., 50., 90.] {
+            let p = e.point_at_length(&g, d);
+            assert!((p.x - d).abs() < 0.1, "Expected {d} got {p}.");
+        }
+    }
+
+    #[test]
+    fn quarter_circle_length() {
+        let mut g = BBGraph::new();
+        let edges = circle(&mut g, Vec2::ZERO, 100.);
+        let length = g.edge(edges[0]).unwrap().length(&g);
+
+        // The cubic approximation of a circle is very slightly larger than a true circle.
+        assert!((length - PI * 50.).abs() < 0.1, "Length was {length}.");
+    }
+
+    #[test]
+    fn length_is_clamped() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.quadratic(vec2(0., 0.), vec2(50., 50.), vec2(100., 0.));
+
+        assert_eq!(e.t_at_length(&g, -10.), 0.);
+        assert_eq!(e.t_at_length(&g, 1000.), 1.);
+    }
+
+    #[test]
+    fn tolerance_improves_accuracy() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.cubic(
+            vec2(0., 0.),
+            vec2(0., 100.),
+            vec2(100., -100.),
+            vec2(100., 0.),
+        );
+
+        let precise = e.arc_length_table(&g, 0.0001).length();
+        let coarse = e.arc_length_table(&g, 10.).length();
+        assert!(coarse <= precise);
+        assert!((e.length(&g) - precise).abs() < 0.1);
+    }
+
+    #[test]
+    fn degenerate_tangent() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.cubic(vec2(0., 0.), vec2(0., 0.), vec2(100., 0.), vec2(100., 0.));
+
+        assert_eq!(e.t_derivative(&g, 0.), Vec2::ZERO);
+        assert!(e.t_tangent(&g, 0.).distance(vec2(1., 0.)) < 0.001);
+    }
+}
+
+mod sample_evenly {
+    use super::*;
+
+    #[test]
+    fn chain_of_lines() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (e1, _) = g.line_from(first.end_idx(), vec2(10., 10.));
+
+        let samples: Vec<_> = g.sample_evenly(&[e0, e1], 5., 0.01).unwrap().collect();
+        let positions: Vec<_> = samples.iter().map(|s| s.position).collect();
+        assert_eq!(
+            positions,
+            vec![
+                vec2(0., 0.),
+                vec2(5., 0.),
+                vec2(10., 0.),
+                vec2(10., 5.),
+                vec2(10., 10.)
+            ]
+        );
+        assert_eq!(samples[1].tangent, vec2(1., 0.));
+        assert_eq!(samples[3].tangent, vec2(0., 1.));
+        assert_eq!(samples[3].edge_idx, e1);
+    }
+
+    #[test]
+    fn reversed_edges_follow_chain() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        // Stored pointing back towards the first edge.
+        let (e1, _) = g.line_to(vec2(20., 0.), first.end_idx());
+
+        let samples: Vec<_> = g.sample_evenly(&[e0, e1], 6., 0.01).unwrap().collect();
+        let last = samples.last().unwrap();
+        assert_eq!(last.position, vec2(18., 0.));
+        assert_eq!(last.tangent, vec2(1., 0.));
+        assert_eq!(last.edge_idx, e1);
+        assert!((last.t - 0.2).abs() < 0.0001);
+    }
+
+    #[test]
+    fn cycle_samples_are_evenly_spaced() {
+        let mut g = BBGraph::new();
+        circle(&mut g, Vec2::ZERO, 100.);
+        g.update_regions().unwrap();
+        let region = g.regions.values().next().unwrap();
+
+        let samples: Vec<_> = region
+            .root_cycle
+            .sample_evenly(&g, 10., 0.001)
+            .unwrap()
+            .collect();
+        assert_eq!(samples.len(), 63);
+        for pair in samples.windows(2) {
+            let arc = pair[0].position.distance(pair[1].position);
+            assert!((arc - 10.).abs() < 0.05, "Spacing was {arc}.");
+            assert!((pair[0].position.length() - 100.).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn many_samples_dont_drift() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.line(vec2(0., 0.), vec2(100., 0.));
+
+        let samples: Vec<_> = g.sample_evenly(&[e0], 0.1, 0.01).unwrap().collect();
+        assert_eq!(samples.len(), 1001);
+        for (i, sample) in samples.iter().enumerate() {
+            assert_eq!(sample.distance, i as f32 * 0.1);
+        }
+    }
+
+    #[test]
+    fn non_positive_spacing() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+
+        assert_eq!(g.sample_evenly(&[e0], 0., 0.01).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,98 @@
+use std::f32::consts::TAU;
+
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::{asserts::assert_within_tolerance, shapes::polyline};
+
+/// Points along the straight line from `a` to `b`, excluding `b`.
+fn subdivided(a: Vec2, b: Vec2, count: usize) -> Vec<Vec2> {
+    (0..count)
+        .map(|i| a.lerp(b, i as f32 / count as f32))
+        .collect()
+}
+
+fn circle_points(count: usize, radius: f32) -> Vec<Vec2> {
+    (0..count)
+        .map(|i| Vec2::from_angle(TAU * i as f32 / count as f32) * radius)
+        .collect()
+}
+
+#[test]
+fn circle() {
+    let mut g = BBGraph::new();
+    let points = circle_points(64, 50.);
+    polyline(&mut g, &points, true);
+
+    let report = g.simplify(0.5).unwrap();
+    assert_eq!(report.removed_edges.len(), 64);
+    assert!(g.edges_count() <= 8, "{} edges", g.edges_count());
+    assert_eq!(g.edges_count(), report.added_edges.len());
+    assert!(g.edges.values().all(|e| matches!(e, BBEdge::Cubic { .. })));
+    assert_within_tolerance(&g, &points, 0.5);
+}
+
+#[test]
+fn corners_are_kept() {
+    let corners = [vec2(0., 0.), vec2(10., 0.), vec2(10., 10.), vec2(0., 10.)];
+    let mut points = vec![];
+    for i in 0..4 {
+        points.extend(subdivided(corners[i], corners[(i + 1) % 4], 10));
+    }
+    let mut g = BBGraph::new();
+    polyline(&mut g, &points, true);
+
+    g.simplify(0.01).unwrap();
+    assert_eq!(g.edges_count(), 4);
+    assert_eq!(g.nodes_count(), 4);
+    for corner in corners {
+        assert!(g.nodes.values().any(|n| n.position() == corner));
+    }
+    assert_within_tolerance(&g, &points, 0.01);
+}
+
+#[test]
+fn branch_nodes_are_kept() {
+    let mut g = BBGraph::new();
+    let mut points = subdivided(Vec2::ZERO, vec2(-10., 0.), 10);
+    points.push(vec2(-10., 0.));
+    let branch_idx = polyline(&mut g, &points, false)[0];
+    for arm in [vec2(10., 0.), vec2(0., 10.)] {
+        let mut node_idx = branch_idx;
+        for p in subdivided(Vec2::ZERO, arm, 10)
+            .into_iter()
+            .skip(1)
+            .chain([arm])
+        {
+            node_idx = g.line_from(node_idx, p).1.end_idx();
+        }
+    }
+
+    g.simplify(0.01).unwrap();
+    assert_eq!(g.node(branch_idx).unwrap().adjacents().len(), 3);
+    assert_eq!(g.edges_count(), 3);
+    assert_eq!(g.nodes_count(), 4);
+}
+
+#[test]
+fn corner_angle_is_configurable() {
+    let mut g = BBGraph::new();
+    polyline(&mut g, &circle_points(64, 50.), true);
+
+    // Every node turns by 2π / 64, more than the corner angle.
+    let report = g.simplify_with_corner_angle(0.5, 0.05).unwrap();
+    assert!(report.is_empty());
+    assert_eq!(g.edges_count(), 64);
+}
+
+#[test]
+fn already_simple() {
+    let mut g = BBGraph::new();
+    let (e0, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+    g.cubic(vec2(0., 10.), vec2(5., 20.), vec2(10., 0.), vec2(20., 10.));
+
+    assert!(g.simplify(0.1).unwrap().is_empty());
+    assert!(g.edge(e0).is_ok());
+    assert_eq!(g.edges_count(), 2);
+}
@@ -0,0 +1,182 @@
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+/// Builds a grid of horizontal and vertical lines that cross each other.
+fn lattice(lines: usize, spacing: f32) -> BBGraph {
+    let mut g = BBGraph::new();
+    let length = lines as f32 * spacing;
+    for i in 0..lines {
+        let offset = i as f32 * spacing + spacing / 2.;
+        g.line(vec2(offset, 0.), vec2(offset, length));
+        g.line(vec2(0., offset), vec2(length, offset));
+    }
+    g
+}
+
+fn with_index(mut g: BBGraph) -> BBGraph {
+    g.enable_spatial_index(10.);
+    g
+}
+
+mod queries {
+    use super::*;
+
+    #[test]
+    fn edges_near_point_matches_without_index() {
+        let g = lattice(5, 20.);
+        let indexed = with_index(g.clone());
+
+        for p in [
+            vec2(10., 3.),
+            vec2(31., 31.),
+            vec2(200., 200.),
+            vec2(20., 20.),
+        ] {
+            assert_eq!(g.edges_near_point(p, 2.), indexed.edges_near_point(p, 2.));
+        }
+        assert_eq!(indexed.edges_near_point(vec2(10., 3.), 2.).len(), 1);
+        assert_eq!(indexed.edges_near_point(vec2(20., 20.), 2.).len(), 0);
+    }
+
+    #[test]
+    fn edges_near_point_uses_curve_not_bounds() {
+        let mut g = BBGraph::new();
+        let (curve, _) = g.quadratic(vec2(0., 0.), vec2(50., 100.), vec2(100., 0.));
+        let g = with_index(g);
+
+        // Inside the control bounds but far from the curve itself.
+        assert!(g.edges_near_point(vec2(50., 90.), 5.).is_empty());
+        assert_eq!(g.edges_near_point(vec2(50., 50.), 1.), vec![curve]);
+    }
+
+    #[test]
+    fn nodes_in_rect_matches_without_index() {
+        let g = lattice(5, 20.);
+        let indexed = with_index(g.clone());
+
+        let (min, max) = (vec2(0., 0.), vec2(50., 5.));
+        let nodes = indexed.nodes_in_rect(min, max);
+        assert_eq!(nodes, g.nodes_in_rect(min, max));
+        // The start nodes of the first 3 vertical lines.
+        assert_eq!(nodes.len(), 3);
+    }
+
+    #[test]
+    fn broad_phase_pairs_match_without_index() {
+        let g = lattice(4, 20.);
+        let indexed = with_index(g.clone());
+
+        let pairs = indexed.broad_phase_edge_pairs();
+        assert_eq!(pairs, g.broad_phase_edge_pairs());
+        // Every horizontal line crosses every vertical line.
+        assert_eq!(pairs.len(), 16);
+    }
+}
+
+mod updates {
+    use super::*;
+
+    #[test]
+    fn deleted_edges_are_removed() {
+        let mut g = with_index(BBGraph::new());
+        let (edge, _) = g.line(vec2(0., 0.), vec2(100., 0.));
+        assert_eq!(g.edges_near_point(vec2(50., 0.), 1.), vec![edge]);
+
+        g.delete_edge(edge).unwrap();
+        assert!(g.edges_near_point(vec2(50., 0.), 1.).is_empty());
+        assert!(g.nodes_in_rect(vec2(-1., -1.), vec2(101., 1.)).is_empty());
+    }
+
+    #[test]
+    fn translate_moves_index() {
+        let mut g = with_index(BBGraph::new());
+        let (edge, _) = g.cubic(vec2(0., 0.), vec2(30., 30.), vec2(60., 30.), vec2(100., 0.));
+        let offset = vec2(500., 500.);
+        g.translate(offset);
+
+        let p = g.edge(edge).unwrap().t_point(&g, 0.5);
+        assert_eq!(g.edges_near_point(p, 1.), vec![edge]);
+        assert!(g.edges_near_point(p - offset, 1.).is_empty());
+        assert_eq!(
+            g.nodes_in_rect(offset - Vec2::ONE, offset + Vec2::ONE)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn node_mut_updates_index() {
+        let mut g = with_index(BBGraph::new());
+        let (edge, e) = g.line(vec2(0., 0.), vec2(100., 0.));
+        let (other, _) = g.line(vec2(50., 25.), vec2(50., 100.));
+        assert!(g.broad_phase_edge_pairs().is_empty());
+
+        // Move the end of the edge so that it crosses the other edge.
+        g.node_mut(e.end_idx()).unwrap().set_position(vec2(100., 100.));
+        assert_eq!(g.broad_phase_edge_pairs(), vec![(edge.min(other), edge.max(other))]);
+        assert!(g.edges_near_point(vec2(90., 0.), 1.).is_empty());
+        assert_eq!(g.edges_near_point(vec2(90., 90.), 1.), vec![edge]);
+
+        g.expand_intersections().unwrap();
+        assert_eq!(g.edges_count(), 4);
+    }
+
+    #[test]
+    fn edge_mut_updates_index() {
+        let mut g = with_index(BBGraph::new());
+        let (edge, _) = g.cubic(vec2(0., 0.), vec2(30., 0.), vec2(60., 0.), vec2(100., 0.));
+        if let BBEdge::Cubic { ctrl1, ctrl2, .. } = g.edge_mut(edge).unwrap() {
+            *ctrl1 = vec2(30., 100.);
+            *ctrl2 = vec2(60., 100.);
+        }
+
+        let p = g.edge(edge).unwrap().t_point(&g, 0.5);
+        assert_eq!(g.edges_near_point(p, 1.), vec![edge]);
+    }
+
+    #[test]
+    fn edits_are_applied_incrementally() {
+        let mut g = with_index(BBGraph::new());
+        let (_, e) = g.line(vec2(0., 0.), vec2(100., 0.));
+        let end = e.end_idx();
+        let near = |p: Vec2| (p - Vec2::ONE, p + Vec2::ONE);
+        let (min, max) = near(vec2(100., 0.));
+        assert_eq!(g.nodes_in_rect(min, max), vec![end]);
+
+        // Untracked changes aren't picked up until the index is marked as stale, so the index
+        // can't have been rebuilt by the tracked edits in between.
+        g.nodes
+            .get_mut(&end)
+            .unwrap()
+            .set_position(vec2(100., 100.));
+        let (_, other) = g.line(vec2(0., 50.), vec2(100., 50.));
+        g.translate(vec2(10., 0.));
+        let (min, max) = near(vec2(110., 0.));
+        assert_eq!(g.nodes_in_rect(min, max), vec![end]);
+        let (min, max) = near(vec2(110., 50.));
+        assert_eq!(g.nodes_in_rect(min, max), vec![other.end_idx()]);
+
+        g.mark_spatial_index_stale();
+        let (min, max) = near(vec2(110., 0.));
+        assert!(g.nodes_in_rect(min, max).is_empty());
+        let (min, max) = near(vec2(110., 100.));
+        assert_eq!(g.nodes_in_rect(min, max), vec![end]);
+    }
+
+    #[test]
+    fn expand_intersections_is_the_same_with_index() {
+        let mut g = lattice(4, 20.);
+        let mut indexed = with_index(g.clone());
+        g.expand_intersections().unwrap();
+        indexed.expand_intersections().unwrap();
+
+        assert_eq!(indexed.edges_count(), g.edges_count());
+        assert_eq!(indexed.nodes_count(), g.nodes_count());
+        // Newly split edges are indexed.
+        assert_eq!(indexed.edges_near_point(vec2(10., 5.), 1.).len(), 1);
+        assert_eq!(
+            indexed.edges_near_point(vec2(10., 5.), 1.),
+            g.edges_near_point(vec2(10., 5.), 1.)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+/// Returns the position of every node in the graph, sorted for comparison.
+fn node_positions(g: &BBGraph) -> Vec<Vec2> {
+    let mut positions: Vec<_> = g.nodes.values().map(|n| n.position()).collect();
+    positions.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    positions
+}
+
+fn error_offset(d: &str) -> usize {
+    match BBGraph::try_new_from_svg_path(d) {
+        Err(BBError::InvalidSvgPath(offset, _)) => offset,
+        other => panic!("Expected an InvalidSvgPath error, got {other:?}."),
+    }
+}
+
+mod commands {
+    use super::*;
+
+    #[test]
+    fn absolute_and_relative_lines() {
+        let absolute = BBGraph::try_new_from_svg_path("M0 0 L10 0 L10 10 L0 10 Z").unwrap();
+        let relative = BBGraph::try_new_from_svg_path("m0 0 h10 v10 h-10 z").unwrap();
+        let mixed = BBGraph::try_new_from_svg_path("M0,0 H10 V10 l-10,0 Z").unwrap();
+
+        for g in [&absolute, &relative, &mixed] {
+            assert_eq!(g.nodes_count(), 4);
+            assert_eq!(g.edges_count(), 4);
+            assert_eq!(
+                node_positions(g),
+                vec![vec2(0., 0.), vec2(0., 10.), vec2(10., 0.), vec2(10., 10.)]
+            );
+        }
+    }
+
+    #[test]
+    fn implicit_commands() {
+        let g = BBGraph::try_new_from_svg_path("M0,0 10,0 10,10").unwrap();
+        assert_eq!(g.edges_count(), 2);
+
+        let g = BBGraph::try_new_from_svg_path("M1.5.5L-2-3e1").unwrap();
+        assert_eq!(node_positions(&g), vec![vec2(-2., -30.), vec2(1.5, 0.5)]);
+    }
+
+    #[test]
+    fn smooth_cubic_reflects_control_point() {
+        let mut g = BBGraph::new();
+        let edges = g
+            .add_svg_path("M0 0 C0 10 10 10 10 0 s10 -10 10 0")
+            .unwrap();
+
+        match g.edge(edges[1]).unwrap() {
+            BBEdge::Cubic { ctrl1, ctrl2, .. } => {
+                assert_eq!(*ctrl1, vec2(10., -10.));
+                assert_eq!(*ctrl2, vec2(20., -10.));
+            }
+            edge => panic!("Expected a cubic, got {edge}."),
+        }
+    }
+
+    #[test]
+    fn smooth_quadratic_reflects_control_point() {
+        let mut g = BBGraph::new();
+        let edges = g.add_svg_path("M0 0 Q5 10 10 0 T20 0").unwrap();
+
+        match g.edge(edges[1]).unwrap() {
+            BBEdge::Quadratic { ctrl1, .. } => assert_eq!(*ctrl1, vec2(15., -10.)),
+            edge => panic!("Expected a quadratic, got {edge}."),
+        }
+        assert_eq!(g.edge(edges[1]).unwrap().end_pos(&g), vec2(20., 0.));
+    }
+
+    #[test]
+    fn arcs_become_cubics() {
+        let g =
+            BBGraph::try_new_from_svg_path("M10 0 A10 10 0 0 1 -10 0 A10 10 0 0 1 10 0 Z").unwrap();
+
+        assert_eq!(g.edges_count(), 4);
+        assert_eq!(g.nodes_count(), 4);
+        for edge in g.edges.values() {
+            assert!(matches!(edge, BBEdge::Cubic { .. }));
+            for i in 0..=10 {
+                let distance = edge.t_point(&g, i as f32 / 10.).length();
+                assert!(
+                    (distance - 10.).abs() < 0.01,
+                    "Point was {distance} from center."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn arc_flags_without_separators() {
+        let mut g = BBGraph::new();
+        let edges = g.add_svg_path("M0 0a5 5 0 1110 0").unwrap();
+
+        let last = g.edge(*edges.last().unwrap()).unwrap();
+        assert!(last.end_pos(&g).distance(vec2(10., 0.)) < 0.0001);
+    }
+
+    #[test]
+    fn arc_radii_are_scaled_up() {
+        let g = BBGraph::try_new_from_svg_path("M0 0 A1 1 0 0 1 10 0").unwrap();
+
+        for edge in g.edges.values() {
+            let distance = edge.t_point(&g, 0.5).distance(vec2(5., 0.));
+            assert!(
+                (distance - 5.).abs() < 0.01,
+                "Point was {distance} from center."
+            );
+        }
+    }
+}
+
+mod welding {
+    use super::*;
+
+    #[test]
+    fn coincident_end_is_welded() {
+        let g = BBGraph::try_new_from_svg_path("M0 0 L10 0 L10 10 L0 0").unwrap();
+
+        assert_eq!(g.nodes_count(), 3);
+        assert_eq!(g.edges_count(), 3);
+    }
+
+    #[test]
+    fn close_after_coincident_end() {
+        let g = BBGraph::try_new_from_svg_path("M0 0 L10 0 Q10 10 0 0 Z").unwrap();
+
+        assert_eq!(g.nodes_count(), 2);
+        assert_eq!(g.edges_count(), 2);
+    }
+
+    #[test]
+    fn open_subpath_is_not_welded() {
+        let g = BBGraph::try_new_from_svg_path("M0 0 L10 0 L10 10").unwrap();
+
+        assert_eq!(g.nodes_count(), 3);
+        assert_eq!(g.edges_count(), 2);
+    }
+
+    #[test]
+    fn multiple_subpaths() {
+        let mut g =
+            BBGraph::try_new_from_svg_path("M0 0 h20 v20 h-20 z M5 5 h10 v10 h-10 z").unwrap();
+
+        assert_eq!(g.nodes_count(), 8);
+        assert_eq!(g.edges_count(), 8);
+        assert_eq!(g.update_regions().unwrap().len(), 2);
+    }
+}
+
+mod errors {
+    use super::*;
+
+    #[test]
+    fn must_start_with_move() {
+        assert_eq!(error_offset("L10 10"), 0);
+        assert_eq!(error_offset("  10 10"), 2);
+    }
+
+    #[test]
+    fn character_offset() {
+        assert_eq!(error_offset("M0 0 L10 x"), 9);
+        assert_eq!(error_offset("M0 0 K"), 5);
+        assert_eq!(error_offset("M0 0 A5 5 0 2 0 10 0"), 12);
+        // Offsets count characters, not bytes.
+        assert_eq!(error_offset("M0 0 L é"), 7);
+    }
+
+    #[test]
+    fn graph_is_unchanged() {
+        let mut g = BBGraph::try_new_from_svg_path("M0 0 L10 0").unwrap();
+
+        assert!(g.add_svg_path("M20 20 L30 30 L").is_err());
+        assert_eq!(g.nodes_count(), 2);
+        assert_eq!(g.edges_count(), 1);
+    }
+}
+
+/// Compares `svg` with the golden file `tests/svgs/{name}.svg`, creating the golden file if it
+/// doesn't exist.  On a mismatch the new output is saved to `tests/svgs/{name}.failed.svg`.
+fn assert_golden(name: &str, svg: &str) {
+    let path = format!("./tests/svgs/{name}.svg");
+    let Ok(golden) = std::fs::read_to_string(&path) else {
+        std::fs::create_dir_all("./tests/svgs").unwrap();
+        std::fs::write(&path, svg).unwrap();
+        return;
+    };
+    if golden != svg {
+        std::fs::write(format!("./tests/svgs/{name}.failed.svg"), svg).unwrap();
+        panic!("SVG output doesn't match {path}.");
+    }
+}
+
+mod export {
+    use super::*;
+
+    #[test]
+    fn path_data() {
+        let mut g = BBGraph::new();
+        let (e0, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        let (e1, e) = g.quadratic_from(first.end_idx(), vec2(15., 5.), vec2(20., 0.));
+        let (e2, _) = g.cubic_from(
+            e.end_idx(),
+            vec2(20., 1. / 3.),
+            vec2(-0.0001, 20.),
+            vec2(30., 0.),
+        );
+
+        assert_eq!(
+            g.svg_path_data(&[e0, e1, e2]).unwrap(),
+            "M0 0 L10 0 Q15 5 20 0 C20 0.333 0 20 30 0"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let d = "M0 0 L10 0 Q15 5 20 0 C20 10 0 20 0 10 Z";
+        let mut g = BBGraph::try_new_from_svg_path(d).unwrap();
+        let region_idx = g.update_regions().unwrap()[0];
+        let edges = g.region(region_idx).unwrap().root_cycle.edges.clone();
+
+        let exported = g.svg_path_data(&edges).unwrap();
+        let round_tripped = BBGraph::try_new_from_svg_path(&exported).unwrap();
+        assert_eq!(round_tripped.edges_count(), 4);
+        assert_eq!(node_positions(&round_tripped), node_positions(&g));
+    }
+
+    #[test]
+    fn filled_square() {
+        let mut g = BBGraph::try_new_from_svg_path("M0 0 h10 v10 h-10 z").unwrap();
+        g.update_regions().unwrap();
+
+        assert_golden("filled_square", &g.to_svg().unwrap());
+    }
+
+    #[test]
+    fn square_with_hole() {
+        let mut g =
+            BBGraph::try_new_from_svg_path("M0 0 h20 v20 h-20 z M5 5 h10 v10 h-10 z").unwrap();
+        g.update_regions().unwrap();
+
+        assert_golden("square_with_hole", &g.to_svg().unwrap());
+    }
+
+    #[test]
+    fn strokes() {
+        // A branching polyline and a curve, neither of which enclose a region.
+        let mut g = BBGraph::new();
+        let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.line_from(first.end_idx(), vec2(20., 0.));
+        g.line_from(first.end_idx(), vec2(10., 10.));
+        g.quadratic(vec2(30., 0.), vec2(35., 10.), vec2(40., 0.));
+        g.update_regions().unwrap();
+
+        assert_golden("strokes", &g.to_svg().unwrap());
+    }
+
+    #[test]
+    fn fill_rule() {
+        let mut g = BBGraph::try_new_from_svg_path("M0 0 h10 v10 h-10 z").unwrap();
+        let region_idx = g.update_regions().unwrap()[0];
+        assert!(g.to_svg().unwrap().contains(r#"fill-rule="evenodd""#));
+
+        g.set_region_winding_rule(region_idx, BBWindingRule::NonZero)
+            .unwrap();
+        assert!(g.to_svg().unwrap().contains(r#"fill-rule="nonzero""#));
+    }
+
+    #[test]
+    fn empty_graph() {
+        assert_eq!(
+            BBGraph::new().to_svg().unwrap(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 0 0\">\n</svg>\n"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::f32::consts::FRAC_PI_4;
+
+use bb_vector_network::prelude::*;
+use glam::{vec2, Affine2, Mat2, Mat3, Vec2};
+
+/// Builds a square with one curved side and returns its region.
+fn curved_square(g: &mut BBGraph, offset: Vec2) -> (Vec<BBEdgeIndex>, BBRegionIndex) {
+    let (e0, first) = g.line(offset, offset + vec2(10., 0.));
+    let (e1, e) = g.cubic_from(
+        first.end_idx(),
+        offset + vec2(15., 3.),
+        offset + vec2(15., 7.),
+        offset + vec2(10., 10.),
+    );
+    let (e2, e) = g.quadratic_from(e.end_idx(), offset + vec2(5., 12.), offset + vec2(0., 10.));
+    let (e3, _) = g.line_from_to(e.end_idx(), first.start_idx());
+    let region_idx = g
+        .update_regions()
+        .unwrap()
+        .into_iter()
+        .find(|idx| g.region(*idx).unwrap().root_cycle.edges.contains(&e0))
+        .unwrap();
+    (vec![e0, e1, e2, e3], region_idx)
+}
+
+/// Signed area of the region's root cycle, counterclockwise is positive.
+fn root_cycle_area(g: &BBGraph, region_idx: BBRegionIndex) -> f32 {
+    let polyline = g.flatten_region(region_idx, 0.1).unwrap().remove(0);
+    let positions: Vec<_> = polyline.positions().collect();
+    positions
+        .iter()
+        .zip(positions.iter().cycle().skip(1))
+        .map(|(a, b)| a.determinate(*b))
+        .sum::<f32>()
+        / 2.
+}
+
+/// Checks that transforming the graph gives the same curves as transforming points on them.
+fn assert_curves_transformed(before: &BBGraph, after: &BBGraph, transform: Affine2) {
+    for (edge_idx, edge) in before.edges.iter() {
+        let transformed = after.edge(*edge_idx).unwrap();
+        for i in 0..=10 {
+            let t = i as f32 / 10.;
+            let expected = transform.transform_point2(edge.t_point(before, t));
+            let actual = transformed.t_point(after, t);
+            assert!(
+                expected.distance(actual) < 0.001,
+                "{actual} should be {expected}."
+            );
+        }
+    }
+}
+
+mod transform {
+    use super::*;
+
+    #[test]
+    fn scale_and_skew() {
+        let mut g = BBGraph::new();
+        curved_square(&mut g, Vec2::ZERO);
+        let before = g.clone();
+
+        let transform = Affine2::from_scale(vec2(2., 0.5))
+            * Affine2::from_mat2(Mat2::from_cols(vec2(1., 0.), vec2(0.5, 1.)))
+            * Affine2::from_angle_translation(FRAC_PI_4, vec2(3., -4.));
+        g.transform(transform);
+
+        assert_curves_transformed(&before, &g, transform);
+    }
+
+    #[test]
+    fn from_mat3() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.quadratic(vec2(0., 0.), vec2(5., 5.), vec2(10., 0.));
+
+        g.transform(Affine2::from_mat3(Mat3::from_scale(vec2(3., 3.))));
+        let edge = g.edge(e0).unwrap();
+        assert_eq!(edge.end_pos(&g), vec2(30., 0.));
+        assert!(matches!(edge, BBEdge::Quadratic { ctrl1, .. } if *ctrl1 == vec2(15., 15.)));
+    }
+
+    #[test]
+    fn reflection_keeps_cycle_orientation() {
+        let mut g = BBGraph::new();
+        let (_, region_idx) = curved_square(&mut g, Vec2::ZERO);
+        let area = root_cycle_area(&g, region_idx);
+        let cycle = g.region(region_idx).unwrap().root_cycle.edges.clone();
+
+        g.transform(Affine2::from_scale(vec2(-1., 1.)));
+
+        let mut reversed = cycle.clone();
+        reversed.reverse();
+        assert_eq!(g.region(region_idx).unwrap().root_cycle.edges, reversed);
+        assert!((root_cycle_area(&g, region_idx) - area).abs() < 0.01);
+        assert!(g.fill_cycles().unwrap().len() == 1);
+    }
+
+    #[test]
+    fn rotation_keeps_cycles() {
+        let mut g = BBGraph::new();
+        let (_, region_idx) = curved_square(&mut g, Vec2::ZERO);
+        let cycle = g.region(region_idx).unwrap().root_cycle.edges.clone();
+
+        g.transform(Affine2::from_angle(1.));
+        assert_eq!(g.region(region_idx).unwrap().root_cycle.edges, cycle);
+    }
+
+    #[test]
+    fn updates_spatial_index() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.enable_spatial_index(5.);
+
+        g.transform(Affine2::from_translation(vec2(0., 100.)));
+        assert!(g.edges_near_point(vec2(5., 0.), 1.).is_empty());
+        assert_eq!(g.edges_near_point(vec2(5., 100.), 1.), vec![e0]);
+    }
+}
+
+mod transform_subset {
+    use super::*;
+
+    #[test]
+    fn only_moves_subset() {
+        let mut g = BBGraph::new();
+        let (edges, _) = curved_square(&mut g, Vec2::ZERO);
+        curved_square(&mut g, vec2(100., 0.));
+        let before = g.clone();
+
+        let transform = Affine2::from_scale_angle_translation(vec2(2., 3.), 0.5, vec2(1., 1.));
+        g.transform_subset(&edges, transform).unwrap();
+
+        for (edge_idx, edge) in before.edges.iter() {
+            let after = g.edge(*edge_idx).unwrap();
+            for t in [0., 0.3, 1.] {
+                let expected = match edges.contains(edge_idx) {
+                    true => transform.transform_point2(edge.t_point(&before, t)),
+                    false => edge.t_point(&before, t),
+                };
+                assert!(expected.distance(after.t_point(&g, t)) < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn reflection_only_reverses_transformed_regions() {
+        let mut g = BBGraph::new();
+        let (edges, region_a) = curved_square(&mut g, Vec2::ZERO);
+        let (_, region_b) = curved_square(&mut g, vec2(100., 0.));
+        let cycle_a = g.region(region_a).unwrap().root_cycle.edges.clone();
+        let cycle_b = g.region(region_b).unwrap().root_cycle.edges.clone();
+
+        g.transform_subset(&edges, Affine2::from_scale(vec2(1., -1.)))
+            .unwrap();
+
+        let mut reversed = cycle_a.clone();
+        reversed.reverse();
+        assert_eq!(g.region(region_a).unwrap().root_cycle.edges, reversed);
+        assert_eq!(g.region(region_b).unwrap().root_cycle.edges, cycle_b);
+    }
+
+    #[test]
+    fn missing_edge() {
+        let mut g = BBGraph::new();
+        let (e0, _) = g.line(vec2(0., 0.), vec2(10., 0.));
+        g.delete_edge(e0).unwrap();
+
+        assert!(g.transform_subset(&[e0], Affine2::IDENTITY).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::shapes::triangle;
+
+/// A closed triangle with its regions calculated.
+fn filled_triangle() -> (BBGraph, Vec<BBEdgeIndex>) {
+    let mut g = BBGraph::new();
+    let edges = triangle(&mut g, Vec2::ZERO);
+    g.update_regions().unwrap();
+    (g, edges)
+}
+
+#[test]
+fn valid_graph() {
+    let (g, _) = filled_triangle();
+    assert_eq!(g.validate(), Ok(()));
+
+    let mut g = BBGraph::new();
+    let (_, first) = g.line(vec2(0., 0.), vec2(10., 0.));
+    // A curve that loops back to its own node is still valid.
+    g.cubic_from_to(
+        first.end_idx(),
+        vec2(20., 10.),
+        vec2(20., -10.),
+        first.end_idx(),
+    );
+    assert_eq!(g.validate(), Ok(()));
+}
+
+#[test]
+fn missing_node() {
+    let (mut g, edges) = filled_triangle();
+    let node_idx = g.edge(edges[0]).unwrap().start_idx();
+    g.nodes.remove(&node_idx);
+
+    let defects = g.validate().unwrap_err();
+    assert!(defects.contains(&BBGraphDefect::MissingNode {
+        edge_idx: edges[0],
+        node_idx
+    }));
+    assert!(defects.contains(&BBGraphDefect::MissingNode {
+        edge_idx: edges[2],
+        node_idx
+    }));
+}
+
+#[test]
+fn adjacents_mismatch() {
+    let (mut g, edges) = filled_triangle();
+    let node_idx = g.edge(edges[0]).unwrap().start_idx();
+    let apex_idx = g.edge(edges[1]).unwrap().end_idx();
+    // Replace the node with a copy of the apex, which lists edges 1 and 2 instead of 0 and 2.
+    let position = g.node(node_idx).unwrap().position();
+    let mut apex = g.node(apex_idx).unwrap().clone();
+    apex.set_position(position);
+    g.nodes.insert(node_idx, apex);
+
+    let defects = g.validate().unwrap_err();
+    assert_eq!(
+        defects,
+        vec![
+            BBGraphDefect::AdjacentMismatch {
+                node_idx,
+                edge_idx: edges[0],
+                expected: 1,
+                found: 0
+            },
+            BBGraphDefect::AdjacentMismatch {
+                node_idx,
+                edge_idx: edges[1],
+                expected: 0,
+                found: 1
+            },
+        ]
+    );
+}
+
+#[test]
+fn missing_adjacent_edge() {
+    let (mut g, edges) = filled_triangle();
+    let node_idx = g.edge(edges[0]).unwrap().end_idx();
+    let removed = g.edges.remove(&edges[0]).unwrap();
+
+    let defects = g.validate().unwrap_err();
+    assert!(defects.contains(&BBGraphDefect::MissingAdjacentEdge {
+        node_idx,
+        edge_idx: edges[0]
+    }));
+    assert!(defects.contains(&BBGraphDefect::MissingAdjacentEdge {
+        node_idx: removed.start_idx(),
+        edge_idx: edges[0]
+    }));
+    // The region still references the edge.
+    assert!(defects.iter().any(
+        |d| matches!(d, BBGraphDefect::MissingRegionEdge { edge_idx, .. } if *edge_idx == edges[0])
+    ));
+}
+
+#[test]
+fn zero_length_edge() {
+    let mut g = BBGraph::new();
+    let (e0, _) = g.line(vec2(5., 5.), vec2(5., 5.));
+
+    assert_eq!(g.validate(), Err(vec![BBGraphDefect::ZeroLengthEdge(e0)]));
+}
+
+#[test]
+fn open_cycle() {
+    let (mut g, edges) = filled_triangle();
+    let region_idx = *g.regions.keys().next().unwrap();
+    let cycle = &mut g.regions.get_mut(&region_idx).unwrap().root_cycle;
+    cycle.edges.retain(|idx| *idx != edges[1]);
+
+    let defects = g.validate().unwrap_err();
+    assert_eq!(defects.len(), 1);
+    assert!(matches!(
+        &defects[0],
+        BBGraphDefect::OpenCycle { region_idx: idx, .. } if *idx == region_idx
+    ));
+}
+
+#[test]
+fn defect_messages() {
+    let mut g = BBGraph::new();
+    let (e0, _) = g.line(vec2(5., 5.), vec2(5., 5.));
+    let defects = g.validate().unwrap_err();
+
+    assert_eq!(
+        defects[0].to_string(),
+        format!("Edge {e0} has zero length.")
+    );
+}
@@ -0,0 +1,166 @@
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+mod common;
+use crate::common::shapes::{polyline, rect};
+
+/// Sets the winding rule of every region in the graph.
+fn set_winding_rule(g: &mut BBGraph, winding_rule: BBWindingRule) {
+    let region_indices: Vec<_> = g.regions.keys().copied().collect();
+    for region_idx in region_indices {
+        g.set_region_winding_rule(region_idx, winding_rule).unwrap();
+    }
+}
+
+/// Returns whether the smallest face that contains `p` is filled.  Only supports line edges.
+fn face_filled_at(g: &BBGraph, p: Vec2) -> bool {
+    let mut faces: Vec<(f32, bool)> = g
+        .regions
+        .values()
+        .flat_map(|region| region.root_cycle.faces())
+        .filter_map(|face| {
+            let polygon: Vec<Vec2> = g
+                .edges_directed(&face.edges)
+                .unwrap()
+                .iter()
+                .map(|(_, edge)| edge.start_pos(g))
+                .collect();
+            let segments = polygon.iter().zip(polygon.iter().cycle().skip(1));
+
+            let mut inside = false;
+            let mut area = 0.;
+            for (a, b) in segments {
+                if (a.y > p.y) != (b.y > p.y) && a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) > p.x {
+                    inside = !inside;
+                }
+                area += a.x * b.y - a.y * b.x;
+            }
+            inside.then_some((area.abs(), face.filled))
+        })
+        .collect();
+    faces.sort_by(|a, b| a.0.total_cmp(&b.0));
+    faces.first().expect("No face contains point.").1
+}
+
+mod nested_rectangles {
+    use super::*;
+
+    fn build(inner_ccw: bool) -> BBGraph {
+        let mut g = BBGraph::new();
+        rect(&mut g, vec2(0., 0.), vec2(20., 20.));
+        if inner_ccw {
+            rect(&mut g, vec2(5., 5.), vec2(15., 15.));
+        } else {
+            // The corners of the ccw rect in reverse.
+            let corners = [vec2(5., 15.), vec2(15., 15.), vec2(15., 5.), vec2(5., 5.)];
+            polyline(&mut g, &corners, true);
+        }
+        g.update_regions().unwrap();
+        g
+    }
+
+    #[test]
+    fn even_odd_makes_a_hole() {
+        for inner_ccw in [true, false] {
+            let g = build(inner_ccw);
+            assert!(face_filled_at(&g, vec2(1., 1.)));
+            assert!(!face_filled_at(&g, vec2(10., 10.)));
+        }
+    }
+
+    #[test]
+    fn non_zero_fills_same_direction() {
+        let mut g = build(true);
+        set_winding_rule(&mut g, BBWindingRule::NonZero);
+        assert!(face_filled_at(&g, vec2(1., 1.)));
+        assert!(face_filled_at(&g, vec2(10., 10.)));
+    }
+
+    #[test]
+    fn non_zero_cuts_opposite_direction() {
+        let mut g = build(false);
+        set_winding_rule(&mut g, BBWindingRule::NonZero);
+        assert!(face_filled_at(&g, vec2(1., 1.)));
+        assert!(!face_filled_at(&g, vec2(10., 10.)));
+    }
+
+    #[test]
+    fn fill_cycles_skips_unfilled_faces() {
+        let mut g = build(true);
+        assert_eq!(g.fill_cycles().unwrap().len(), 2);
+
+        set_winding_rule(&mut g, BBWindingRule::NonZero);
+        // The inner face is covered by the outer one.
+        assert_eq!(g.fill_cycles().unwrap().len(), 1);
+    }
+}
+
+mod overlapping_rectangles {
+    use super::*;
+
+    fn build() -> BBGraph {
+        let mut g = BBGraph::new();
+        rect(&mut g, vec2(0., 0.), vec2(10., 10.));
+        rect(&mut g, vec2(5., 5.), vec2(15., 15.));
+        g.expand_intersections().unwrap();
+        g.update_regions().unwrap();
+        g
+    }
+
+    #[test]
+    fn even_odd_leaves_overlap_empty() {
+        let g = build();
+        assert!(face_filled_at(&g, vec2(2., 2.)));
+        assert!(!face_filled_at(&g, vec2(7.5, 7.5)));
+        assert!(face_filled_at(&g, vec2(13., 13.)));
+    }
+
+    #[test]
+    fn non_zero_fills_overlap() {
+        let mut g = build();
+        set_winding_rule(&mut g, BBWindingRule::NonZero);
+        assert!(face_filled_at(&g, vec2(2., 2.)));
+        assert!(face_filled_at(&g, vec2(7.5, 7.5)));
+        assert!(face_filled_at(&g, vec2(13., 13.)));
+    }
+}
+
+mod large_curves {
+    use super::*;
+
+    /// Adds a closed circle approximated by 4 cubic edges, wound counterclockwise.  Returns the
+    /// first edge.
+    fn circle(g: &mut BBGraph, radius: f32) -> BBEdge {
+        let k = 0.552_284_8 * radius;
+        let right = vec2(radius, 0.);
+        let top = vec2(0., radius);
+        let (_, first) = g.cubic(right, right + vec2(0., k), top + vec2(k, 0.), top);
+        let (_, e) = g.cubic_from(first.end_idx(), top - vec2(k, 0.), -right + vec2(0., k), -right);
+        let (_, e) = g.cubic_from(e.end_idx(), -right - vec2(0., k), -top - vec2(k, 0.), -top);
+        g.cubic_from_to(e.end_idx(), -top + vec2(k, 0.), right - vec2(0., k), first.start_idx());
+        first
+    }
+
+    #[test]
+    fn even_odd_fills_thin_ring() {
+        // The ring is thinner than the error of flattening the circles into a fixed number of
+        // segments.
+        let mut g = BBGraph::new();
+        let outer = circle(&mut g, 1_000.);
+        circle(&mut g, 999.75);
+        g.update_regions().unwrap();
+
+        for t in [0.1, 0.3, 0.5, 0.7, 0.9] {
+            let p = outer.t_point(&g, t) * 0.999_875;
+            assert!(g.region_at_point(p).unwrap().is_some(), "Ring not filled at {p}.");
+        }
+        assert!(g.region_at_point(vec2(0., 0.)).unwrap().is_none());
+    }
+}
+
+#[test]
+fn set_winding_rule_of_missing_region() {
+    let mut g = BBGraph::new();
+    let result = g.set_region_winding_rule(BBRegionIndex::new(100, 0), BBWindingRule::NonZero);
+    assert!(matches!(result, Err(BBError::MissingRegion(_))));
+}
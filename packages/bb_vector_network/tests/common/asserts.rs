@@ -0,0 +1,36 @@
+//! Assertions shared by the graph tests.
+#![allow(dead_code)]
+
+use bb_vector_network::prelude::*;
+use glam::Vec2;
+
+pub fn assert_near(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{actual} should be {expected}."
+    );
+}
+
+pub fn assert_near_v2(actual: Vec2, expected: Vec2, tolerance: f32) {
+    assert!(
+        actual.distance(expected) <= tolerance,
+        "{actual} should be {expected}."
+    );
+}
+
+/// Checks that every point is within `tolerance` of one of `edges`.
+pub fn assert_near_edges(g: &BBGraph, edges: &[BBEdgeIndex], points: &[Vec2], tolerance: f32) {
+    for p in points {
+        let distance = edges
+            .iter()
+            .map(|idx| g.edge(*idx).unwrap().closest_point(g, *p).2)
+            .fold(f32::INFINITY, f32::min);
+        assert!(distance <= tolerance, "{p} is {distance} from the edges.");
+    }
+}
+
+/// Checks that every point is within `tolerance` of an edge in the graph.
+pub fn assert_within_tolerance(g: &BBGraph, points: &[Vec2], tolerance: f32) {
+    let edges: Vec<_> = g.edges.keys().copied().collect();
+    assert_near_edges(g, &edges, points, tolerance);
+}
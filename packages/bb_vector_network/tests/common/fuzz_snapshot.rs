@@ -42,7 +42,7 @@ impl FuzzSnapshot {
 
         let mut node_idxes = vec![];
         for i in 0..graph.nodes_count() {
-            node_idxes.push(BBNodeIndex(i));
+            node_idxes.push(BBNodeIndex::new(i, 0));
         }
         for id in node_idxes {
             let Ok(node) = graph.node_mut(id) else {
@@ -55,7 +55,7 @@ impl FuzzSnapshot {
 
         let mut edge_idxes = vec![];
         for i in 0..graph.edges_count() {
-            edge_idxes.push(BBEdgeIndex(i));
+            edge_idxes.push(BBEdgeIndex::new(i, 0));
         }
         for id in edge_idxes {
             let Ok(edge) = graph.edge_mut(id) else {
@@ -141,7 +141,7 @@ impl FuzzSnapshot {
                 g.edges.insert(*e, edge.reversed());
             }
             g.update_regions().unwrap();
-            let key = case.iter().map(|v| format!("{}", v.0)).join("-");
+            let key = case.iter().map(|v| format!("{}", v.slot())).join("-");
             let did_pass = (test)(key.clone(), g);
             (key, did_pass)
         });
@@ -190,7 +190,7 @@ impl FuzzSnapshot {
             for e in case {
                 let _ = g.delete_edge(*e);
             }
-            let key = case.iter().map(|v| format!("{}", v.0)).join("-");
+            let key = case.iter().map(|v| format!("{}", v.slot())).join("-");
             let did_pass = (test)(key.clone(), g);
             (key, did_pass)
         });
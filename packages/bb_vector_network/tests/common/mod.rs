@@ -1,2 +1,4 @@
+pub mod asserts;
 pub mod draw;
 pub mod fuzz_snapshot;
+pub mod shapes;
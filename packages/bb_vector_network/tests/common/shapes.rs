@@ -0,0 +1,64 @@
+//! Shapes shared by the graph tests, built with the graph building API.
+#![allow(dead_code)]
+
+use bb_vector_network::prelude::*;
+use glam::{vec2, Vec2};
+
+/// Adds a polyline of line edges through `points`, closing it if `closed`.  Returns the node at
+/// each point.
+pub fn polyline(g: &mut BBGraph, points: &[Vec2], closed: bool) -> Vec<BBNodeIndex> {
+    let (_, first) = g.line(points[0], points[1]);
+    let mut nodes = vec![first.start_idx(), first.end_idx()];
+    for p in &points[2..] {
+        let (_, edge) = g.line_from(*nodes.last().unwrap(), *p);
+        nodes.push(edge.end_idx());
+    }
+    if closed {
+        g.line_from_to(*nodes.last().unwrap(), nodes[0]);
+    }
+    nodes
+}
+
+/// Adds a closed rectangle of line edges to the graph, wound counterclockwise starting at `min`.
+/// Returns the node at each corner.
+pub fn rect(g: &mut BBGraph, min: Vec2, max: Vec2) -> Vec<BBNodeIndex> {
+    polyline(g, &[min, vec2(max.x, min.y), max, vec2(min.x, max.y)], true)
+}
+
+/// Adds a closed square of line edges to the graph, see [rect].
+pub fn square(g: &mut BBGraph, min: Vec2, size: f32) -> Vec<BBNodeIndex> {
+    rect(g, min, min + Vec2::splat(size))
+}
+
+/// Adds a closed triangle of line edges to the graph, returning the edges.
+pub fn triangle(g: &mut BBGraph, offset: Vec2) -> Vec<BBEdgeIndex> {
+    let (e0, first) = g.line(offset, offset + vec2(10., 0.));
+    let (e1, e) = g.line_from(first.end_idx(), offset + vec2(5., 10.));
+    let (e2, _) = g.line_from_to(e.end_idx(), first.start_idx());
+    vec![e0, e1, e2]
+}
+
+/// Adds a closed circle approximated by 4 cubic edges to the graph, returning the edges.
+pub fn circle(g: &mut BBGraph, center: Vec2, radius: f32) -> Vec<BBEdgeIndex> {
+    let k = 0.552_284_8 * radius;
+    let right = center + vec2(radius, 0.);
+    let top = center + vec2(0., radius);
+    let left = center - vec2(radius, 0.);
+    let bottom = center - vec2(0., radius);
+
+    let (e0, first) = g.cubic(right, right + vec2(0., k), top + vec2(k, 0.), top);
+    let (e1, e) = g.cubic_from(first.end_idx(), top - vec2(k, 0.), left + vec2(0., k), left);
+    let (e2, e) = g.cubic_from(
+        e.end_idx(),
+        left - vec2(0., k),
+        bottom - vec2(k, 0.),
+        bottom,
+    );
+    let (e3, _) = g.cubic_from_to(
+        e.end_idx(),
+        bottom + vec2(k, 0.),
+        right - vec2(0., k),
+        first.start_idx(),
+    );
+    vec![e0, e1, e2, e3]
+}
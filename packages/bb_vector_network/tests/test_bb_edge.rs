@@ -3,18 +3,18 @@ mod directed_from {
     use bb_vector_network::prelude::*;
     #[test]
     fn unchanged() {
-        let edge = BBEdge::Line { start: BBNodeIndex(0), end: BBNodeIndex(1) };
+        let edge = BBEdge::Line { start: BBNodeIndex::new(0, 0), end: BBNodeIndex::new(1, 0) };
 
-        let result = edge.directed_from(BBNodeIndex(0));
+        let result = edge.directed_from(BBNodeIndex::new(0, 0));
         assert_eq!(edge.start_idx(), result.start_idx());
         assert_eq!(edge.end_idx(), result.end_idx());
     }
 
     #[test]
     fn needs_flip() {
-        let edge = BBEdge::Line { start: BBNodeIndex(0), end: BBNodeIndex(1) };
+        let edge = BBEdge::Line { start: BBNodeIndex::new(0, 0), end: BBNodeIndex::new(1, 0) };
 
-        let result = edge.directed_from(BBNodeIndex(1));
+        let result = edge.directed_from(BBNodeIndex::new(1, 0));
         assert_eq!(edge.start_idx(), result.end_idx());
         assert_eq!(edge.end_idx(), result.start_idx());
     }
@@ -76,3 +76,138 @@ mod calc_end_tangent {
         assert_eq!(e.calc_end_tangent(&g).unwrap(), vec2(-1., 0.))
     }
 }
+
+mod closest_point {
+    use glam::vec2;
+    use bb_vector_network::prelude::*;
+
+    #[test]
+    fn line() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.line(vec2(0., 0.), vec2(10., 0.));
+
+        let (t, point, distance) = e.closest_point(&g, vec2(4., 3.));
+        assert_eq!(t, 0.4);
+        assert_eq!(point, vec2(4., 0.));
+        assert_eq!(distance, 3.);
+    }
+
+    #[test]
+    fn line_past_end() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.line(vec2(0., 0.), vec2(10., 0.));
+
+        let (t, point, _) = e.closest_point(&g, vec2(-5., 1.));
+        assert_eq!((t, point), (0., vec2(0., 0.)));
+        let (t, point, _) = e.closest_point(&g, vec2(15., 1.));
+        assert_eq!((t, point), (1., vec2(10., 0.)));
+    }
+
+    #[test]
+    fn quadratic() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.quadratic(vec2(0., 0.), vec2(50., 100.), vec2(100., 0.));
+
+        let (t, point, distance) = e.closest_point(&g, vec2(50., 80.));
+        assert!((t - 0.5).abs() < 0.001, "t was {t}");
+        assert!(point.distance(vec2(50., 50.)) < 0.01);
+        assert!((distance - 30.).abs() < 0.01);
+    }
+
+    #[test]
+    fn cubic_near_end_points() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.cubic(vec2(0., 0.), vec2(0., 50.), vec2(100., 50.), vec2(100., 0.));
+
+        let (t, point, _) = e.closest_point(&g, vec2(-10., -10.));
+        assert_eq!((t, point), (0., vec2(0., 0.)));
+        let (t, point, _) = e.closest_point(&g, vec2(110., -10.));
+        assert_eq!((t, point), (1., vec2(100., 0.)));
+    }
+
+    #[test]
+    fn cubic_picks_closest_of_multiple_minima() {
+        let mut g = BBGraph::new();
+        // An S curve that passes near the point twice.
+        let (_, e) = g.cubic(vec2(0., 0.), vec2(100., 100.), vec2(-100., 100.), vec2(0., 0.1));
+
+        let p = vec2(10., 40.);
+        let (t, point, distance) = e.closest_point(&g, p);
+        let brute_force = (0..=10000)
+            .map(|i| e.t_point(&g, i as f32 / 10000.).distance(p))
+            .fold(f32::MAX, f32::min);
+        assert!((distance - brute_force).abs() < 0.01);
+        assert_eq!(point, e.t_point(&g, t));
+    }
+
+    #[test]
+    fn degenerate_curves() {
+        let mut g = BBGraph::new();
+        let (_, point_line) = g.line(vec2(5., 5.), vec2(5., 5.));
+        let (_, point_cubic) = g.cubic(vec2(5., 5.), vec2(5., 5.), vec2(5., 5.), vec2(5., 5.));
+
+        for e in [point_line, point_cubic] {
+            let (t, point, distance) = e.closest_point(&g, vec2(8., 9.));
+            assert!(t.is_finite());
+            assert_eq!(point, vec2(5., 5.));
+            assert_eq!(distance, 5.);
+        }
+    }
+}
+
+mod closest_edge {
+    use glam::vec2;
+    use bb_vector_network::prelude::*;
+
+    #[test]
+    fn picks_closest() {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(100., 0.));
+        let (expected, _) = g.quadratic(vec2(0., 10.), vec2(50., 30.), vec2(100., 10.));
+
+        let (edge_idx, _, point, distance) = g.closest_edge(vec2(50., 18.), 50.).unwrap();
+        assert_eq!(edge_idx, expected);
+        assert!(point.distance(vec2(50., 20.)) < 0.01);
+        assert!((distance - 2.).abs() < 0.01);
+    }
+
+    #[test]
+    fn respects_max_distance() {
+        let mut g = BBGraph::new();
+        g.line(vec2(0., 0.), vec2(100., 0.));
+
+        assert!(g.closest_edge(vec2(50., 10.), 5.).is_none());
+        g.enable_spatial_index(20.);
+        assert!(g.closest_edge(vec2(50., 10.), 5.).is_none());
+        assert!(g.closest_edge(vec2(50., 10.), 15.).is_some());
+    }
+}
+
+mod as_curve {
+    use bb_vector_network::{bb_edge::c2_to_v2, prelude::*};
+    use flo_curves::BezierCurve;
+    use glam::vec2;
+
+    #[test]
+    fn quadratic_elevates_from_matching_endpoints() {
+        let mut g = BBGraph::new();
+        let (_, e) = g.quadratic(vec2(0., 0.), vec2(30., 60.), vec2(90., 0.));
+
+        let curve = e.as_curve(&g).unwrap();
+        let (ctrl1, ctrl2) = curve.control_points();
+        assert_eq!(c2_to_v2(curve.start_point()), vec2(0., 0.));
+        assert!(c2_to_v2(ctrl1).abs_diff_eq(vec2(20., 40.), 0.0001));
+        assert!(c2_to_v2(ctrl2).abs_diff_eq(vec2(50., 40.), 0.0001));
+        assert_eq!(c2_to_v2(curve.end_point()), vec2(90., 0.));
+
+        for i in 0..=10 {
+            let t = i as f32 / 10.;
+            let expected = e.t_point(&g, t);
+            let actual = c2_to_v2(curve.point_at_pos(t as f64));
+            assert!(
+                actual.abs_diff_eq(expected, 0.001),
+                "Expected {actual} to equal {expected} at t = {t}."
+            );
+        }
+    }
+}